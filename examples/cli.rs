@@ -2,8 +2,10 @@ extern crate keyring;
 
 use clap::Parser;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
-use keyring::{Entry, Error, Result};
+use keyring::export::{self, ExportDocument, ExportRecord};
+use keyring::{Credential, Entry, Error, Locked, Result, SecretAlphabet, SecretSpec};
 
 fn main() {
     let mut args: Cli = Cli::parse();
@@ -20,6 +22,12 @@ fn main() {
             std::process::exit(1)
         }
     };
+    let hook_command = command_hook_name(&args.command);
+    if let Err(err) = run_hook(&args, "pre", hook_command) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+    let mut clear_handle = None;
     match &args.command {
         Command::Set { .. } => {
             let (secret, password, attributes) = args.get_password_and_attributes();
@@ -28,14 +36,14 @@ fn main() {
                 std::process::exit(1);
             }
             if let Some(secret) = secret {
-                match entry.set_secret(&secret) {
-                    Ok(()) => args.success_message_for(Some(&secret), None, None),
+                match entry.set_secret(secret.expose()) {
+                    Ok(()) => args.success_message_for(Some(secret.expose()), None, None),
                     Err(err) => args.error_message_for(err),
                 }
             }
             if let Some(password) = password {
-                match entry.set_password(&password) {
-                    Ok(()) => args.success_message_for(None, Some(&password), None),
+                match entry.set_password(password.expose()) {
+                    Ok(()) => args.success_message_for(None, Some(password.expose()), None),
                     Err(err) => args.error_message_for(err),
                 }
             }
@@ -52,15 +60,17 @@ fn main() {
         }
         Command::Password => match entry.get_password() {
             Ok(password) => {
-                println!("{password}");
-                args.success_message_for(None, Some(&password), None);
+                let password = Locked::new(password);
+                clear_handle = args.emit(password.expose());
+                args.success_message_for(None, Some(password.expose()), None);
             }
             Err(err) => args.error_message_for(err),
         },
         Command::Secret => match entry.get_secret() {
             Ok(secret) => {
-                println!("{}", secret_string(&secret));
-                args.success_message_for(Some(&secret), None, None);
+                let secret = Locked::new(secret);
+                clear_handle = args.emit(&secret_string(secret.expose()));
+                args.success_message_for(Some(secret.expose()), None, None);
             }
             Err(err) => args.error_message_for(err),
         },
@@ -75,9 +85,431 @@ fn main() {
             Ok(()) => args.success_message_for(None, None, None),
             Err(err) => args.error_message_for(err),
         },
+        Command::Export { path, entries } => execute_export(&args, path, entries),
+        Command::Import { path } => execute_import(&args, path),
+        Command::Generate { .. } => execute_generate(&args, &entry),
+        Command::List { filter } => execute_list(filter),
+    }
+    // Block until any `--clear` restore has actually run: this is a one-shot
+    // process, so a detached thread sleeping past `main`'s return would just
+    // be killed with the process, silently skipping the advertised clear.
+    if let Some(handle) = clear_handle {
+        let _ = handle.join();
+    }
+    if let Err(err) = run_hook(&args, "post", hook_command) {
+        eprintln!("{err}");
+    }
+}
+
+/// The hook name a [Command] variant runs under, e.g. `pre_set`/`post_set`.
+fn command_hook_name(command: &Command) -> &'static str {
+    match command {
+        Command::Set { .. } => "set",
+        Command::Password => "password",
+        Command::Secret => "secret",
+        Command::Attributes => "attributes",
+        Command::Delete => "delete",
+        Command::Export { .. } => "export",
+        Command::Import { .. } => "import",
+        Command::Generate { .. } => "generate",
+        Command::List { .. } => "list",
     }
 }
 
+/// Run the `{stage}_{command}` script from `--hook-dir`, if one exists.
+///
+/// The hook is invoked with the current entry's target/service/user exposed
+/// as `KEYRING_TARGET`/`KEYRING_SERVICE`/`KEYRING_USER` environment
+/// variables, so it can look up or log whatever it needs without parsing
+/// argv. A missing hook file is not an error — hooks are opt-in per
+/// operation. A failing `pre` hook aborts the operation (its exit code
+/// becomes ours); a failing `post` hook only warns, since the underlying
+/// credential operation has already succeeded by then.
+fn run_hook(args: &Cli, stage: &str, command: &str) -> std::result::Result<(), String> {
+    let Some(hook_dir) = &args.hook_dir else {
+        return Ok(());
+    };
+    let path = hook_dir.join(format!("{stage}_{command}"));
+    if !path.is_file() {
+        return Ok(());
+    }
+    let status = std::process::Command::new(&path)
+        .env("KEYRING_COMMAND", command)
+        .env("KEYRING_STAGE", stage)
+        .env("KEYRING_SERVICE", &args.service)
+        .env("KEYRING_USER", &args.user)
+        .env("KEYRING_TARGET", args.target.clone().unwrap_or_default())
+        .status()
+        .map_err(|err| format!("Couldn't run {stage} hook '{}': {err}", path.display()))?;
+    if stage == "pre" && !status.success() {
+        return Err(format!("Aborting: '{}' exited with {status}", path.display()));
+    }
+    if stage == "post" && !status.success() {
+        eprintln!("Warning: '{}' exited with {status}", path.display());
+    }
+    Ok(())
+}
+
+/// Generate a password matching the requested policy, reject it if it shows
+/// up in [COMMON_PASSWORDS], and store the first acceptable candidate.
+fn execute_generate(args: &Cli, entry: &Entry) {
+    let Command::Generate {
+        length,
+        no_uppercase,
+        no_digits,
+        symbols,
+        avoid_ambiguous,
+    } = &args.command
+    else {
+        unreachable!("execute_generate is only called for Command::Generate")
+    };
+
+    let charset = generator_charset(!*no_uppercase, !*no_digits, *symbols, *avoid_ambiguous);
+    if charset.is_empty() {
+        eprintln!("The requested character set is empty; relax one of the exclusion flags");
+        std::process::exit(1);
+    }
+    let spec = SecretSpec {
+        length: *length,
+        alphabet: SecretAlphabet::Charset(charset),
+    };
+
+    let mut candidate = spec.generate();
+    let mut attempts = 1;
+    while is_common_password(&candidate) && attempts < MAX_GENERATE_ATTEMPTS {
+        candidate = spec.generate();
+        attempts += 1;
+    }
+    if is_common_password(&candidate) {
+        eprintln!("Couldn't generate a password outside the bundled common-password list");
+        std::process::exit(1);
+    }
+    let password = Locked::new(String::from_utf8(candidate).expect("generated charset is ASCII"));
+
+    match entry.set_password(password.expose()) {
+        Ok(()) => args.success_message_for(None, Some(password.expose()), None),
+        Err(err) => args.error_message_for(err),
+    }
+}
+
+/// How many times [execute_generate] will retry before giving up on finding
+/// a candidate outside [COMMON_PASSWORDS].
+const MAX_GENERATE_ATTEMPTS: u32 = 200;
+
+const LOWERCASE_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGIT_ALPHABET: &str = "0123456789";
+const SYMBOL_ALPHABET: &str = "!@#$%^&*()-_=+[]{};:,.?";
+/// Characters that are easily confused with one another in many fonts
+/// (`l`/`1`/`I`, `O`/`0`, etc), stripped out when `--avoid-ambiguous` is given.
+const AMBIGUOUS_CHARACTERS: &str = "il1IloO0S5B8";
+
+/// Build the character set a generated password is drawn from.
+fn generator_charset(uppercase: bool, digits: bool, symbols: bool, avoid_ambiguous: bool) -> String {
+    let mut charset = String::from(LOWERCASE_ALPHABET);
+    if uppercase {
+        charset.push_str(UPPERCASE_ALPHABET);
+    }
+    if digits {
+        charset.push_str(DIGIT_ALPHABET);
+    }
+    if symbols {
+        charset.push_str(SYMBOL_ALPHABET);
+    }
+    if avoid_ambiguous {
+        charset.retain(|c| !AMBIGUOUS_CHARACTERS.contains(c));
+    }
+    charset
+}
+
+/// Whether `candidate` (an ASCII password, as produced by [generator_charset])
+/// appears verbatim, case-insensitively, in the bundled common-password list.
+fn is_common_password(candidate: &[u8]) -> bool {
+    let candidate = String::from_utf8_lossy(candidate).to_lowercase();
+    COMMON_PASSWORDS
+        .binary_search(&candidate.as_str())
+        .is_ok()
+}
+
+/// A short, illustrative denylist of widely reused passwords, sorted for
+/// binary search. A real deployment would want to embed a much larger
+/// corpus (e.g. the top 10k from a breach corpus like HaveIBeenPwned) via
+/// `include_str!` instead; this list just keeps the most obvious picks —
+/// `password`, `123456`, the qwerty run, and so on — out of reach.
+const COMMON_PASSWORDS: &[&str] = &[
+    "111111",
+    "123123",
+    "12345",
+    "123456",
+    "1234567",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "1q2w3e4r",
+    "654321",
+    "666666",
+    "7777777",
+    "987654321",
+    "abc123",
+    "admin",
+    "asdfghjkl",
+    "bailey",
+    "baseball",
+    "dragon",
+    "football",
+    "freedom",
+    "google",
+    "iloveyou",
+    "letmein",
+    "login",
+    "master",
+    "monkey",
+    "passw0rd",
+    "password",
+    "password1",
+    "password123",
+    "princess",
+    "qazwsx",
+    "qwerty",
+    "qwerty123",
+    "qwertyuiop",
+    "shadow",
+    "solo",
+    "starwars",
+    "sunshine",
+    "superman",
+    "trustno1",
+    "welcome",
+    "whatever",
+    "zaq1zaq1",
+];
+
+/// Collect the named entries' secrets and attributes, seal them under an
+/// interactively-prompted passphrase, and write the result to `path`.
+fn execute_export(args: &Cli, path: &std::path::Path, entries: &[String]) {
+    if entries.is_empty() {
+        eprintln!("You must give at least one --entry to export");
+        std::process::exit(1);
+    }
+    let mut records = Vec::with_capacity(entries.len());
+    for spec in entries {
+        let (target, service, user) = match parse_entry_spec(spec) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                eprintln!("Couldn't parse entry '{spec}': {err}");
+                std::process::exit(1);
+            }
+        };
+        let entry = match target.as_deref() {
+            Some(target) => Entry::new_with_target(target, &service, &user),
+            None => Entry::new(&service, &user),
+        };
+        let entry = entry.unwrap_or_else(|err| {
+            eprintln!("Couldn't open entry '{spec}': {err}");
+            std::process::exit(1);
+        });
+        let secret = entry.get_secret().unwrap_or_else(|err| {
+            eprintln!("Couldn't read the secret for '{spec}': {err}");
+            std::process::exit(1);
+        });
+        let attributes = entry.get_attributes().unwrap_or_default();
+        records.push(ExportRecord {
+            target,
+            service,
+            user,
+            attributes,
+            secret,
+        });
+    }
+
+    let document = ExportDocument {
+        version: export::FORMAT_VERSION,
+        entries: records,
+    };
+    let json = export::to_json(&document).unwrap_or_else(|err| {
+        eprintln!("Couldn't serialize the export document: {err}");
+        std::process::exit(1);
+    });
+    let passphrase = Locked::new(
+        rpassword::prompt_password("Export passphrase: ").unwrap_or_else(|_| String::new()),
+    );
+    let sealed = export::seal(json.as_bytes(), passphrase.expose()).unwrap_or_else(|err| {
+        eprintln!("Couldn't seal the export document: {err}");
+        std::process::exit(1);
+    });
+    if let Err(err) = std::fs::write(path, &sealed) {
+        eprintln!("Couldn't write {}: {err}", path.display());
+        std::process::exit(1);
+    }
+    if args.verbose {
+        eprintln!(
+            "Exported {} entries to {}",
+            document.entries.len(),
+            path.display()
+        );
+    }
+}
+
+/// Decrypt a file previously written by [execute_export] and replay every
+/// record into its entry, reporting each record's outcome independently so
+/// one failure doesn't abort the rest of the import.
+fn execute_import(args: &Cli, path: &std::path::Path) {
+    let sealed = std::fs::read(path).unwrap_or_else(|err| {
+        eprintln!("Couldn't read {}: {err}", path.display());
+        std::process::exit(1);
+    });
+    let passphrase = Locked::new(
+        rpassword::prompt_password("Export passphrase: ").unwrap_or_else(|_| String::new()),
+    );
+    let json = export::unseal(&sealed, passphrase.expose()).unwrap_or_else(|err| {
+        eprintln!("Couldn't decrypt {}: {err}", path.display());
+        std::process::exit(1);
+    });
+    let json = String::from_utf8(json).unwrap_or_else(|_| {
+        eprintln!("{} does not contain valid UTF-8 JSON", path.display());
+        std::process::exit(1);
+    });
+    let document = export::from_json(&json).unwrap_or_else(|err| {
+        eprintln!("Couldn't parse {}: {err}", path.display());
+        std::process::exit(1);
+    });
+
+    for record in &document.entries {
+        let description = match &record.target {
+            Some(target) => format!("[{target}]{}@{}", record.user, record.service),
+            None => format!("{}@{}", record.user, record.service),
+        };
+        let result = import_one(record);
+        match result {
+            Ok(()) => {
+                if args.verbose {
+                    eprintln!("Imported '{description}'");
+                }
+            }
+            Err(err) => eprintln!("Couldn't import '{description}': {err}"),
+        }
+    }
+}
+
+/// Replay one [ExportRecord] onto the entry it names.
+fn import_one(record: &ExportRecord) -> Result<()> {
+    let entry = match record.target.as_deref() {
+        Some(target) => Entry::new_with_target(target, &record.service, &record.user)?,
+        None => Entry::new(&record.service, &record.user)?,
+    };
+    entry.set_secret(&record.secret)?;
+    if !record.attributes.is_empty() {
+        let attributes: HashMap<&str, &str> = record
+            .attributes
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        // a store that can't keep attributes shouldn't fail the import
+        let _ = entry.update_attributes(&attributes);
+    }
+    Ok(())
+}
+
+/// List every credential in the default store, optionally restricted to
+/// those whose recovered service or user starts with `filter`.
+fn execute_list(filter: &Option<String>) {
+    let entries = match Entry::search(&HashMap::new()) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("Couldn't enumerate the credential store: {err}");
+            std::process::exit(1);
+        }
+    };
+    let rows: Vec<(String, String, String, String)> = entries
+        .into_iter()
+        .filter_map(|entry| entry.get_attributes().ok())
+        .map(credential_row)
+        .filter(|(_, service, user, _)| match filter {
+            Some(prefix) => service.starts_with(prefix.as_str()) || user.starts_with(prefix.as_str()),
+            None => true,
+        })
+        .collect();
+    print_credential_table(&rows);
+}
+
+/// Render the credentials attached to an [Ambiguous](Error::Ambiguous) error
+/// as table rows, best-effort (a credential whose attributes can't be read
+/// is simply dropped from the listing rather than aborting the others).
+fn credential_rows(creds: Vec<Box<Credential>>) -> Vec<(String, String, String, String)> {
+    creds
+        .into_iter()
+        .filter_map(|cred| Entry::new_with_credential(cred).get_attributes().ok())
+        .map(credential_row)
+        .collect()
+}
+
+/// Recover a searched credential's identity from its attributes well enough
+/// to render it. Different backends key their attributes differently
+/// (`service`/`username` on Linux, `service`/`user` elsewhere, `target` on
+/// keyutils/libsecret_dynamic); this checks the conventional names and falls
+/// back to blanks rather than failing, since a blank column beats dropping
+/// the credential from the listing entirely.
+fn credential_row(attributes: HashMap<String, String>) -> (String, String, String, String) {
+    let target = attributes.get("target").cloned().unwrap_or_default();
+    let service = attributes.get("service").cloned().unwrap_or_default();
+    let user = attributes
+        .get("user")
+        .or_else(|| attributes.get("username"))
+        .cloned()
+        .unwrap_or_default();
+    (target, service, user, attributes_string(&attributes))
+}
+
+/// Print `rows` (target, service, user, attribute summary) as a table with
+/// columns aligned to the widest entry in each.
+fn print_credential_table(rows: &[(String, String, String, String)]) {
+    if rows.is_empty() {
+        eprintln!("No matching credentials found");
+        return;
+    }
+    let widths = [
+        column_width("TARGET", rows.iter().map(|row| &row.0)),
+        column_width("SERVICE", rows.iter().map(|row| &row.1)),
+        column_width("USER", rows.iter().map(|row| &row.2)),
+    ];
+    println!(
+        "{:w0$}  {:w1$}  {:w2$}  ATTRIBUTES",
+        "TARGET",
+        "SERVICE",
+        "USER",
+        w0 = widths[0],
+        w1 = widths[1],
+        w2 = widths[2]
+    );
+    for (target, service, user, attrs) in rows {
+        println!(
+            "{target:w0$}  {service:w1$}  {user:w2$}  {attrs}",
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        );
+    }
+}
+
+fn column_width<'a>(header: &str, values: impl Iterator<Item = &'a String>) -> usize {
+    values.map(String::len).chain([header.len()]).max().unwrap_or(0)
+}
+
+/// Parse an `--entry` argument of the form `[target@]service:user`.
+fn parse_entry_spec(spec: &str) -> std::result::Result<(Option<String>, String, String), String> {
+    let (target, rest) = match spec.split_once('@') {
+        Some((target, rest)) => (Some(target.to_string()), rest),
+        None => (None, spec),
+    };
+    let (service, user) = rest
+        .split_once(':')
+        .ok_or_else(|| "expected '[target@]service:user'".to_string())?;
+    if service.is_empty() || user.is_empty() {
+        return Err("expected '[target@]service:user'".to_string());
+    }
+    Ok((target, service.to_string(), user.to_string()))
+}
+
 #[derive(Debug, Parser)]
 #[clap(author = "github.com/hwchen/keyring-rs")]
 /// Keyring CLI: A command-line interface to platform secure storage
@@ -99,6 +531,24 @@ pub struct Cli {
     /// The user for the entry.
     pub user: String,
 
+    #[clap(long, action)]
+    /// Copy a retrieved password or secret to the clipboard instead of
+    /// printing it to standard output, so it never lands in terminal
+    /// scrollback or shell history.
+    pub clipboard: bool,
+
+    #[clap(long, value_parser)]
+    /// With `--clipboard`, overwrite the clipboard with its previous contents
+    /// (or clear it, if there were none) after this many seconds.
+    pub clear: Option<u64>,
+
+    #[clap(long, value_parser)]
+    /// Directory of pre/post hook scripts to run around this operation, named
+    /// `pre_<command>`/`post_<command>` (e.g. `pre_set`, `post_delete`). A
+    /// pre-hook that exits non-zero aborts the operation; post-hooks run
+    /// after success. See [run_hook] for the environment passed to each.
+    pub hook_dir: Option<PathBuf>,
+
     #[clap(subcommand)]
     pub command: Command,
 }
@@ -133,6 +583,50 @@ pub enum Command {
     /// Retrieve attributes available in the secure store.
     Attributes,
     Delete,
+    /// Export one or more entries into a single passphrase-sealed file.
+    Export {
+        /// Where to write the sealed export document.
+        path: PathBuf,
+
+        #[clap(long = "entry", value_parser)]
+        /// An entry to include, as `[target@]service:user`. Give this flag
+        /// once per entry; at least one is required.
+        entries: Vec<String>,
+    },
+    /// Import entries from a file previously written by `export`.
+    Import {
+        /// The sealed export document to read.
+        path: PathBuf,
+    },
+    /// Generate a strong random password and store it in the secure store.
+    Generate {
+        #[clap(short, long, value_parser, default_value = "20")]
+        /// The number of characters in the generated password.
+        length: usize,
+
+        #[clap(long, action)]
+        /// Exclude uppercase letters from the generated password.
+        no_uppercase: bool,
+
+        #[clap(long, action)]
+        /// Exclude digits from the generated password.
+        no_digits: bool,
+
+        #[clap(long, action)]
+        /// Include symbols (e.g. `!@#$%^&*`) in the generated password.
+        symbols: bool,
+
+        #[clap(long, action)]
+        /// Avoid visually ambiguous characters, like `l`, `1`, `I`, `O` and `0`.
+        avoid_ambiguous: bool,
+    },
+    /// List every credential in the default store as a table, optionally
+    /// restricted to those whose service or user starts with a prefix.
+    List {
+        #[clap(value_parser)]
+        /// Only list credentials whose service or user starts with this.
+        filter: Option<String>,
+    },
 }
 
 impl Cli {
@@ -144,6 +638,27 @@ impl Cli {
         }
     }
 
+    /// Emit a retrieved password or secret to the clipboard if `--clipboard`
+    /// was given, or to standard output otherwise.
+    ///
+    /// A clipboard failure (no display server, no clipboard feature compiled
+    /// in) falls back to printing, so the value is never silently lost. When
+    /// `--clear` was also given, the returned handle must be joined by the
+    /// caller before the process exits, or the restore never runs.
+    fn emit(&self, value: &str) -> Option<std::thread::JoinHandle<()>> {
+        if self.clipboard {
+            match clipboard::copy_with_clear(value, self.clear) {
+                Ok(handle) => {
+                    println!("(copied to clipboard)");
+                    return handle;
+                }
+                Err(err) => eprintln!("Couldn't copy to the clipboard, printing instead: {err}"),
+            }
+        }
+        println!("{value}");
+        None
+    }
+
     fn entry_for(&self) -> Result<Entry> {
         if let Some(target) = &self.target {
             Entry::new_with_target(target, &self.service, &self.user)
@@ -160,7 +675,8 @@ impl Cli {
                     eprintln!("No credential found for '{description}'");
                 }
                 Error::Ambiguous(creds) => {
-                    eprintln!("More than one credential found for '{description}': {creds:?}");
+                    eprintln!("More than one credential found for '{description}':");
+                    print_credential_table(&credential_rows(creds));
                 }
                 err => match self.command {
                     Command::Set { .. } => {
@@ -178,6 +694,17 @@ impl Cli {
                     Command::Delete => {
                         eprintln!("Couldn't delete credential for '{description}': {err}");
                     }
+                    Command::Generate { .. } => {
+                        eprintln!("Couldn't store the generated password for '{description}': {err}");
+                    }
+                    Command::List { .. } => {
+                        eprintln!("Couldn't enumerate the credential store: {err}");
+                    }
+                    Command::Export { .. } | Command::Import { .. } => {
+                        // these commands report their own per-record errors
+                        // and never call through this entry-oriented path
+                        unreachable!("export/import report their own errors")
+                    }
                 },
             }
         }
@@ -228,14 +755,28 @@ impl Cli {
             Command::Delete => {
                 eprintln!("Successfully deleted credential for '{description}'");
             }
+            Command::Generate { .. } => {
+                let pw = password.unwrap();
+                eprintln!("Generated and stored password for '{description}': '{pw}'");
+            }
+            Command::List { .. } => {
+                // execute_list renders its own table; it never calls through
+                // this entry-oriented path
+                unreachable!("list reports its own results")
+            }
+            Command::Export { .. } | Command::Import { .. } => {
+                // these commands report their own per-record success messages
+                // and never call through this entry-oriented path
+                unreachable!("export/import report their own success messages")
+            }
         }
     }
 
     fn get_password_and_attributes(
         &self,
     ) -> (
-        Option<Vec<u8>>,
-        Option<String>,
+        Option<Locked<Vec<u8>>>,
+        Option<Locked<String>>,
         Option<HashMap<String, String>>,
     ) {
         if let Command::Set {
@@ -274,7 +815,7 @@ fn eprint_attributes(attributes: HashMap<String, String>) {
     }
 }
 
-fn decode_secret(input: &Option<String>) -> Vec<u8> {
+fn decode_secret(input: &Option<String>) -> Locked<Vec<u8>> {
     use base64::prelude::*;
 
     let encoded = if let Some(input) = input {
@@ -283,10 +824,10 @@ fn decode_secret(input: &Option<String>) -> Vec<u8> {
         rpassword::prompt_password("Base64 encoding: ").unwrap_or_else(|_| String::new())
     };
     if encoded.is_empty() {
-        return Vec::new();
+        return Locked::new(Vec::new());
     }
     match BASE64_STANDARD.decode(encoded) {
-        Ok(secret) => secret,
+        Ok(secret) => Locked::new(secret),
         Err(err) => {
             eprintln!("Sorry, the provided secret data is not base64-encoded: {err}");
             std::process::exit(1);
@@ -294,13 +835,13 @@ fn decode_secret(input: &Option<String>) -> Vec<u8> {
     }
 }
 
-fn read_password(input: &Option<String>) -> String {
+fn read_password(input: &Option<String>) -> Locked<String> {
     let password = if let Some(input) = input {
         input.clone()
     } else {
         rpassword::prompt_password("Password: ").unwrap_or_else(|_| String::new())
     };
-    password
+    Locked::new(password)
 }
 
 fn attributes_string(attributes: &HashMap<String, String>) -> String {
@@ -327,3 +868,60 @@ fn parse_attributes(input: &String) -> Option<HashMap<String, String>> {
     }
     Some(attributes)
 }
+
+/// Clipboard support for `--clipboard`/`--clear`, gated behind the
+/// `clipboard` feature so headless builds of this example (CI, containers
+/// with no display server) don't need to link a clipboard backend.
+#[cfg(feature = "clipboard")]
+mod clipboard {
+    use std::thread::JoinHandle;
+    use std::time::Duration;
+
+    /// Copy `text` to the OS clipboard.
+    ///
+    /// If `clear_after` is given, spawn a thread that restores whatever the
+    /// clipboard held before this call (or clears it, if there was nothing)
+    /// once the timeout elapses, and return its handle so a copied secret
+    /// doesn't sit there indefinitely. Since this is a one-shot CLI process,
+    /// the caller must join the handle before exiting `main`, or the restore
+    /// is killed along with the process before it can run.
+    pub fn copy_with_clear(
+        text: &str,
+        clear_after: Option<u64>,
+    ) -> std::result::Result<Option<JoinHandle<()>>, String> {
+        let mut clipboard = arboard::Clipboard::new().map_err(|err| err.to_string())?;
+        let previous = clipboard.get_text().ok();
+        clipboard
+            .set_text(text.to_string())
+            .map_err(|err| err.to_string())?;
+
+        let handle = clear_after.map(|seconds| {
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_secs(seconds));
+                if let Ok(mut clipboard) = arboard::Clipboard::new() {
+                    match previous {
+                        Some(text) => {
+                            let _ = clipboard.set_text(text);
+                        }
+                        None => {
+                            let _ = clipboard.clear();
+                        }
+                    }
+                }
+            })
+        });
+        Ok(handle)
+    }
+}
+
+/// Without the `clipboard` feature, `--clipboard` always reports itself
+/// unavailable so [Cli::emit] falls back to printing.
+#[cfg(not(feature = "clipboard"))]
+mod clipboard {
+    pub fn copy_with_clear(
+        _text: &str,
+        _clear_after: Option<u64>,
+    ) -> std::result::Result<Option<std::thread::JoinHandle<()>>, String> {
+        Err("this build was compiled without the 'clipboard' feature".to_string())
+    }
+}
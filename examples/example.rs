@@ -17,7 +17,7 @@ fn main() -> Result<()> {
         ),
         Err(err) => panic!("Could not get password: {}", err),
     }
-    if let Err(err) = keyring.delete_password() {
+    if let Err(err) = keyring.delete_credential() {
         panic!("Could not delete password: {}", err);
     }
     assert!(
@@ -22,7 +22,7 @@ fn test_empty_password_input() {
     entry.set_password(in_pass).unwrap();
     let out_pass = entry.get_password().unwrap();
     assert_eq!(in_pass, out_pass);
-    entry.delete_password().unwrap();
+    entry.delete_credential().unwrap();
     assert!(
         matches!(entry.get_password(), Err(Error::NoEntry)),
         "Able to read a deleted password"
@@ -36,7 +36,7 @@ fn test_round_trip_ascii_password() {
     entry.set_password(password).unwrap();
     let stored_password = entry.get_password().unwrap();
     assert_eq!(stored_password, password);
-    entry.delete_password().unwrap();
+    entry.delete_credential().unwrap();
     assert!(matches!(entry.get_password(), Err(Error::NoEntry)))
 }
 
@@ -47,7 +47,7 @@ fn test_round_trip_non_ascii_password() {
     entry.set_password(password).unwrap();
     let stored_password = entry.get_password().unwrap();
     assert_eq!(stored_password, password);
-    entry.delete_password().unwrap();
+    entry.delete_credential().unwrap();
     assert!(matches!(entry.get_password(), Err(Error::NoEntry)))
 }
 
@@ -62,6 +62,6 @@ fn test_update_password() {
     entry.set_password(password).unwrap();
     let stored_password = entry.get_password().unwrap();
     assert_eq!(stored_password, password);
-    entry.delete_password().unwrap();
+    entry.delete_credential().unwrap();
     assert!(matches!(entry.get_password(), Err(Error::NoEntry)))
 }
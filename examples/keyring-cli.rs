@@ -1,8 +1,11 @@
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
 use rpassword::read_password_from_tty;
 use structopt::StructOpt;
 
 extern crate keyring;
-use keyring::{Entry, Error};
+use keyring::{Entry, Error, SecretSpec};
 
 #[derive(Debug, StructOpt)]
 #[structopt(about = "A utility to access platform secure storage")]
@@ -38,7 +41,43 @@ pub enum Command {
         password: Option<String>,
     },
     /// Get the password from the secure store
-    Get,
+    Get {
+        #[structopt(long)]
+        /// If no credential exists yet, generate a random password of this
+        /// many characters, store it, and print it.
+        generate: Option<usize>,
+    },
+    /// Set a binary secret in the secure store
+    SetSecret {
+        #[structopt(long)]
+        /// Read the secret from the given file instead of the argument.
+        file: Option<PathBuf>,
+
+        #[structopt(long)]
+        /// Read the secret from standard input instead of the argument.
+        stdin: bool,
+
+        #[structopt(long)]
+        /// The input is base64-encoded and is decoded before being stored.
+        base64: bool,
+
+        #[structopt(long)]
+        /// The input is hex-encoded and is decoded before being stored.
+        hex: bool,
+
+        /// The secret to set, as a literal argument.
+        secret: Option<String>,
+    },
+    /// Get a binary secret from the secure store and write it to standard output
+    GetSecret {
+        #[structopt(long)]
+        /// Emit the secret as base64 instead of raw bytes.
+        base64: bool,
+
+        #[structopt(long)]
+        /// Emit the secret as hex instead of raw bytes.
+        hex: bool,
+    },
     /// Delete the entry from the secure store
     Delete,
 }
@@ -63,7 +102,18 @@ fn execute_args(args: &KeyringCli) {
                 eprintln!("(Failed to read password, so none set.)")
             }
         }
-        Command::Get => execute_get_password(&entry),
+        Command::Get { generate: None } => execute_get_password(&entry),
+        Command::Get {
+            generate: Some(length),
+        } => execute_get_or_create_password(&entry, *length),
+        Command::SetSecret {
+            file,
+            stdin,
+            base64,
+            hex,
+            secret,
+        } => execute_set_secret(&entry, file.as_deref(), *stdin, *base64, *hex, secret.as_deref()),
+        Command::GetSecret { base64, hex } => execute_get_secret(&entry, *base64, *hex),
         Command::Delete => execute_delete_password(&entry),
     }
 }
@@ -85,8 +135,131 @@ fn execute_get_password(entry: &Entry) {
     }
 }
 
+fn execute_get_or_create_password(entry: &Entry, length: usize) {
+    match entry.get_or_create_password(&SecretSpec::password(length)) {
+        Ok(password) => println!("Password is '{}'", &password),
+        Err(Error::NoStorageAccess(err)) => eprintln!("Couldn't retrieve the password: {}", err),
+        Err(err) => eprintln!("Unexpected error retrieving the password: {}", err),
+    }
+}
+
+fn execute_set_secret(
+    entry: &Entry,
+    file: Option<&std::path::Path>,
+    stdin: bool,
+    base64: bool,
+    hex: bool,
+    secret: Option<&str>,
+) {
+    let raw = if let Some(file) = file {
+        match std::fs::read(file) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                eprintln!("Couldn't read the secret from {}: {}", file.display(), err);
+                return;
+            }
+        }
+    } else if stdin {
+        let mut bytes = Vec::new();
+        if let Err(err) = std::io::stdin().read_to_end(&mut bytes) {
+            eprintln!("Couldn't read the secret from standard input: {}", err);
+            return;
+        }
+        bytes
+    } else if let Some(secret) = secret {
+        secret.as_bytes().to_vec()
+    } else {
+        eprintln!("No secret provided: give it as an argument, or pass --file or --stdin");
+        return;
+    };
+    let secret = match decode_secret(raw, base64, hex) {
+        Ok(secret) => secret,
+        Err(err) => {
+            eprintln!("Couldn't decode the secret: {}", err);
+            return;
+        }
+    };
+    match entry.set_secret(&secret) {
+        Ok(()) => println!("Secret set successfully"),
+        Err(Error::NoStorageAccess(err)) => eprintln!("Couldn't set the secret: {}", err),
+        Err(err) => eprintln!("Unexpected error setting the secret: {}", err),
+    }
+}
+
+fn execute_get_secret(entry: &Entry, base64: bool, hex: bool) {
+    match entry.get_secret() {
+        Ok(secret) => {
+            let encoded = encode_secret(&secret, base64, hex);
+            if let Err(err) = std::io::stdout().write_all(&encoded) {
+                eprintln!("Couldn't write the secret to standard output: {}", err);
+            }
+        }
+        Err(Error::NoEntry) => eprintln!("(No secret found)"),
+        Err(Error::NoStorageAccess(err)) => eprintln!("Couldn't retrieve the secret: {}", err),
+        Err(err) => eprintln!("Unexpected error retrieving the secret: {}", err),
+    }
+}
+
+/// Decode the raw input bytes according to the encoding flags.
+///
+/// At most one of `base64`/`hex` is honored; when neither is set the
+/// bytes are stored verbatim.
+fn decode_secret(raw: Vec<u8>, base64: bool, hex: bool) -> Result<Vec<u8>, String> {
+    use base64::prelude::*;
+
+    if base64 {
+        let text = String::from_utf8(raw).map_err(|err| err.to_string())?;
+        BASE64_STANDARD
+            .decode(text.trim())
+            .map_err(|err| err.to_string())
+    } else if hex {
+        let text = String::from_utf8(raw).map_err(|err| err.to_string())?;
+        decode_hex(text.trim())
+    } else {
+        Ok(raw)
+    }
+}
+
+/// Encode the stored secret for output according to the encoding flags.
+///
+/// Base64/hex output is newline-terminated so it is usable in pipelines;
+/// raw output is emitted byte-for-byte.
+fn encode_secret(secret: &[u8], base64: bool, hex: bool) -> Vec<u8> {
+    use base64::prelude::*;
+
+    if base64 {
+        let mut out = BASE64_STANDARD.encode(secret).into_bytes();
+        out.push(b'\n');
+        out
+    } else if hex {
+        let mut out = encode_hex(secret).into_bytes();
+        out.push(b'\n');
+        out
+    } else {
+        secret.to_vec()
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push_str(&format!("{:02x}", byte));
+    }
+    out
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>, String> {
+    if text.len() % 2 != 0 {
+        return Err("hex input has an odd number of digits".to_string());
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|err| err.to_string()))
+        .collect()
+}
+
 fn execute_delete_password(entry: &Entry) {
-    match entry.delete_password() {
+    match entry.delete_credential() {
         Ok(()) => println!("(Password deleted)"),
         Err(Error::NoEntry) => eprintln!("(No password found)"),
         Err(Error::NoStorageAccess(err)) => eprintln!("Couldn't delete the password: {}", err),
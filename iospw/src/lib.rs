@@ -20,8 +20,11 @@
 //! [this article](https://mozilla.github.io/firefox-browser-architecture/experiments/2017-09-06-rust-on-ios.html),
 //! but be aware that it was written long enough ago that some of the processor
 //! architectures it refers to are no longer in use.
+use std::collections::HashMap;
+
 use core_foundation::base::{CFRetain, OSStatus, TCFType};
 use core_foundation::data::{CFData, CFDataRef};
+use core_foundation::dictionary::{CFDictionary, CFDictionaryRef};
 use core_foundation::string::{CFString, CFStringRef};
 
 extern crate keyring;
@@ -124,6 +127,190 @@ pub unsafe extern "C" fn KeyringCopyPassword(
     }
 }
 
+/// Set a generic password for the given service and account, gating later
+/// reads behind a device-local authentication policy.
+///
+/// The `policy` argument selects the access-control flag applied when the
+/// item is created: `0` requires user presence (biometry or passcode), `1`
+/// requires the currently enrolled biometry, and `2` requires the device
+/// passcode.  Any other value returns `errSecParam`.  Reading the secret then
+/// prompts the user for the corresponding authentication.
+#[no_mangle]
+pub extern "C" fn KeyringSetPasswordWithPolicy(
+    service: CFStringRef,
+    user: CFStringRef,
+    password: CFStringRef,
+    policy: u32,
+) -> OSStatus {
+    if service.is_null() || user.is_null() || password.is_null() {
+        return errSecParam;
+    }
+    if policy > 2 {
+        return errSecParam;
+    }
+    let service = unsafe { CFString::wrap_under_get_rule(service) }.to_string();
+    let account = unsafe { CFString::wrap_under_get_rule(user) }.to_string();
+    let password = unsafe { CFString::wrap_under_get_rule(password) }.to_string();
+    let entry = Entry::new(&service, &account);
+    match entry.set_password_with_policy(&password, policy) {
+        Ok(_) => errSecSuccess,
+        Err(Error::PlatformFailure(err)) => err.code(),
+        Err(Error::NoStorageAccess(err)) => err.code(),
+        Err(_) => errSecBadReq,
+    }
+}
+
+/// Set a generic binary secret for the given service and account.
+/// Creates or updates a keychain entry.  Unlike [KeyringSetPassword], the
+/// secret is stored as raw bytes, so it may contain NUL bytes or otherwise
+/// not be valid UTF8.
+/// Otherwise, an appropriate error status is returned.
+#[no_mangle]
+pub extern "C" fn KeyringSetSecret(
+    service: CFStringRef,
+    user: CFStringRef,
+    secret: CFDataRef,
+) -> OSStatus {
+    if service.is_null() || user.is_null() || secret.is_null() {
+        return errSecParam;
+    }
+    let service = unsafe { CFString::wrap_under_get_rule(service) }.to_string();
+    let account = unsafe { CFString::wrap_under_get_rule(user) }.to_string();
+    let secret = unsafe { CFData::wrap_under_get_rule(secret) };
+    let entry = Entry::new(&service, &account);
+    match entry.set_secret(secret.bytes()) {
+        Ok(_) => errSecSuccess,
+        Err(Error::PlatformFailure(err)) => err.code(),
+        Err(Error::NoStorageAccess(err)) => err.code(),
+        Err(_) => errSecBadReq,
+    }
+}
+
+/// Get the binary secret for the given service and account.
+/// If no keychain entry exists, returns `errSecItemNotFound`.
+/// Otherwise, returns an appropriate error status (with no secret).
+///
+/// # Safety
+/// The `secret` argument is a mutable pointer to a CFDataRef, used as an
+/// input-output variable following the same conventions as the `password`
+/// argument to [KeyringCopyPassword]: it should come in either as nil or as
+/// the address of a CFDataRef whose value is nil.  If it is non-nil and a
+/// secret is found, a new CFData item is allocated, retained, and assigned
+/// through the pointer; the prior value is not freed.
+#[no_mangle]
+pub unsafe extern "C" fn KeyringCopySecret(
+    service: CFStringRef,
+    user: CFStringRef,
+    secret: *mut CFDataRef,
+) -> OSStatus {
+    if service.is_null() || user.is_null() {
+        return errSecBadReq;
+    }
+    let service = CFString::wrap_under_get_rule(service).to_string();
+    let account = CFString::wrap_under_get_rule(user).to_string();
+    let entry = Entry::new(&service, &account);
+    match entry.get_secret() {
+        Ok(bytes) => {
+            if !secret.is_null() {
+                let data = CFData::from_buffer(&bytes);
+                // take an extra retain count to hand to our caller
+                CFRetain(data.as_CFTypeRef());
+                *secret = data.as_concrete_TypeRef();
+            }
+            errSecSuccess
+        }
+        Err(Error::NoEntry) => errSecItemNotFound,
+        Err(Error::PlatformFailure(err)) => err.code(),
+        Err(Error::NoStorageAccess(err)) => err.code(),
+        Err(_) => errSecBadReq,
+    }
+}
+
+/// Get the platform attributes for the given service and account as a
+/// dictionary of string keys to string values.
+/// If no keychain entry exists, returns `errSecItemNotFound`.
+/// Otherwise, returns an appropriate error status (with no attributes).
+///
+/// # Safety
+/// The `attributes` argument is a mutable pointer to a CFDictionaryRef, used
+/// as an input-output variable following the same conventions as the
+/// `password` argument to [KeyringCopyPassword]: it should come in either as
+/// nil or as the address of a CFDictionaryRef whose value is nil.  If it is
+/// non-nil, a new CFDictionary mapping CFString keys to CFString values is
+/// allocated, retained, and assigned through the pointer; the prior value is
+/// not freed.
+#[no_mangle]
+pub unsafe extern "C" fn KeyringCopyAttributes(
+    service: CFStringRef,
+    user: CFStringRef,
+    attributes: *mut CFDictionaryRef,
+) -> OSStatus {
+    if service.is_null() || user.is_null() {
+        return errSecBadReq;
+    }
+    let service = CFString::wrap_under_get_rule(service).to_string();
+    let account = CFString::wrap_under_get_rule(user).to_string();
+    let entry = Entry::new(&service, &account);
+    match entry.get_attributes() {
+        Ok(map) => {
+            if !attributes.is_null() {
+                let pairs: Vec<(CFString, CFString)> = map
+                    .iter()
+                    .map(|(key, value)| (CFString::new(key), CFString::new(value)))
+                    .collect();
+                let dictionary = CFDictionary::from_CFType_pairs(&pairs);
+                // take an extra retain count to hand to our caller
+                CFRetain(dictionary.as_CFTypeRef());
+                *attributes = dictionary.as_concrete_TypeRef();
+            }
+            errSecSuccess
+        }
+        Err(Error::NoEntry) => errSecItemNotFound,
+        Err(Error::PlatformFailure(err)) => err.code(),
+        Err(Error::NoStorageAccess(err)) => err.code(),
+        Err(_) => errSecBadReq,
+    }
+}
+
+/// Update the platform attributes for the given service and account from a
+/// dictionary of string keys to string values.  Keys or values that are not
+/// CFStrings are ignored.
+/// If no keychain entry exists, returns `errSecItemNotFound`.
+/// Otherwise, an appropriate error status is returned.
+#[no_mangle]
+pub extern "C" fn KeyringUpdateAttributes(
+    service: CFStringRef,
+    user: CFStringRef,
+    attributes: CFDictionaryRef,
+) -> OSStatus {
+    if service.is_null() || user.is_null() || attributes.is_null() {
+        return errSecParam;
+    }
+    let service = unsafe { CFString::wrap_under_get_rule(service) }.to_string();
+    let account = unsafe { CFString::wrap_under_get_rule(user) }.to_string();
+    let dictionary: CFDictionary = unsafe { CFDictionary::wrap_under_get_rule(attributes) };
+    // collect owned strings first, then borrow them for update_attributes
+    let mut owned: HashMap<String, String> = HashMap::new();
+    let (keys, values) = dictionary.get_keys_and_values();
+    for (key, value) in keys.into_iter().zip(values) {
+        let key = unsafe { CFString::wrap_under_get_rule(key as CFStringRef) };
+        let value = unsafe { CFString::wrap_under_get_rule(value as CFStringRef) };
+        owned.insert(key.to_string(), value.to_string());
+    }
+    let borrowed: HashMap<&str, &str> = owned
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    let entry = Entry::new(&service, &account);
+    match entry.update_attributes(&borrowed) {
+        Ok(_) => errSecSuccess,
+        Err(Error::NoEntry) => errSecItemNotFound,
+        Err(Error::PlatformFailure(err)) => err.code(),
+        Err(Error::NoStorageAccess(err)) => err.code(),
+        Err(_) => errSecBadReq,
+    }
+}
+
 /// Delete the keychain entry for the given service and account.  If none
 /// exists, returns `errSecItemNotFound`.
 /// Otherwise, an appropriate error status is returned.
@@ -135,7 +322,7 @@ pub extern "C" fn KeyringDeletePassword(service: CFStringRef, user: CFStringRef)
     let service = unsafe { CFString::wrap_under_get_rule(service) }.to_string();
     let account = unsafe { CFString::wrap_under_get_rule(user) }.to_string();
     let entry = Entry::new(&service, &account);
-    match entry.delete_password() {
+    match entry.delete_credential() {
         Ok(_) => errSecSuccess,
         Err(Error::NoEntry) => errSecItemNotFound,
         Err(Error::PlatformFailure(err)) => err.code(),
@@ -17,7 +17,7 @@ fn test_empty_password_input() {
     entry.set_password(in_pass).unwrap();
     let out_pass = entry.get_password().unwrap();
     assert_eq!(in_pass, out_pass);
-    entry.delete_password().unwrap();
+    entry.delete_credential().unwrap();
     assert!(
         matches!(entry.get_password(), Err(Error::NoEntry)),
         "Able to read a deleted password"
@@ -32,7 +32,7 @@ fn test_round_trip_ascii_password() {
     entry.set_password(password).unwrap();
     let stored_password = entry.get_password().unwrap();
     assert_eq!(stored_password, password);
-    entry.delete_password().unwrap();
+    entry.delete_credential().unwrap();
     assert!(matches!(entry.get_password(), Err(Error::NoEntry)))
 }
 
@@ -44,7 +44,7 @@ fn test_round_trip_non_ascii_password() {
     entry.set_password(password).unwrap();
     let stored_password = entry.get_password().unwrap();
     assert_eq!(stored_password, password);
-    entry.delete_password().unwrap();
+    entry.delete_credential().unwrap();
     assert!(matches!(entry.get_password(), Err(Error::NoEntry)))
 }
 
@@ -61,7 +61,7 @@ fn test_independent_credential_and_password() {
     let (stored_password, credential2) = entry.get_password_and_credential().unwrap();
     assert_eq!(stored_password, password);
     assert_eq!(credential1, credential2);
-    entry.delete_password().unwrap();
+    entry.delete_credential().unwrap();
     assert!(
         matches!(entry.get_password(), Err(Error::NoEntry)),
         "Able to read a deleted password"
@@ -78,8 +78,8 @@ fn test_same_target() {
     entry1.set_password(&password1).unwrap();
     let password2 = entry2.get_password().unwrap();
     assert_eq!(password2, password1);
-    entry1.delete_password().unwrap();
-    assert!(matches!(entry2.delete_password(), Err(Error::NoEntry)))
+    entry1.delete_credential().unwrap();
+    assert!(matches!(entry2.delete_credential(), Err(Error::NoEntry)))
 }
 
 fn generate_random_string() -> String {
@@ -0,0 +1,318 @@
+/*!
+
+# Asynchronous entries
+
+The [async Secret Service](crate::secret_service) store is asynchronous all the
+way down: its native view ([AsyncSsCredential](crate::secret_service::AsyncSsCredential))
+talks non-blocking DBus and implements [AsyncCredentialApi](crate::credential::AsyncCredentialApi).
+The blocking [Entry](crate::Entry) can only drive it by parking a thread on a
+runtime, which the secret-service caveats call out as fragile for RPC backends.
+
+This module mirrors the blocking surface with an [AsyncEntry] whose methods are
+`async fn`s you `.await` directly.  Because `async fn` in a trait is not
+object-safe, entries hold a boxed [AsyncCredential] — the object-safe,
+boxed-future view of [AsyncCredentialApi](crate::credential::AsyncCredentialApi)
+provided here — so any async credential can back an entry behind one type.
+Synchronous stores are adapted with [BlockingCredential], which satisfies the
+async trait by calling the blocking API inline; this is what
+[AsyncEntry::new] falls back to when no async builder is installed.
+
+For UI event loops that cannot block or `.await` at all, [poll_once] drives a
+future a single step and reports a [KeyStorageResponse]: `Waiting` if the
+operation has not finished (retry on the next frame) or `Ready` with the
+result.  The shape follows the `KeyStorageResponse` enum used by GUI key
+storage layers such as notedeck's.
+ */
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::RwLock;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use super::credential::{
+    AsyncCredentialApi, Credential, CredentialBuilder, CredentialPersistence,
+};
+use super::error::Result;
+
+/// A boxed, `Send` future with the lifetime of its borrow.
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// The object-safe form of [AsyncCredentialApi](crate::credential::AsyncCredentialApi).
+///
+/// Each method returns a boxed future instead of using `async fn`, so the trait
+/// is object-safe and entries can hold a `Box<dyn DynAsyncCredentialApi>`.  A
+/// blanket implementation adapts every [AsyncCredentialApi](crate::credential::AsyncCredentialApi)
+/// type, so backends implement only the ergonomic `async fn` trait.
+pub trait DynAsyncCredentialApi {
+    /// Set the credential's password.
+    fn set_password<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<()>>;
+    /// Set the credential's secret.
+    fn set_secret<'a>(&'a self, secret: &'a [u8]) -> BoxFuture<'a, Result<()>>;
+    /// Retrieve the credential's password.
+    fn get_password(&self) -> BoxFuture<'_, Result<String>>;
+    /// Retrieve the credential's secret.
+    fn get_secret(&self) -> BoxFuture<'_, Result<Vec<u8>>>;
+    /// Delete the underlying credential.
+    fn delete_credential(&self) -> BoxFuture<'_, Result<()>>;
+    /// Return the concrete object cast to [Any](std::any::Any).
+    fn as_any(&self) -> &dyn std::any::Any;
+}
+
+impl<T: AsyncCredentialApi + Send + Sync> DynAsyncCredentialApi for T {
+    fn set_password<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(AsyncCredentialApi::set_password(self, password))
+    }
+    fn set_secret<'a>(&'a self, secret: &'a [u8]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(AsyncCredentialApi::set_secret(self, secret))
+    }
+    fn get_password(&self) -> BoxFuture<'_, Result<String>> {
+        Box::pin(AsyncCredentialApi::get_password(self))
+    }
+    fn get_secret(&self) -> BoxFuture<'_, Result<Vec<u8>>> {
+        Box::pin(AsyncCredentialApi::get_secret(self))
+    }
+    fn delete_credential(&self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(AsyncCredentialApi::delete_credential(self))
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        AsyncCredentialApi::as_any(self)
+    }
+}
+
+/// A thread-safe, object-safe async credential, the async analogue of [Credential](crate::Credential).
+pub type AsyncCredential = dyn DynAsyncCredentialApi + Send + Sync;
+
+/// The async analogue of [CredentialBuilderApi](crate::credential::CredentialBuilderApi).
+pub trait AsyncCredentialBuilderApi {
+    /// Build an async credential for the given target, service, and user.
+    fn build<'a>(
+        &'a self,
+        target: Option<&'a str>,
+        service: &'a str,
+        user: &'a str,
+    ) -> BoxFuture<'a, Result<Box<AsyncCredential>>>;
+
+    /// Return the concrete object cast to [Any](std::any::Any).
+    fn as_any(&self) -> &dyn std::any::Any;
+
+    /// The lifetime of credentials produced by this builder.
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+}
+
+/// A thread-safe async credential builder.
+pub type AsyncCredentialBuilder = dyn AsyncCredentialBuilderApi + Send + Sync;
+
+/// An adapter that presents a blocking [Credential](crate::Credential) as an
+/// [AsyncCredentialApi](crate::credential::AsyncCredentialApi).
+///
+/// The blocking calls run inline on the awaiting task.  This is appropriate for
+/// local stores (keyutils, the encrypted file store, the mock) whose operations
+/// don't block on IO; for an RPC store, prefer a native async credential.
+pub struct BlockingCredential {
+    inner: Box<Credential>,
+}
+
+impl BlockingCredential {
+    /// Wrap a blocking credential in its async adapter.
+    pub fn new(inner: Box<Credential>) -> Self {
+        Self { inner }
+    }
+}
+
+impl AsyncCredentialApi for BlockingCredential {
+    async fn set_password(&self, password: &str) -> Result<()> {
+        self.inner.set_password(password)
+    }
+    async fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        self.inner.set_secret(secret)
+    }
+    async fn get_password(&self) -> Result<String> {
+        self.inner.get_password()
+    }
+    async fn get_secret(&self) -> Result<Vec<u8>> {
+        self.inner.get_secret()
+    }
+    async fn delete_credential(&self) -> Result<()> {
+        self.inner.delete_credential()
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// An adapter that presents a blocking [CredentialBuilder](crate::CredentialBuilder)
+/// as an [AsyncCredentialBuilder].
+pub struct BlockingCredentialBuilder {
+    inner: Box<CredentialBuilder>,
+}
+
+impl BlockingCredentialBuilder {
+    /// Wrap a blocking credential builder in its async adapter.
+    pub fn new(inner: Box<CredentialBuilder>) -> Self {
+        Self { inner }
+    }
+}
+
+impl AsyncCredentialBuilderApi for BlockingCredentialBuilder {
+    fn build<'a>(
+        &'a self,
+        target: Option<&'a str>,
+        service: &'a str,
+        user: &'a str,
+    ) -> BoxFuture<'a, Result<Box<AsyncCredential>>> {
+        Box::pin(async move {
+            let credential = self.inner.build(target, service, user)?;
+            Ok(Box::new(BlockingCredential::new(credential)) as Box<AsyncCredential>)
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn persistence(&self) -> CredentialPersistence {
+        self.inner.persistence()
+    }
+}
+
+static DEFAULT_ASYNC_BUILDER: RwLock<Option<Box<AsyncCredentialBuilder>>> = RwLock::new(None);
+
+/// Set the async credential builder used by default to create [AsyncEntry] values.
+///
+/// This is the async analogue of
+/// [set_default_credential_builder](crate::set_default_credential_builder); call
+/// it at startup before creating entries.  When no async builder is installed,
+/// [AsyncEntry::new] adapts the blocking default builder with
+/// [BlockingCredentialBuilder].
+pub fn set_default_async_credential_builder(new: Box<AsyncCredentialBuilder>) {
+    let mut guard = DEFAULT_ASYNC_BUILDER
+        .write()
+        .expect("Poisoned RwLock in keyring-rs: please report a bug!");
+    *guard = Some(new);
+}
+
+/// Build an async credential from the installed builder, or the adapted default.
+async fn build_default_async_credential(
+    target: Option<&str>,
+    service: &str,
+    user: &str,
+) -> Result<Box<AsyncCredential>> {
+    let installed = {
+        let guard = DEFAULT_ASYNC_BUILDER
+            .read()
+            .expect("Poisoned RwLock in keyring-rs: please report a bug!");
+        guard.is_some()
+    };
+    if installed {
+        let guard = DEFAULT_ASYNC_BUILDER
+            .read()
+            .expect("Poisoned RwLock in keyring-rs: please report a bug!");
+        let builder = guard.as_ref().unwrap();
+        return builder.build(target, service, user).await;
+    }
+    let builder = BlockingCredentialBuilder::new(crate::default::default_credential_builder());
+    builder.build(target, service, user).await
+}
+
+/// The async analogue of [Entry](crate::Entry).
+///
+/// Its methods `.await` the underlying [AsyncCredential] directly instead of
+/// blocking a thread.  Construct one with [AsyncEntry::new] (using the default
+/// async builder) or [AsyncEntry::new_with_credential] (supplying your own
+/// async credential).
+pub struct AsyncEntry {
+    inner: Box<AsyncCredential>,
+}
+
+impl AsyncEntry {
+    /// Create an async entry for the given service and user.
+    pub async fn new(service: &str, user: &str) -> Result<AsyncEntry> {
+        let inner = build_default_async_credential(None, service, user).await?;
+        Ok(AsyncEntry { inner })
+    }
+
+    /// Create an async entry for the given target, service, and user.
+    pub async fn new_with_target(target: &str, service: &str, user: &str) -> Result<AsyncEntry> {
+        let inner = build_default_async_credential(Some(target), service, user).await?;
+        Ok(AsyncEntry { inner })
+    }
+
+    /// Create an async entry backed by the given async credential.
+    pub fn new_with_credential(credential: Box<AsyncCredential>) -> AsyncEntry {
+        AsyncEntry { inner: credential }
+    }
+
+    /// Set the password for this entry.
+    pub async fn set_password(&self, password: &str) -> Result<()> {
+        self.inner.set_password(password).await
+    }
+
+    /// Set the secret for this entry.
+    pub async fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        self.inner.set_secret(secret).await
+    }
+
+    /// Retrieve the password saved for this entry.
+    pub async fn get_password(&self) -> Result<String> {
+        self.inner.get_password().await
+    }
+
+    /// Retrieve the secret saved for this entry.
+    pub async fn get_secret(&self) -> Result<Vec<u8>> {
+        self.inner.get_secret().await
+    }
+
+    /// Delete the underlying credential for this entry.
+    pub async fn delete_credential(&self) -> Result<()> {
+        self.inner.delete_credential().await
+    }
+
+    /// Return a reference to this entry's wrapped credential as [Any](std::any::Any).
+    pub fn get_credential(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+}
+
+/// The result of a non-blocking poll of a keyring operation.
+///
+/// A UI frame that cannot block drives a keyring future with [poll_once] and
+/// retries on the next frame while the answer is [Waiting](KeyStorageResponse::Waiting).
+pub enum KeyStorageResponse<R> {
+    /// The operation has not completed; poll again later.
+    Waiting,
+    /// The operation finished with this result.
+    Ready(Result<R>),
+}
+
+/// Drive a keyring future a single step without blocking.
+///
+/// Returns [Ready](KeyStorageResponse::Ready) if the future has completed and
+/// [Waiting](KeyStorageResponse::Waiting) otherwise.  The caller keeps the same
+/// pinned future across frames and re-polls it until it is ready; the future is
+/// woken with a no-op waker, so this is a busy-poll suited to a render loop
+/// that already ticks every frame, not a general executor.
+pub fn poll_once<R, F>(future: Pin<&mut F>) -> KeyStorageResponse<R>
+where
+    F: Future<Output = Result<R>>,
+{
+    let waker = noop_waker();
+    let mut context = Context::from_waker(&waker);
+    match future.poll(&mut context) {
+        Poll::Pending => KeyStorageResponse::Waiting,
+        Poll::Ready(result) => KeyStorageResponse::Ready(result),
+    }
+}
+
+/// Construct a waker that does nothing when woken.
+fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(std::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    // Safety: the vtable's functions ignore their data pointer, so a null
+    // pointer is sound, and none of them have side effects.
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
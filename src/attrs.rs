@@ -28,6 +28,7 @@ produce the platform-specific attributes that identify each item.
  */
 
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 #[derive(Debug)]
 pub enum Platform {
@@ -95,8 +96,36 @@ impl PlatformCredential {
     }
 }
 
-// TODO: Make this a Fn trait so we can accept closures
-pub type CredentialMapper = fn(&Platform, &str, &str) -> PlatformCredential;
+/// A mapping from a platform and a (service, username) pair to the
+/// platform-specific attributes that identify a credential.
+///
+/// This is a boxed closure rather than a bare `fn` pointer so that callers can
+/// supply *stateful* mappers — for example one that captures a configured
+/// Windows `target_name` template, or a set of extra Linux attributes pulled
+/// from a config file — which a plain function pointer cannot express.
+pub type CredentialMapper = Box<dyn Fn(&Platform, &str, &str) -> PlatformCredential + Send + Sync>;
+
+/// The process-global mapper override, if one has been installed.
+static DEFAULT_MAPPER: RwLock<Option<CredentialMapper>> = RwLock::new(None);
+
+/// Install a process-global credential mapper.
+///
+/// Once set, [map_credential] uses this mapper in place of
+/// [default_credential_mapper].  Passing a new mapper replaces any previous
+/// override.
+pub fn set_default_credential_mapper(mapper: CredentialMapper) {
+    *DEFAULT_MAPPER.write().unwrap() = Some(mapper);
+}
+
+/// Map a (service, username) pair to a [PlatformCredential] for the given
+/// platform, using the installed [override](set_default_credential_mapper) if
+/// one is present and [default_credential_mapper] otherwise.
+pub fn map_credential(platform: Platform, service: &str, username: &str) -> PlatformCredential {
+    if let Some(mapper) = DEFAULT_MAPPER.read().unwrap().as_ref() {
+        return mapper(&platform, service, username);
+    }
+    default_credential_mapper(platform, service, username)
+}
 
 pub fn default_credential_mapper(
     platform: Platform,
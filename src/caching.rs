@@ -0,0 +1,177 @@
+/*!
+
+# Two-tier caching credential store
+
+This store composes any two credentials — a fast *cache* tier and a durable
+*store* tier — into a single credential that keeps them in sync. It generalizes
+the keyutils-over-secret-service arrangement provided by the
+[keyutils_persistent](crate::keyutils_persistent) module, so you can layer, for
+example, an in-memory cache over a platform keychain, or a keyutils cache over a
+file-based store.
+
+The tiers are kept consistent by the following invariants:
+
+- **Writes** go to the cache first and then the store. If the store write fails,
+  the cache is reverted to its previous secret (or deleted, if it had none), so
+  the two tiers never disagree about a value the store never accepted.
+- **Reads** try the cache first and fall back to the store, backfilling the
+  cache on a store hit so the next read is fast.
+- **Deletes** hit both tiers but only the store's error is propagated; a cache
+  that is already empty does not mask a successful delete.
+
+Attributes are deliberately not exposed: the cache tier (keyutils, in the
+canonical arrangement) has no attribute storage, so this store keeps the default
+no-attribute behavior to avoid the two tiers disagreeing about them.
+ */
+
+use log::debug;
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
+};
+use super::error::{Error, Result};
+
+/// A credential that caches a durable store behind a faster tier.
+///
+/// See the [module documentation](crate::caching) for the invariants it
+/// maintains between the two tiers.
+#[derive(Debug)]
+pub struct CachingCredential {
+    cache: Box<Credential>,
+    store: Box<Credential>,
+}
+
+impl CachingCredential {
+    /// Wrap a `cache` tier over a `store` tier.
+    pub fn new(cache: Box<Credential>, store: Box<Credential>) -> Self {
+        Self { cache, store }
+    }
+}
+
+impl CredentialApi for CachingCredential {
+    /// Set a password in both tiers (see [set_secret](CachingCredential::set_secret)).
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Set a secret in the cache, then the store.
+    ///
+    /// If the store write fails, the cache is reverted to its previous secret
+    /// (or deleted, if it had none) before the error is returned.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let prev_secret = self.cache.get_secret();
+        self.cache.set_secret(secret)?;
+
+        if let Err(err) = self.store.set_secret(secret) {
+            debug!("Failed set of store tier: {err}; reverting cache");
+            match prev_secret {
+                Ok(ref secret) => self.cache.set_secret(secret),
+                Err(Error::NoEntry) => self.cache.delete_credential(),
+                Err(err) => Err(err),
+            }?;
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve a password, preferring the cache (see [get_secret](CachingCredential::get_secret)).
+    fn get_password(&self) -> Result<String> {
+        let secret = self.get_secret()?;
+        super::error::decode_password(secret)
+    }
+
+    /// Retrieve a secret from the cache, falling back to the store.
+    ///
+    /// A value fetched from the store is written back into the cache so the
+    /// next read is served from the faster tier.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        match self.cache.get_secret() {
+            Ok(secret) => {
+                return Ok(secret);
+            }
+            Err(err) => {
+                debug!("Failed get from cache tier: {err}; trying store")
+            }
+        }
+
+        let secret = self.store.get_secret().map_err(ambiguous_to_no_entry)?;
+        self.cache.set_secret(&secret)?;
+
+        Ok(secret)
+    }
+
+    /// Report metadata from the durable store tier.
+    ///
+    /// The cache tier (keyutils, canonically) has no attribute storage, so any
+    /// metadata lives only in the store; this forwards to it rather than
+    /// returning the cache's empty default.
+    fn get_metadata(&self) -> Result<super::credential::CredentialMetadata> {
+        self.store.get_metadata()
+    }
+
+    /// Delete the credential from both tiers.
+    ///
+    /// Only the store's error is propagated; a failure to delete from the cache
+    /// is logged and ignored.
+    fn delete_credential(&self) -> Result<()> {
+        if let Err(err) = self.cache.delete_credential() {
+            debug!("cannot delete cache credential: {err}");
+        }
+
+        self.store.delete_credential()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The builder for [caching credentials](CachingCredential).
+///
+/// It holds a builder for each tier and builds both for every entry.
+#[derive(Debug)]
+pub struct CachingCredentialBuilder {
+    cache: Box<CredentialBuilder>,
+    store: Box<CredentialBuilder>,
+}
+
+impl CachingCredentialBuilder {
+    /// Wrap a `cache`-tier builder over a `store`-tier builder.
+    pub fn new(cache: Box<CredentialBuilder>, store: Box<CredentialBuilder>) -> Self {
+        Self { cache, store }
+    }
+}
+
+impl CredentialBuilderApi for CachingCredentialBuilder {
+    /// Build a [CachingCredential] by building each tier for the given target,
+    /// service, and user.
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        let cache = self.cache.build(target, service, user)?;
+        let store = self.store.build(target, service, user)?;
+        Ok(Box::new(CachingCredential::new(cache, store)))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// The persistence of this store is that of its durable store tier.
+    fn persistence(&self) -> CredentialPersistence {
+        self.store.persistence()
+    }
+}
+
+/// Replace any Ambiguous error with a NoEntry one
+fn ambiguous_to_no_entry(err: Error) -> Error {
+    if let Error::Ambiguous(_) = err {
+        return Error::NoEntry;
+    };
+
+    err
+}
@@ -0,0 +1,155 @@
+/*!
+
+# Cargo credential-provider protocol adapter
+
+Cargo discovers credential providers as helper processes that speak a small
+JSON protocol over stdin/stdout (the same `perform(RegistryInfo, Action, args)`
+shape exposed by the external per-platform cargo credential binaries).  This
+module adapts a [keyring::Entry](crate::Entry) into that protocol so keyring-rs
+can serve as a drop-in `cargo:keyring` credential provider.
+
+A registry's `index_url` (or name) is mapped to a service string of the form
+`cargo-registry:{index_url}` with an empty account, and the `get`/`login`/`logout`
+actions are performed via [Entry::get_password](crate::Entry::get_password),
+[set_password](crate::Entry::set_password), and
+[delete_credential](crate::Entry::delete_credential).  A
+[NoEntry](crate::Error::NoEntry) miss is surfaced as the protocol's `NotFound`.
+ */
+use std::io::{BufRead, Write};
+
+use serde_json::{json, Value};
+
+use crate::{Entry, Error};
+
+/// The service-name prefix used to key cargo registry credentials.
+const SERVICE_PREFIX: &str = "cargo-registry:";
+
+/// Build the [Entry](crate::Entry) backing a given registry index URL.
+fn registry_entry(index_url: &str) -> crate::Result<Entry> {
+    Entry::new(&format!("{SERVICE_PREFIX}{index_url}"), "")
+}
+
+/// Cache-control hints Cargo understands in a credential response.
+#[derive(Debug, Clone, Copy)]
+pub enum CacheControl {
+    /// Never cache the token.
+    Never,
+    /// Cache the token for the duration of the cargo session.
+    Session,
+    /// Cache the token until the given Unix expiry time (seconds).
+    Expires(i64),
+}
+
+impl CacheControl {
+    /// Render the hint as the `cache` field of a credential response.
+    ///
+    /// The open-ended variants are plain strings; an expiry is encoded as
+    /// `{ "expires": <unix-seconds> }`, the shape Cargo expects for a timed
+    /// cache.
+    fn to_json(self) -> Value {
+        match self {
+            CacheControl::Never => json!("never"),
+            CacheControl::Session => json!("session"),
+            CacheControl::Expires(at) => json!({ "expires": at }),
+        }
+    }
+}
+
+/// Perform a single decoded protocol action against the keyring store.
+///
+/// `action` is one of `"get"`, `"login"`, or `"logout"`; `index_url` identifies
+/// the registry; `token` carries the secret for a `login`.  The returned JSON
+/// value is the `CredentialResponse` body to write back to Cargo.
+pub fn perform(action: &str, index_url: &str, token: Option<&str>) -> crate::Result<Value> {
+    let entry = registry_entry(index_url)?;
+    match action {
+        "get" => match entry.get_password() {
+            Ok(token) => Ok(json!({
+                "kind": "get",
+                "token": token,
+                "cache": CacheControl::Session.to_json(),
+            })),
+            Err(Error::NoEntry) => Ok(json!({ "kind": "not-found" })),
+            Err(err) => Err(err),
+        },
+        "login" => {
+            let token = token.ok_or_else(|| {
+                Error::Invalid("token".to_string(), "login requires a token".to_string())
+            })?;
+            entry.set_password(token)?;
+            Ok(json!({ "kind": "login" }))
+        }
+        "logout" => {
+            match entry.delete_credential() {
+                Ok(()) | Err(Error::NoEntry) => {}
+                Err(err) => return Err(err),
+            }
+            Ok(json!({ "kind": "logout" }))
+        }
+        other => Err(Error::Invalid(
+            "action".to_string(),
+            format!("unknown action '{other}'"),
+        )),
+    }
+}
+
+/// Run the credential provider protocol over the given reader/writer.
+///
+/// The provider first emits a hello frame advertising protocol version 1, then
+/// processes one request frame per line, writing a response frame per request.
+/// Reading stops at end of input.
+pub fn run(mut input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+    // advertise the protocol version we speak
+    writeln!(output, "{}", json!({ "v": [1] }))?;
+    output.flush()?;
+
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(request) => handle_request(&request),
+            Err(err) => json!({ "Err": { "kind": "other", "message": err.to_string() } }),
+        };
+        writeln!(output, "{response}")?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// Run the credential helper against this process's standard streams.
+///
+/// This is the entry point a `cargo:keyring` provider binary calls from
+/// `main`: it drives the [run] protocol loop over locked stdin/stdout, so any
+/// keystore this crate exposes can serve as a Cargo credential provider backed
+/// by the user's chosen store.
+pub fn run_credential_helper() -> std::io::Result<()> {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    run(stdin.lock(), stdout.lock())
+}
+
+/// Decode one request frame and map it onto [perform], wrapping the result in
+/// the protocol's `Ok`/`Err` envelope.
+fn handle_request(request: &Value) -> Value {
+    let action = request
+        .get("kind")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let index_url = request
+        .get("registry")
+        .and_then(|registry| registry.get("index-url"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let token = request.get("token").and_then(Value::as_str);
+    match perform(action, index_url, token) {
+        Ok(body) => json!({ "Ok": body }),
+        Err(Error::NoEntry) => json!({ "Err": { "kind": "not-found" } }),
+        Err(err) => json!({ "Err": { "kind": "other", "message": err.to_string() } }),
+    }
+}
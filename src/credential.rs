@@ -30,8 +30,205 @@ if  matches!(persistence, credential::CredentialPersistence::UntilDelete) {
 use std::any::Any;
 use std::collections::HashMap;
 
+use chacha20poly1305::aead::rand_core::RngCore;
+use time::OffsetDateTime;
+
 use super::Result;
 
+/// Machine-readable metadata about a stored credential.
+///
+/// Every field is optional because the backends vary widely in what metadata
+/// they expose: the Windows Credential Manager records a last-written
+/// `FILETIME`, a comment, a persistence scope, and a type, while the mock and
+/// keyutils stores expose none of these.  `last_written` is a
+/// [time::OffsetDateTime] so callers can sort and filter on it directly rather
+/// than parsing a locale-dependent string.
+#[derive(Debug, Clone, Default)]
+pub struct CredentialMetadata {
+    /// A human-readable comment stored alongside the credential.
+    pub comment: Option<String>,
+    /// The store-specific persistence scope (e.g. `"Enterprise"` on Windows).
+    pub persist: Option<String>,
+    /// The store-specific credential type (e.g. `"Generic"` on Windows).
+    pub cred_type: Option<String>,
+    /// When the credential was last written, if the backend records it.
+    pub last_written: Option<OffsetDateTime>,
+    /// When the credential was first created, if the store tracks it.
+    ///
+    /// Stores that persist attributes record this in the reserved
+    /// `keyring.created` attribute the first time a secret is written or
+    /// rotated; stores that drop attributes leave it `None`.
+    pub created: Option<OffsetDateTime>,
+    /// When the credential's secret was last rotated, if the store tracks it.
+    ///
+    /// Stamped into the reserved `keyring.rotated` attribute by
+    /// [Entry::rotate_secret](crate::Entry::rotate_secret); `None` on stores
+    /// that drop attributes, which lets a caller build an age-based rotation
+    /// policy ("warn if older than N days") only where the timestamp survives.
+    pub last_rotated: Option<OffsetDateTime>,
+}
+
+/// The kind of secret a credential holds.
+///
+/// Real credential stores keep more than passwords: the W3C Credential
+/// Management taxonomy distinguishes passwords, public-key (WebAuthn) handles,
+/// one-time-password seeds, federated tokens, and certificate identities.
+/// Tagging an entry with its kind lets higher-level apps (password managers,
+/// OTP tools, Nostr clients) persist heterogeneous credentials through one API
+/// and recover the type on the way back out.  The kind is carried in the
+/// reserved `keyring.kind` attribute, which stores serialize natively when they
+/// can and fall back to a string attribute otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CredentialKind {
+    /// A plain password or opaque secret (the default).
+    Password,
+    /// A public-key / WebAuthn credential key handle.
+    PublicKey,
+    /// A one-time-password (TOTP/HOTP) seed.
+    Otp,
+    /// A federated-identity token.
+    Federated,
+    /// A certificate-plus-private-key identity.
+    Identity,
+    /// A store- or application-specific kind named by the string.
+    Other(String),
+}
+
+impl CredentialKind {
+    /// The string form stored in the `keyring.kind` attribute.
+    pub fn as_attribute(&self) -> String {
+        match self {
+            CredentialKind::Password => "password".to_string(),
+            CredentialKind::PublicKey => "public-key".to_string(),
+            CredentialKind::Otp => "otp".to_string(),
+            CredentialKind::Federated => "federated".to_string(),
+            CredentialKind::Identity => "identity".to_string(),
+            CredentialKind::Other(kind) => kind.clone(),
+        }
+    }
+
+    /// Parse the kind from its `keyring.kind` attribute value.
+    pub fn from_attribute(value: &str) -> Self {
+        match value {
+            "password" => CredentialKind::Password,
+            "public-key" => CredentialKind::PublicKey,
+            "otp" => CredentialKind::Otp,
+            "federated" => CredentialKind::Federated,
+            "identity" => CredentialKind::Identity,
+            other => CredentialKind::Other(other.to_string()),
+        }
+    }
+}
+
+/// How much attribute storage a backend actually provides.
+///
+/// Backends vary: the mock and keyutils stores drop attributes entirely, the
+/// secret-service and libsecret stores retain an open-ended set, and some
+/// stores persist only a fixed list of known keys.  [CredentialCapabilities]
+/// surfaces this so a caller (or the test harness) can tell at runtime whether
+/// metadata it writes will survive a round trip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeSupport {
+    /// Attributes are ignored; [get_attributes](CredentialApi::get_attributes)
+    /// always reports an empty map.
+    None,
+    /// Only the named attribute keys are persisted; others are dropped.
+    Some(Vec<String>),
+    /// Any attribute key/value pair is persisted.
+    Arbitrary,
+}
+
+/// The capabilities a credential store exposes beyond set/get/delete.
+#[derive(Debug, Clone)]
+pub struct CredentialCapabilities {
+    /// How much attribute storage the backend provides.
+    pub attributes: AttributeSupport,
+}
+
+/// The alphabet a generated secret draws its units from.
+///
+/// [Bytes](SecretAlphabet::Bytes) yields a uniformly random byte array for
+/// binary secrets; [Charset](SecretAlphabet::Charset) yields printable text
+/// drawn from the given characters, which is what you want for a human-usable
+/// password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretAlphabet {
+    /// Uniformly random bytes.
+    Bytes,
+    /// Characters drawn uniformly from the given set.
+    Charset(String),
+}
+
+/// The default alphabet used by [SecretSpec::password]: the URL-safe base64
+/// characters, which are password-manager friendly and shell-safe.
+pub const DEFAULT_PASSWORD_ALPHABET: &str =
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A recipe for generating a fresh random secret.
+///
+/// Used by the get-or-create methods (see
+/// [get_or_create_secret](CredentialApi::get_or_create_secret)) to mint a
+/// secret when none exists yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretSpec {
+    /// The number of units (bytes or characters) to generate.
+    pub length: usize,
+    /// The alphabet the generated secret is drawn from.
+    pub alphabet: SecretAlphabet,
+}
+
+impl SecretSpec {
+    /// A spec for `length` uniformly random bytes.
+    pub fn bytes(length: usize) -> Self {
+        Self {
+            length,
+            alphabet: SecretAlphabet::Bytes,
+        }
+    }
+
+    /// A spec for a `length`-character password over [DEFAULT_PASSWORD_ALPHABET].
+    pub fn password(length: usize) -> Self {
+        Self {
+            length,
+            alphabet: SecretAlphabet::Charset(DEFAULT_PASSWORD_ALPHABET.to_string()),
+        }
+    }
+
+    /// Generate a secret from the operating system's secure RNG.
+    pub fn generate(&self) -> Vec<u8> {
+        use chacha20poly1305::aead::OsRng;
+        self.generate_with(&mut OsRng)
+    }
+
+    /// Generate a secret using the given random source.
+    ///
+    /// Seeding a deterministic `rng` makes the generated secret reproducible,
+    /// which is how the mock store exercises the create path in tests.
+    pub fn generate_with(&self, rng: &mut impl RngCore) -> Vec<u8> {
+        match &self.alphabet {
+            SecretAlphabet::Bytes => {
+                let mut out = vec![0u8; self.length];
+                rng.fill_bytes(&mut out);
+                out
+            }
+            SecretAlphabet::Charset(chars) => {
+                let charset: Vec<char> = chars.chars().collect();
+                if charset.is_empty() {
+                    return Vec::new();
+                }
+                (0..self.length)
+                    .map(|_| {
+                        let idx = (rng.next_u32() as usize) % charset.len();
+                        charset[idx]
+                    })
+                    .collect::<String>()
+                    .into_bytes()
+            }
+        }
+    }
+}
+
 /// The API that [credentials](Credential) implement.
 pub trait CredentialApi {
     /// Set the credential's password (a string).
@@ -54,8 +251,49 @@ pub trait CredentialApi {
     /// This has no effect on the underlying store.
     fn get_secret(&self) -> Result<Vec<u8>>;
 
+    /// Retrieve the password wrapped in a leak-resistant [Secret](crate::secret::Secret).
+    ///
+    /// The returned value zeroizes its buffer on drop and redacts itself when
+    /// printed.  The default wraps [get_password](CredentialApi::get_password);
+    /// a store that can scrub its own intermediate plaintext (see the Linux
+    /// store) overrides this to avoid leaving an un-zeroized copy behind.
+    fn get_password_secret(&self) -> Result<crate::secret::Secret<String>> {
+        Ok(crate::secret::Secret::new(self.get_password()?))
+    }
+
+    /// Retrieve the secret bytes wrapped in a leak-resistant
+    /// [Secret](crate::secret::Secret).
+    ///
+    /// The default wraps [get_secret](CredentialApi::get_secret).
+    fn get_secret_bytes(&self) -> Result<crate::secret::Secret<Vec<u8>>> {
+        Ok(crate::secret::Secret::new(self.get_secret()?))
+    }
+
+    /// Retrieve this credential's secret, creating it first if there is none.
+    ///
+    /// If [get_secret](CredentialApi::get_secret) succeeds, its result is
+    /// returned unchanged.  If it reports [NoEntry](crate::Error::NoEntry), a
+    /// fresh secret is generated from `spec`, persisted via
+    /// [set_secret](CredentialApi::set_secret), and returned; any other error
+    /// propagates.  This captures the ubiquitous read-or-initialize pattern
+    /// clients write around first-run secrets.
+    ///
+    /// The default implementation draws from the operating system RNG; stores
+    /// that want reproducible generation (such as the mock) override it.
+    fn get_or_create_secret(&self, spec: &SecretSpec) -> Result<Vec<u8>> {
+        match self.get_secret() {
+            Ok(secret) => Ok(secret),
+            Err(super::error::Error::NoEntry) => {
+                let secret = spec.generate();
+                self.set_secret(&secret)?;
+                Ok(secret)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     /// Get the attributes on this credential from the underlying store.
-    /// 
+    ///
     /// We provide a default (no-op) implementation of this method
     /// for backward compatibility with stores that don't implement it.
     fn get_attributes(&self) -> Result<HashMap<String, String>> {
@@ -76,6 +314,84 @@ pub trait CredentialApi {
         Ok(())
     }
 
+    /// Report the [capabilities](CredentialCapabilities) of this store.
+    ///
+    /// The default reports [AttributeSupport::None], matching the historical
+    /// behavior of stores that drop attributes; stores that persist attributes
+    /// override this so callers can discover it at runtime.
+    fn capabilities(&self) -> CredentialCapabilities {
+        CredentialCapabilities {
+            attributes: AttributeSupport::None,
+        }
+    }
+
+    /// Report the [kind](CredentialKind) of secret this credential holds.
+    ///
+    /// The default reads the reserved `keyring.kind` attribute (treating a
+    /// missing value as [Password](CredentialKind::Password)); stores that
+    /// track a native credential type can override this.
+    fn kind(&self) -> Result<CredentialKind> {
+        let attributes = self.get_attributes()?;
+        Ok(attributes
+            .get("keyring.kind")
+            .map(|value| CredentialKind::from_attribute(value))
+            .unwrap_or(CredentialKind::Password))
+    }
+
+    /// Retrieve this credential's certificate as DER-encoded bytes.
+    ///
+    /// Stores that model an *identity* (a certificate bound to a private key),
+    /// such as the Apple Security framework, override this.  The default
+    /// reports [NotSupported](crate::Error::NotSupported) so callers can detect
+    /// the missing capability.
+    fn get_certificate(&self) -> Result<Vec<u8>> {
+        Err(super::error::Error::NotSupported("get_certificate".to_string()))
+    }
+
+    /// Retrieve an opaque reference to this credential's private key.
+    ///
+    /// The bytes are store-specific and only meaningful to the same store; they
+    /// are not the raw key material.  The default reports
+    /// [NotSupported](crate::Error::NotSupported).
+    fn get_private_key_ref(&self) -> Result<Vec<u8>> {
+        Err(super::error::Error::NotSupported(
+            "get_private_key_ref".to_string(),
+        ))
+    }
+
+    /// Sign `data` with this credential's private key, returning the signature.
+    ///
+    /// Stores backed by an identity (Apple `SecIdentity`, a PKCS#11 token)
+    /// override this; the default reports
+    /// [NotSupported](crate::Error::NotSupported).
+    fn sign(&self, _data: &[u8]) -> Result<Vec<u8>> {
+        Err(super::error::Error::NotSupported("sign".to_string()))
+    }
+
+    /// Enumerate every stored credential matching this entry's attributes.
+    ///
+    /// Backends that can list more than one match for a service or attribute
+    /// subset (the Linux secret service, for instance, already searches and
+    /// then discards all but the first hit) override this to return one
+    /// [Credential] per hit, which is what a vault-export tool needs before it
+    /// can dump every entry.  Because a `dyn CredentialApi` cannot clone itself
+    /// into a box, the default reports [NotSupported](crate::Error::NotSupported)
+    /// rather than fabricating a single-element list.
+    fn search(&self) -> Result<Vec<Box<Credential>>> {
+        Err(super::error::Error::NotSupported("search".to_string()))
+    }
+
+    /// Get machine-readable [metadata](CredentialMetadata) for this credential.
+    ///
+    /// We provide a default implementation that reports no metadata (but still
+    /// errs in the same cases as [get_secret](CredentialApi::get_secret)) for
+    /// backward compatibility with stores that don't record any.
+    fn get_metadata(&self) -> Result<CredentialMetadata> {
+        // this should err in the same cases as get_secret, so first call that for effect
+        self.get_secret()?;
+        Ok(CredentialMetadata::default())
+    }
+
     /// Delete the underlying credential, if there is one.
     ///
     /// This is not idempotent if the credential existed!
@@ -105,6 +421,47 @@ pub trait CredentialApi {
     }
 }
 
+/// The asynchronous analogue of [CredentialApi].
+///
+/// Backends whose underlying platform library exposes a non-blocking API (at
+/// present only the `async-secret-service` store) implement this trait so that
+/// `tokio` callers can `.await` keyring operations directly instead of
+/// spawning a thread to run the blocking API (the deadlock hazard called out
+/// in the secret-service module docs).  The method set mirrors
+/// [CredentialApi], including the defaulted attribute and metadata calls.
+#[allow(async_fn_in_trait)]
+pub trait AsyncCredentialApi {
+    /// Set the credential's password (a string).
+    async fn set_password(&self, password: &str) -> Result<()>;
+
+    /// Set the credential's secret (a byte array).
+    async fn set_secret(&self, password: &[u8]) -> Result<()>;
+
+    /// Retrieve a password (a string) from the credential, if one has been set.
+    async fn get_password(&self) -> Result<String>;
+
+    /// Retrieve a secret (a byte array) from the credential, if one has been set.
+    async fn get_secret(&self) -> Result<Vec<u8>>;
+
+    /// Get the attributes on this credential from the underlying store.
+    async fn get_attributes(&self) -> Result<HashMap<String, String>> {
+        self.get_secret().await?;
+        Ok(HashMap::new())
+    }
+
+    /// Update attributes on the underlying credential store.
+    async fn update_attributes(&self, _: &HashMap<&str, &str>) -> Result<()> {
+        self.get_secret().await?;
+        Ok(())
+    }
+
+    /// Delete the underlying credential, if there is one.
+    async fn delete_credential(&self) -> Result<()>;
+
+    /// Return the underlying concrete object cast to [Any].
+    fn as_any(&self) -> &dyn Any;
+}
+
 /// A thread-safe implementation of the [Credential API](CredentialApi).
 pub type Credential = dyn CredentialApi + Send + Sync;
 
@@ -136,6 +493,17 @@ pub trait CredentialBuilderApi {
     /// A credential need not be persisted until its password is set.
     fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>>;
 
+    /// Find every stored credential matching a subset of attributes.
+    ///
+    /// Unlike [build](CredentialBuilderApi::build), which names one entry by its
+    /// target/service/user, this queries the store for all entries whose
+    /// attributes contain the given key/value pairs, returning one [Credential]
+    /// per hit.  A default is provided for stores that can't enumerate; it
+    /// reports [NotSupported](crate::Error::NotSupported).
+    fn find(&self, _attributes: &HashMap<&str, &str>) -> Result<Vec<Box<Credential>>> {
+        Err(super::error::Error::NotSupported("find".to_string()))
+    }
+
     /// Return the underlying concrete object cast to [Any].
     ///
     /// Because credential builders need not have any internal structure,
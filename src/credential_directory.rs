@@ -0,0 +1,194 @@
+/*!
+
+# systemd credential-directory store
+
+systemd can inject secrets into a service's environment through its
+[credentials](https://systemd.io/CREDENTIALS/) mechanism: with `LoadCredential=`
+or `ImportCredential=` in a unit file, the service manager writes each secret to
+a file in a private directory and sets `$CREDENTIAL_DIRECTORY` to its path.
+
+This module exposes those injected secrets as a read-only credential store.
+`get_password`/`get_secret` resolve an entry to a file in `$CREDENTIAL_DIRECTORY`
+(named by the entry's service and user through a configurable
+[naming scheme](CredentialNaming)) and return its contents, reporting a
+[NoEntry](crate::Error::NoEntry) error when the file is absent.  Because
+systemd owns the directory, `set_password`/`set_secret`/`delete_credential`
+always fail with a read-only error.
+
+This lets an application running under a systemd unit pick up injected secrets
+transparently, without a secret-service daemon.
+ */
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
+};
+use super::error::{decode_password, Error as ErrorCode, Result};
+
+/// The environment variable systemd sets to the credential directory path.
+const CREDENTIAL_DIRECTORY: &str = "CREDENTIAL_DIRECTORY";
+
+/// How an entry's service and user are turned into a credential file name.
+#[derive(Debug, Clone)]
+pub enum CredentialNaming {
+    /// Use the service name alone, ignoring the user.  This matches the common
+    /// case where a unit loads a credential under a single name.
+    Service,
+    /// Join the service and user with the given separator (e.g. `"."` yields
+    /// `service.user`).
+    ServiceAndUser(String),
+    /// Use an explicit file name supplied as the entry's target.
+    Target,
+}
+
+impl Default for CredentialNaming {
+    fn default() -> Self {
+        CredentialNaming::ServiceAndUser(".".to_string())
+    }
+}
+
+/// The representation of a systemd credential-directory entry.
+#[derive(Debug, Clone)]
+pub struct CredDirCredential {
+    pub service: String,
+    pub user: String,
+    pub target: Option<String>,
+    pub naming: CredentialNaming,
+}
+
+impl CredentialApi for CredDirCredential {
+    /// Always fails: the systemd credential directory is read-only.
+    fn set_password(&self, _password: &str) -> Result<()> {
+        Err(read_only())
+    }
+
+    /// Always fails: the systemd credential directory is read-only.
+    fn set_secret(&self, _secret: &[u8]) -> Result<()> {
+        Err(read_only())
+    }
+
+    /// Read the injected credential and decode it as a UTF-8 password.
+    fn get_password(&self) -> Result<String> {
+        decode_password(self.get_secret()?)
+    }
+
+    /// Read the raw bytes of the injected credential.
+    ///
+    /// Returns a [NoEntry](ErrorCode::NoEntry) error if no credential with this
+    /// name was injected, or if `$CREDENTIAL_DIRECTORY` is not set.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let path = self.path()?;
+        match std::fs::read(&path) {
+            Ok(bytes) => Ok(bytes),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Err(ErrorCode::NoEntry),
+            Err(err) => Err(ErrorCode::PlatformFailure(Box::new(err))),
+        }
+    }
+
+    /// Always fails: the systemd credential directory is read-only.
+    fn update_attributes(&self, _attributes: &HashMap<&str, &str>) -> Result<()> {
+        Err(read_only())
+    }
+
+    /// Always fails: the systemd credential directory is read-only.
+    fn delete_credential(&self) -> Result<()> {
+        Err(read_only())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl CredDirCredential {
+    /// Create a credential for the given target, service, and user, using the
+    /// default [naming scheme](CredentialNaming).
+    ///
+    /// A non-empty target is used verbatim as the credential file name.
+    pub fn new_with_target(target: Option<&str>, service: &str, user: &str) -> Result<Self> {
+        Self::new_with_naming(target, service, user, CredentialNaming::default())
+    }
+
+    /// Create a credential with an explicit [naming scheme](CredentialNaming).
+    pub fn new_with_naming(
+        target: Option<&str>,
+        service: &str,
+        user: &str,
+        naming: CredentialNaming,
+    ) -> Result<Self> {
+        if let Some("") = target {
+            return Err(ErrorCode::Invalid(
+                "target".to_string(),
+                "cannot be empty".to_string(),
+            ));
+        }
+        Ok(Self {
+            service: service.to_string(),
+            user: user.to_string(),
+            target: target.map(str::to_string),
+            naming,
+        })
+    }
+
+    /// The file name this credential resolves to under the credential directory.
+    fn file_name(&self) -> String {
+        match &self.naming {
+            CredentialNaming::Target => self
+                .target
+                .clone()
+                .unwrap_or_else(|| self.service.clone()),
+            CredentialNaming::Service => self.service.clone(),
+            CredentialNaming::ServiceAndUser(separator) => {
+                format!("{}{separator}{}", self.service, self.user)
+            }
+        }
+    }
+
+    /// The full path this credential resolves to, or a
+    /// [NoEntry](ErrorCode::NoEntry) error if no directory was injected.
+    fn path(&self) -> Result<PathBuf> {
+        let dir = std::env::var_os(CREDENTIAL_DIRECTORY).ok_or(ErrorCode::NoEntry)?;
+        Ok(PathBuf::from(dir).join(self.file_name()))
+    }
+}
+
+/// The builder for systemd credential-directory credentials.
+#[derive(Debug, Default)]
+pub struct CredDirCredentialBuilder {}
+
+/// Returns an instance of the systemd credential-directory credential builder.
+pub fn default_credential_builder() -> Box<CredentialBuilder> {
+    Box::new(CredDirCredentialBuilder {})
+}
+
+impl CredentialBuilderApi for CredDirCredentialBuilder {
+    /// Build a [CredDirCredential] for the given target, service, and user.
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        Ok(Box::new(CredDirCredential::new_with_target(
+            target, service, user,
+        )?))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// Credentials are injected by the service manager and live only as long as
+    /// the unit's runtime directory.
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::ProcessOnly
+    }
+}
+
+/// The error returned by the store's unsupported write operations.
+fn read_only() -> ErrorCode {
+    ErrorCode::Invalid(
+        "operation".to_string(),
+        "the systemd credential directory is read-only".to_string(),
+    )
+}
@@ -0,0 +1,186 @@
+/*!
+
+# Passphrase-encrypted credential wrapper
+
+This module provides [EncryptedCredential], a composable [CredentialApi]
+wrapper that encrypts a secret at rest before handing it to any inner store.
+It borrows the [NIP-49](https://github.com/nostr-protocol/nips/blob/master/49.md)
+approach: a 32-byte key is derived from a caller-supplied passphrase and a
+random salt with the scrypt KDF (a tunable `log_n` work factor, `r = 8`,
+`p = 1`), and the plaintext is sealed with XChaCha20-Poly1305.
+
+The wrapper is useful in front of backends whose at-rest protection is weak
+(for example a file store), giving defense-in-depth without changing the
+backend.  It mirrors the wrapping pattern used by
+[SsKeyutilsCredential](crate::secret_service_with_keyutils::SsKeyutilsCredential):
+the wrapper delegates attribute and deletion operations to the inner
+credential and only interposes on the secret itself.
+
+The stored blob is versioned so the format can evolve:
+
+```text
+[ version (1) | log_n (1) | salt (16) | nonce (24) | ciphertext+tag ]
+```
+ */
+use std::collections::HashMap;
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, XChaCha20Poly1305, XNonce};
+use scrypt::{scrypt, Params};
+
+use super::credential::{Credential, CredentialApi};
+use super::error::{Error as ErrorCode, Result};
+
+/// The blob format version written by this module.
+const VERSION: u8 = 1;
+/// The length in bytes of the random scrypt salt.
+const SALT_LEN: usize = 16;
+/// The length in bytes of the XChaCha20-Poly1305 nonce.
+const NONCE_LEN: usize = 24;
+/// The length in bytes of the derived key.
+const KEY_LEN: usize = 32;
+/// The fixed scrypt `r` parameter.
+const SCRYPT_R: u32 = 8;
+/// The fixed scrypt `p` parameter.
+const SCRYPT_P: u32 = 1;
+
+/// A [CredentialApi] wrapper that encrypts secrets at rest with a passphrase.
+///
+/// The inner credential supplies the actual storage; this wrapper only
+/// transforms the bytes that cross the [set_secret](CredentialApi::set_secret)
+/// and [get_secret](CredentialApi::get_secret) boundary.
+pub struct EncryptedCredential {
+    inner: Box<Credential>,
+    passphrase: String,
+    log_n: u8,
+}
+
+impl std::fmt::Debug for EncryptedCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // never print the passphrase
+        f.debug_struct("EncryptedCredential")
+            .field("inner", &self.inner)
+            .field("log_n", &self.log_n)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EncryptedCredential {
+    /// Wrap an inner credential so its secret is encrypted with `passphrase`.
+    ///
+    /// The `log_n` work factor selects the scrypt cost as `N = 2^log_n`; the
+    /// NIP-49 default of 16 is a reasonable starting point.
+    pub fn new(inner: Box<Credential>, passphrase: &str, log_n: u8) -> Self {
+        Self {
+            inner,
+            passphrase: passphrase.to_string(),
+            log_n,
+        }
+    }
+
+    /// Derive the AEAD key from the passphrase and salt at the given work factor.
+    fn derive_key(&self, log_n: u8, salt: &[u8]) -> Result<[u8; KEY_LEN]> {
+        let params = Params::new(log_n, SCRYPT_R, SCRYPT_P, KEY_LEN)
+            .map_err(|err| ErrorCode::Invalid("log_n".to_string(), err.to_string()))?;
+        let mut key = [0u8; KEY_LEN];
+        scrypt(self.passphrase.as_bytes(), salt, &params, &mut key)
+            .map_err(|err| ErrorCode::Invalid("scrypt".to_string(), err.to_string()))?;
+        Ok(key)
+    }
+}
+
+impl CredentialApi for EncryptedCredential {
+    /// Encrypt and store the password as a secret.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Encrypt `secret` and hand the versioned blob to the inner credential.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = self.derive_key(self.log_n, &salt)?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, secret)
+            .map_err(|err| ErrorCode::PlatformFailure(Box::new(EncryptError(err.to_string()))))?;
+
+        let mut blob = Vec::with_capacity(2 + SALT_LEN + NONCE_LEN + ciphertext.len());
+        blob.push(VERSION);
+        blob.push(self.log_n);
+        blob.extend_from_slice(&salt);
+        blob.extend_from_slice(nonce.as_slice());
+        blob.extend_from_slice(&ciphertext);
+        self.inner.set_secret(&blob)
+    }
+
+    /// Decrypt the stored blob and decode it as a UTF-8 password.
+    fn get_password(&self) -> Result<String> {
+        let secret = self.get_secret()?;
+        String::from_utf8(secret).map_err(|err| ErrorCode::BadEncoding(err.into_bytes()))
+    }
+
+    /// Read the versioned blob from the inner credential and decrypt it.
+    ///
+    /// Returns [BadEncoding](ErrorCode::BadEncoding) if the stored blob is
+    /// truncated or uses an unknown version, and an
+    /// [Invalid](ErrorCode::Invalid) `passphrase` error if the AEAD tag fails
+    /// to verify (the symptom of a wrong passphrase).
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let blob = self.inner.get_secret()?;
+        let header = 2 + SALT_LEN + NONCE_LEN;
+        if blob.len() < header || blob[0] != VERSION {
+            return Err(ErrorCode::BadEncoding(blob));
+        }
+        let log_n = blob[1];
+        let salt = &blob[2..2 + SALT_LEN];
+        let nonce = XNonce::from_slice(&blob[2 + SALT_LEN..header]);
+        let ciphertext = &blob[header..];
+
+        let key = self.derive_key(log_n, salt)?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        cipher.decrypt(nonce, ciphertext).map_err(|_| {
+            ErrorCode::Invalid(
+                "passphrase".to_string(),
+                "could not decrypt secret; wrong passphrase?".to_string(),
+            )
+        })
+    }
+
+    /// Delegate attribute reads to the inner credential.
+    fn get_attributes(&self) -> Result<HashMap<String, String>> {
+        self.inner.get_attributes()
+    }
+
+    /// Delegate attribute updates to the inner credential.
+    fn update_attributes(&self, attributes: &HashMap<&str, &str>) -> Result<()> {
+        self.inner.update_attributes(attributes)
+    }
+
+    /// Delegate deletion to the inner credential.
+    fn delete_credential(&self) -> Result<()> {
+        self.inner.delete_credential()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A wrapper error carrying an AEAD encryption failure message.
+#[derive(Debug)]
+struct EncryptError(String);
+
+impl std::fmt::Display for EncryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "encryption failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for EncryptError {}
@@ -0,0 +1,329 @@
+/*!
+
+# Encrypted-file keystore
+
+On platforms with no secure store — and in CI and containers — the crate would
+otherwise fall back to [mock](crate::mock), which persists nothing.  This module
+provides a durable, cross-platform alternative that keeps each entry as an
+encrypted JSON record under a per-user directory, so headless environments get
+real persistence without a platform keychain.
+
+Each `<target, service, user>` entry is stored in its own file.  The secret is
+sealed at rest under a caller-supplied passphrase: a 32-byte key is derived with
+scrypt (the `log_n` cost parameter, a per-record random salt, and the AEAD nonce
+are all stored in the record), and the secret is encrypted with
+XChaCha20-Poly1305, exactly as in the
+[EncryptedCredential](crate::encrypted::EncryptedCredential) wrapper.
+[get_password](EncryptedFileCredential::get_password) /
+[get_secret](EncryptedFileCredential::get_secret) re-derive the key and decrypt;
+a wrong passphrase surfaces a [BadEncoding](crate::Error::BadEncoding)
+decryption failure rather than a spurious [NoEntry](crate::Error::NoEntry), so
+callers can tell "no such entry" apart from "wrong passphrase".
+
+Install the store with [set_default_credential_builder](crate::set_default_credential_builder)
+and [default_credential_builder]:
+```no_run
+# use keyring::{set_default_credential_builder, encrypted_file};
+set_default_credential_builder(encrypted_file::default_credential_builder(
+    "/home/user/.keyring",
+    "correct horse battery staple",
+));
+```
+ */
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, XChaCha20Poly1305, XNonce};
+use scrypt::{scrypt, Params};
+use serde_json::{json, Value};
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
+};
+use super::error::{Error, Result};
+
+/// The record schema version.
+const VERSION: u64 = 1;
+/// The length in bytes of the per-record scrypt salt.
+const SALT_LEN: usize = 16;
+/// The length in bytes of the derived key.
+const KEY_LEN: usize = 32;
+/// The fixed scrypt `r` parameter.
+const SCRYPT_R: u32 = 8;
+/// The fixed scrypt `p` parameter.
+const SCRYPT_P: u32 = 1;
+/// The default scrypt work factor (`N = 2^15`).
+const DEFAULT_LOG_N: u8 = 15;
+
+/// A credential persisted as an encrypted JSON record in a per-user directory.
+#[derive(Debug, Clone)]
+pub struct EncryptedFileCredential {
+    dir: PathBuf,
+    passphrase: String,
+    log_n: u8,
+    target: Option<String>,
+    service: String,
+    user: String,
+}
+
+impl EncryptedFileCredential {
+    /// Create a credential rooted at `dir`, sealed under `passphrase`.
+    pub fn new_with_target(
+        dir: &Path,
+        passphrase: &str,
+        log_n: u8,
+        target: Option<&str>,
+        service: &str,
+        user: &str,
+    ) -> Result<Self> {
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            passphrase: passphrase.to_string(),
+            log_n,
+            target: target.map(str::to_string),
+            service: service.to_string(),
+            user: user.to_string(),
+        })
+    }
+
+    /// The path of the record file backing this credential.
+    fn record_path(&self) -> PathBuf {
+        let key = format!(
+            "{}\u{0}{}\u{0}{}",
+            self.target.as_deref().unwrap_or_default(),
+            self.service,
+            self.user
+        );
+        self.dir.join(format!("{}.json", hex(key.as_bytes())))
+    }
+
+    /// Derive the AEAD key from the passphrase and salt at the given work factor.
+    fn derive_key(&self, log_n: u8, salt: &[u8]) -> Result<[u8; KEY_LEN]> {
+        let params = Params::new(log_n, SCRYPT_R, SCRYPT_P, KEY_LEN)
+            .map_err(|err| Error::Invalid("log_n".to_string(), err.to_string()))?;
+        let mut key = [0u8; KEY_LEN];
+        scrypt(self.passphrase.as_bytes(), salt, &params, &mut key)
+            .map_err(|err| Error::Invalid("scrypt".to_string(), err.to_string()))?;
+        Ok(key)
+    }
+}
+
+impl CredentialApi for EncryptedFileCredential {
+    /// Encrypt and store the password as a secret.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Seal `secret` and write it as a JSON record.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = self.derive_key(self.log_n, &salt)?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, secret)
+            .map_err(|err| Error::PlatformFailure(Box::new(CryptoError(err.to_string()))))?;
+
+        let record = json!({
+            "version": VERSION,
+            "target": self.target,
+            "service": self.service,
+            "user": self.user,
+            "log_n": self.log_n,
+            "salt": salt.to_vec(),
+            "nonce": nonce.to_vec(),
+            "ciphertext": ciphertext,
+        });
+        let bytes = serde_json::to_vec_pretty(&record)
+            .map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+        std::fs::create_dir_all(&self.dir).map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+        std::fs::write(self.record_path(), bytes)
+            .map_err(|err| Error::PlatformFailure(Box::new(err)))
+    }
+
+    /// Read the record and decode the decrypted secret as a UTF-8 password.
+    fn get_password(&self) -> Result<String> {
+        let secret = self.get_secret()?;
+        super::error::decode_password(secret)
+    }
+
+    /// Read the record, re-derive the key, and decrypt the secret.
+    ///
+    /// Returns [NoEntry](Error::NoEntry) if no record exists and
+    /// [BadEncoding](Error::BadEncoding) if the record is malformed or the
+    /// passphrase is wrong (the AEAD tag fails to verify).
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let bytes = match std::fs::read(self.record_path()) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Err(Error::NoEntry),
+            Err(err) => return Err(Error::PlatformFailure(Box::new(err))),
+        };
+        let record: Value =
+            serde_json::from_slice(&bytes).map_err(|_| Error::BadEncoding(bytes.clone()))?;
+        let log_n = record
+            .get("log_n")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| Error::BadEncoding(bytes.clone()))? as u8;
+        let salt = bytes_field(&record, "salt").ok_or_else(|| Error::BadEncoding(bytes.clone()))?;
+        let nonce =
+            bytes_field(&record, "nonce").ok_or_else(|| Error::BadEncoding(bytes.clone()))?;
+        let ciphertext =
+            bytes_field(&record, "ciphertext").ok_or_else(|| Error::BadEncoding(bytes.clone()))?;
+
+        let key = self.derive_key(log_n, &salt)?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        let nonce = XNonce::from_slice(&nonce);
+        cipher
+            .decrypt(nonce, ciphertext.as_slice())
+            // a wrong passphrase is indistinguishable from a corrupt record here
+            .map_err(|_| Error::BadEncoding(ciphertext))
+    }
+
+    /// Delete the record file.
+    ///
+    /// Returns [NoEntry](Error::NoEntry) if there was no record.
+    fn delete_credential(&self) -> Result<()> {
+        match std::fs::remove_file(self.record_path()) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Err(Error::NoEntry),
+            Err(err) => Err(Error::PlatformFailure(Box::new(err))),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A builder for [EncryptedFileCredential]s rooted at a directory.
+#[derive(Debug)]
+pub struct EncryptedFileCredentialBuilder {
+    dir: PathBuf,
+    passphrase: String,
+    log_n: u8,
+}
+
+impl CredentialBuilderApi for EncryptedFileCredentialBuilder {
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        let credential = EncryptedFileCredential::new_with_target(
+            &self.dir,
+            &self.passphrase,
+            self.log_n,
+            target,
+            service,
+            user,
+        )?;
+        Ok(Box::new(credential))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// Records live on disk until they are explicitly deleted.
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+}
+
+/// Return an encrypted-file credential builder rooted at `dir`, sealing secrets
+/// under `passphrase` at the default scrypt work factor.
+pub fn default_credential_builder<P: AsRef<Path>>(
+    dir: P,
+    passphrase: &str,
+) -> Box<CredentialBuilder> {
+    Box::new(EncryptedFileCredentialBuilder {
+        dir: dir.as_ref().to_path_buf(),
+        passphrase: passphrase.to_string(),
+        log_n: DEFAULT_LOG_N,
+    })
+}
+
+/// Read a field as a JSON array of byte-valued numbers.
+fn bytes_field(record: &Value, name: &str) -> Option<Vec<u8>> {
+    let array = record.get(name)?.as_array()?;
+    array
+        .iter()
+        .map(|v| v.as_u64().filter(|n| *n <= u8::MAX as u64).map(|n| n as u8))
+        .collect()
+}
+
+/// Hex-encode bytes for use as a filesystem-safe record name.
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push_str(&format!("{byte:02x}"));
+    }
+    out
+}
+
+/// A wrapper error carrying an AEAD encryption failure message.
+#[derive(Debug)]
+struct CryptoError(String);
+
+impl std::fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "encryption failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::generate_random_string, Entry, Error};
+
+    use super::EncryptedFileCredential;
+
+    fn entry_new(dir: &std::path::Path, passphrase: &str, service: &str, user: &str) -> Entry {
+        let credential =
+            EncryptedFileCredential::new_with_target(dir, passphrase, 10, None, service, user)
+                .expect("Can't create encrypted-file credential");
+        Entry::new_with_credential(Box::new(credential))
+    }
+
+    fn temp_dir() -> std::path::PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("keyring-rs-ef-{}", generate_random_string()));
+        dir
+    }
+
+    #[test]
+    fn test_missing_entry() {
+        let dir = temp_dir();
+        let name = generate_random_string();
+        let entry = entry_new(&dir, "pw", &name, &name);
+        assert!(matches!(entry.get_password(), Err(Error::NoEntry)));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = temp_dir();
+        let name = generate_random_string();
+        let entry = entry_new(&dir, "pw", &name, &name);
+        entry.set_password("secret").expect("Can't set password");
+        assert_eq!(entry.get_password().expect("Can't get password"), "secret");
+        entry.delete_credential().expect("Can't delete password");
+        assert!(matches!(entry.get_password(), Err(Error::NoEntry)));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let dir = temp_dir();
+        let name = generate_random_string();
+        entry_new(&dir, "right", &name, &name)
+            .set_password("secret")
+            .expect("Can't set password");
+        let wrong = entry_new(&dir, "wrong", &name, &name);
+        assert!(matches!(wrong.get_password(), Err(Error::BadEncoding(_))));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
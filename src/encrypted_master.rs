@@ -0,0 +1,270 @@
+/*!
+
+# Master-passphrase–encrypted credential wrapper
+
+This module provides [MasterEncryptedCredential], a [CredentialApi] wrapper that
+encrypts a secret at rest under a key derived from a master passphrase. It is a
+sibling to the scrypt-based [EncryptedCredential](crate::encrypted::EncryptedCredential):
+it derives its 32-byte key with Argon2id (a memory-hard KDF) over a per-entry
+random salt, and seals the secret with XChaCha20-Poly1305 under a fresh random
+24-byte nonce.
+
+Unlike the scrypt wrapper, this one also persists a small *verify blob* — a
+fixed known constant sealed under the same derived key — so callers can validate
+a passphrase with [unlock](MasterEncryptedCredential::unlock) before relying on
+it, distinguishing a wrong passphrase from a missing entry.
+
+The stored blob has the layout:
+
+```text
+[ salt (16) | verify_nonce (24) | verify_ct (37) | secret_nonce (24) | secret_ct ]
+```
+
+Critical invariants: the passphrase is held only in memory (never written to the
+inner store), the derived key is zeroized after every operation, and an AEAD tag
+failure surfaces as an [Invalid](crate::Error::Invalid) authentication error
+rather than [NoEntry](crate::Error::NoEntry).
+ */
+use std::collections::HashMap;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, XChaCha20Poly1305, XNonce};
+use zeroize::Zeroize;
+
+use super::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
+use super::error::{Error as ErrorCode, Result};
+
+/// The length in bytes of the random Argon2id salt.
+const SALT_LEN: usize = 16;
+/// The length in bytes of the XChaCha20-Poly1305 nonce.
+const NONCE_LEN: usize = 24;
+/// The length in bytes of the derived key.
+const KEY_LEN: usize = 32;
+/// The fixed plaintext sealed into the verify blob.
+const VERIFY_CONST: &[u8] = b"keyring-master-verify";
+/// The length in bytes of the sealed verify blob (plaintext plus a 16-byte tag).
+const VERIFY_CT_LEN: usize = VERIFY_CONST.len() + 16;
+
+/// A [CredentialApi] wrapper that encrypts secrets under a master passphrase.
+///
+/// The inner credential supplies the actual storage; this wrapper only
+/// transforms the bytes that cross the [set_secret](CredentialApi::set_secret)
+/// and [get_secret](CredentialApi::get_secret) boundary.
+pub struct MasterEncryptedCredential {
+    inner: Box<Credential>,
+    passphrase: String,
+}
+
+impl std::fmt::Debug for MasterEncryptedCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // never print the passphrase
+        f.debug_struct("MasterEncryptedCredential")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MasterEncryptedCredential {
+    /// Wrap an inner credential so its secret is encrypted with `passphrase`.
+    pub fn new(inner: Box<Credential>, passphrase: &str) -> Self {
+        Self {
+            inner,
+            passphrase: passphrase.to_string(),
+        }
+    }
+
+    /// Derive the AEAD key from the passphrase and salt with Argon2id.
+    ///
+    /// The returned key must be [zeroized](Zeroize::zeroize) by the caller once
+    /// it is no longer needed.
+    fn derive_key(&self, salt: &[u8]) -> Result<[u8; KEY_LEN]> {
+        let mut key = [0u8; KEY_LEN];
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+            .map_err(|err| ErrorCode::Invalid("argon2".to_string(), err.to_string()))?;
+        Ok(key)
+    }
+
+    /// Validate `passphrase` against the stored verify blob.
+    ///
+    /// Returns `Ok(())` if the passphrase decrypts the verify blob, a
+    /// [NoEntry](ErrorCode::NoEntry) error if there is no stored credential, and
+    /// an [Invalid](ErrorCode::Invalid) `passphrase` error if the blob fails to
+    /// authenticate.
+    pub fn unlock(&self, passphrase: &str) -> Result<()> {
+        let blob = self.inner.get_secret()?;
+        let min_len = SALT_LEN + NONCE_LEN + VERIFY_CT_LEN + NONCE_LEN;
+        if blob.len() < min_len {
+            return Err(ErrorCode::BadEncoding(blob));
+        }
+        let salt = &blob[..SALT_LEN];
+        let verify_nonce = XNonce::from_slice(&blob[SALT_LEN..SALT_LEN + NONCE_LEN]);
+        let verify_ct = &blob[SALT_LEN + NONCE_LEN..SALT_LEN + NONCE_LEN + VERIFY_CT_LEN];
+
+        let mut key = [0u8; KEY_LEN];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|err| ErrorCode::Invalid("argon2".to_string(), err.to_string()))?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        key.zeroize();
+        match cipher.decrypt(verify_nonce, verify_ct) {
+            Ok(plaintext) if plaintext == VERIFY_CONST => Ok(()),
+            _ => Err(ErrorCode::Invalid(
+                "passphrase".to_string(),
+                "verify blob did not authenticate; wrong passphrase?".to_string(),
+            )),
+        }
+    }
+}
+
+impl CredentialApi for MasterEncryptedCredential {
+    /// Encrypt and store the password as a secret.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Encrypt `secret`, prepend a verify blob, and store the result.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let mut key = self.derive_key(&salt)?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        key.zeroize();
+
+        let verify_nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let verify_ct = cipher
+            .encrypt(&verify_nonce, VERIFY_CONST)
+            .map_err(|err| ErrorCode::PlatformFailure(Box::new(EncryptError(err.to_string()))))?;
+        let secret_nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let secret_ct = cipher
+            .encrypt(&secret_nonce, secret)
+            .map_err(|err| ErrorCode::PlatformFailure(Box::new(EncryptError(err.to_string()))))?;
+
+        let mut blob =
+            Vec::with_capacity(SALT_LEN + NONCE_LEN + verify_ct.len() + NONCE_LEN + secret_ct.len());
+        blob.extend_from_slice(&salt);
+        blob.extend_from_slice(verify_nonce.as_slice());
+        blob.extend_from_slice(&verify_ct);
+        blob.extend_from_slice(secret_nonce.as_slice());
+        blob.extend_from_slice(&secret_ct);
+        self.inner.set_secret(&blob)
+    }
+
+    /// Decrypt the stored blob and decode it as a UTF-8 password.
+    fn get_password(&self) -> Result<String> {
+        let secret = self.get_secret()?;
+        String::from_utf8(secret).map_err(|err| ErrorCode::BadEncoding(err.into_bytes()))
+    }
+
+    /// Read the stored blob from the inner credential and decrypt the secret.
+    ///
+    /// Returns [BadEncoding](ErrorCode::BadEncoding) if the blob is truncated
+    /// and an [Invalid](ErrorCode::Invalid) `passphrase` error if the AEAD tag
+    /// fails to verify.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let blob = self.inner.get_secret()?;
+        let header = SALT_LEN + NONCE_LEN + VERIFY_CT_LEN + NONCE_LEN;
+        if blob.len() < header {
+            return Err(ErrorCode::BadEncoding(blob));
+        }
+        let salt = &blob[..SALT_LEN];
+        let secret_nonce_start = SALT_LEN + NONCE_LEN + VERIFY_CT_LEN;
+        let secret_nonce = XNonce::from_slice(&blob[secret_nonce_start..header]);
+        let secret_ct = &blob[header..];
+
+        let mut key = self.derive_key(salt)?;
+        let cipher = XChaCha20Poly1305::new((&key).into());
+        key.zeroize();
+        cipher.decrypt(secret_nonce, secret_ct).map_err(|_| {
+            ErrorCode::Invalid(
+                "passphrase".to_string(),
+                "could not decrypt secret; wrong passphrase?".to_string(),
+            )
+        })
+    }
+
+    /// Delegate attribute reads to the inner credential.
+    fn get_attributes(&self) -> Result<HashMap<String, String>> {
+        self.inner.get_attributes()
+    }
+
+    /// Delegate attribute updates to the inner credential.
+    fn update_attributes(&self, attributes: &HashMap<&str, &str>) -> Result<()> {
+        self.inner.update_attributes(attributes)
+    }
+
+    /// Delegate deletion to the inner credential.
+    fn delete_credential(&self) -> Result<()> {
+        self.inner.delete_credential()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The builder for [master-encrypted credentials](MasterEncryptedCredential).
+///
+/// It wraps an inner builder, encrypting every credential the inner builder
+/// produces under the configured master passphrase.
+pub struct MasterEncryptedCredentialBuilder {
+    inner: Box<CredentialBuilder>,
+    passphrase: String,
+}
+
+impl std::fmt::Debug for MasterEncryptedCredentialBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // never print the passphrase
+        f.debug_struct("MasterEncryptedCredentialBuilder")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MasterEncryptedCredentialBuilder {
+    /// Wrap an inner builder with a master `passphrase`.
+    pub fn new(inner: Box<CredentialBuilder>, passphrase: &str) -> Self {
+        Self {
+            inner,
+            passphrase: passphrase.to_string(),
+        }
+    }
+}
+
+impl CredentialBuilderApi for MasterEncryptedCredentialBuilder {
+    /// Build the inner credential and wrap it in a [MasterEncryptedCredential].
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        let inner = self.inner.build(target, service, user)?;
+        Ok(Box::new(MasterEncryptedCredential::new(
+            inner,
+            &self.passphrase,
+        )))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// The persistence of this store is that of the inner store.
+    fn persistence(&self) -> super::credential::CredentialPersistence {
+        self.inner.persistence()
+    }
+}
+
+/// A wrapper error carrying an AEAD encryption failure message.
+#[derive(Debug)]
+struct EncryptError(String);
+
+impl std::fmt::Display for EncryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "encryption failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for EncryptError {}
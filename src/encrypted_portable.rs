@@ -0,0 +1,450 @@
+/*!
+
+# Single-file portable encrypted keystore
+
+Where [encrypted_file](crate::encrypted_file) keeps one sealed JSON record per
+entry, this store keeps *every* entry in a single encrypted file.  That makes
+the whole keystore trivially portable — one file to copy between a headless
+server and a laptop, or to check into an encrypted backup — and is the on-disk
+format the [export](crate::export) subsystem emits.  Like the encrypted-file
+store, it gives CI and containers with no D-Bus/Secret Service real persistence.
+
+The sealing follows the encrypted-blob technique used by the Aerogramme storage
+layer:
+
+1. the in-memory state — a map from `(target, service, user)` to
+   `(attributes, secret)` — is serialized to a byte buffer with MessagePack,
+2. the buffer is compressed with zstd,
+3. the compressed buffer is sealed with a libsodium-style secretbox
+   (XSalsa20-Poly1305) under a fresh random 24-byte nonce.
+
+The symmetric key is derived from the caller's passphrase with Argon2id over a
+random salt generated when the file is first created.  The file layout is
+therefore `salt || nonce || ciphertext`.
+
+Writes mutate the cached map and then atomically rewrite the file (write to a
+temporary sibling, then rename over the original) so a crash mid-write can never
+truncate the keystore.  The decrypted map is cached behind a `Mutex` so repeated
+reads don't re-derive the key.
+
+Critical invariants: a write is refused with [NoStorageAccess](crate::Error::NoStorageAccess)
+if the passphrase cannot decrypt an existing file (so a typo'd passphrase never
+overwrites a good keystore with a fresh empty one), and the plaintext buffer is
+zeroized after it has been re-encrypted.
+ */
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use crypto_secretbox::aead::{Aead, KeyInit};
+use crypto_secretbox::{Nonce, XSalsa20Poly1305};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
+};
+use super::error::{decode_password, Error, Result};
+
+/// The length in bytes of the random Argon2id salt stored in the file header.
+pub(crate) const SALT_LEN: usize = 16;
+/// The length in bytes of the XSalsa20-Poly1305 nonce.
+const NONCE_LEN: usize = 24;
+/// The length in bytes of the derived secretbox key.
+pub(crate) const KEY_LEN: usize = 32;
+/// The zstd compression level used when sealing the map.
+const ZSTD_LEVEL: i32 = 3;
+
+/// The identifying triple for an entry, used as the map key.
+type EntryKey = (Option<String>, String, String);
+
+/// The stored value for an entry: its attributes and secret bytes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Record {
+    attributes: HashMap<String, String>,
+    secret: Vec<u8>,
+}
+
+/// The decrypted in-memory state of the whole keystore.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Store {
+    entries: HashMap<String, Record>,
+}
+
+/// Shared, mutable state behind every credential built by one builder.
+///
+/// The salt is fixed for the life of the file (it is generated when the file is
+/// first created and re-read on reopen); the passphrase-derived key and the
+/// cached map live here so all entries share one decrypted copy.
+#[derive(Debug)]
+struct Shared {
+    path: PathBuf,
+    passphrase: String,
+    salt: [u8; SALT_LEN],
+    store: Mutex<Store>,
+}
+
+impl Shared {
+    /// Open `path` under `passphrase`, decrypting its map into the cache, or
+    /// start a fresh empty store (with a new random salt) if the file is absent.
+    fn open(path: &Path, passphrase: &str) -> Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => {
+                let (salt, store) = decrypt_file(&bytes, passphrase)?;
+                Ok(Self {
+                    path: path.to_path_buf(),
+                    passphrase: passphrase.to_string(),
+                    salt,
+                    store: Mutex::new(store),
+                })
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                let mut salt = [0u8; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                Ok(Self {
+                    path: path.to_path_buf(),
+                    passphrase: passphrase.to_string(),
+                    salt,
+                    store: Mutex::new(Store::default()),
+                })
+            }
+            Err(err) => Err(Error::NoStorageAccess(Box::new(err))),
+        }
+    }
+
+    /// Derive the secretbox key from the passphrase and this file's salt.
+    fn derive_key(&self) -> Result<[u8; KEY_LEN]> {
+        derive_key(self.passphrase.as_bytes(), &self.salt)
+    }
+
+    /// Seal the current map and atomically rewrite the backing file.
+    fn flush(&self, store: &Store) -> Result<()> {
+        let mut key = self.derive_key()?;
+        let mut plaintext = rmp_serde::to_vec(store)
+            .map_err(|err| Error::PlatformFailure(Box::new(SealError(err.to_string()))))?;
+        let sealed = compress_and_seal(&key, &plaintext)?;
+        key.zeroize();
+        plaintext.zeroize();
+
+        let mut blob = Vec::with_capacity(SALT_LEN + sealed.len());
+        blob.extend_from_slice(&self.salt);
+        blob.extend_from_slice(&sealed);
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, &blob).map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+        std::fs::rename(&tmp, &self.path).map_err(|err| Error::PlatformFailure(Box::new(err)))
+    }
+}
+
+/// A credential stored as one entry in a single-file encrypted keystore.
+#[derive(Debug, Clone)]
+pub struct PortableCredential {
+    shared: Arc<Shared>,
+    key: EntryKey,
+}
+
+impl PortableCredential {
+    /// The map key string for this credential's identifying triple.
+    fn map_key(&self) -> String {
+        let (target, service, user) = &self.key;
+        format!(
+            "{}\u{0}{}\u{0}{}",
+            target.as_deref().unwrap_or_default(),
+            service,
+            user
+        )
+    }
+}
+
+impl CredentialApi for PortableCredential {
+    /// Store the password as a UTF-8 secret.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Insert or replace this entry's secret and rewrite the file.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let mut store = self.shared.store.lock().expect("poisoned portable store lock");
+        let record = store.entries.entry(self.map_key()).or_default();
+        record.secret = secret.to_vec();
+        self.shared.flush(&store)
+    }
+
+    /// Decode this entry's secret as a UTF-8 password.
+    fn get_password(&self) -> Result<String> {
+        decode_password(self.get_secret()?)
+    }
+
+    /// Read this entry's secret from the cached map.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let store = self.shared.store.lock().expect("poisoned portable store lock");
+        store
+            .entries
+            .get(&self.map_key())
+            .map(|record| record.secret.clone())
+            .ok_or(Error::NoEntry)
+    }
+
+    /// Read this entry's stored attributes.
+    fn get_attributes(&self) -> Result<HashMap<String, String>> {
+        let store = self.shared.store.lock().expect("poisoned portable store lock");
+        store
+            .entries
+            .get(&self.map_key())
+            .map(|record| record.attributes.clone())
+            .ok_or(Error::NoEntry)
+    }
+
+    /// Merge `attributes` into this entry's stored attributes and rewrite.
+    fn update_attributes(&self, attributes: &HashMap<&str, &str>) -> Result<()> {
+        let mut store = self.shared.store.lock().expect("poisoned portable store lock");
+        let record = store.entries.get_mut(&self.map_key()).ok_or(Error::NoEntry)?;
+        for (key, value) in attributes {
+            record.attributes.insert(key.to_string(), value.to_string());
+        }
+        self.shared.flush(&store)
+    }
+
+    /// This store keeps an open-ended attribute map per entry.
+    fn capabilities(&self) -> super::credential::CredentialCapabilities {
+        super::credential::CredentialCapabilities {
+            attributes: super::credential::AttributeSupport::Arbitrary,
+        }
+    }
+
+    /// Remove this entry and rewrite the file.
+    fn delete_credential(&self) -> Result<()> {
+        let mut store = self.shared.store.lock().expect("poisoned portable store lock");
+        if store.entries.remove(&self.map_key()).is_none() {
+            return Err(Error::NoEntry);
+        }
+        self.shared.flush(&store)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The builder for a single-file encrypted keystore.
+///
+/// It holds the shared, already-decrypted state, so every entry it builds reads
+/// and writes the same file.
+pub struct PortableCredentialBuilder {
+    shared: Arc<Shared>,
+}
+
+impl std::fmt::Debug for PortableCredentialBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // never print the passphrase held in the shared state
+        f.debug_struct("PortableCredentialBuilder")
+            .field("path", &self.shared.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialBuilderApi for PortableCredentialBuilder {
+    /// Build a credential for the given triple against the shared keystore.
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        Ok(Box::new(PortableCredential {
+            shared: Arc::clone(&self.shared),
+            key: (
+                target.map(str::to_string),
+                service.to_string(),
+                user.to_string(),
+            ),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// The keystore lives on disk until its entries are explicitly deleted.
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+}
+
+/// Return a single-file encrypted keystore builder backed by `path`, sealing
+/// the file under `passphrase`.
+///
+/// The file is opened (and its map decrypted into the cache) now; a passphrase
+/// that can't decrypt an existing file surfaces a
+/// [NoStorageAccess](Error::NoStorageAccess) error here rather than on first
+/// write.
+pub fn default_credential_builder<P: AsRef<Path>>(
+    path: P,
+    passphrase: &str,
+) -> Result<Box<CredentialBuilder>> {
+    let shared = Shared::open(path.as_ref(), passphrase)?;
+    Ok(Box::new(PortableCredentialBuilder {
+        shared: Arc::new(shared),
+    }))
+}
+
+/// Derive a 32-byte secretbox key from a passphrase and salt with Argon2id.
+///
+/// `pub(crate)` so other sealed-at-rest formats (see [export](crate::export))
+/// can derive a compatible key without duplicating the Argon2id parameters.
+pub(crate) fn derive_key(passphrase: &[u8], salt: &[u8]) -> Result<[u8; KEY_LEN]> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase, salt, &mut key)
+        .map_err(|err| Error::Invalid("argon2".to_string(), err.to_string()))?;
+    Ok(key)
+}
+
+/// Compress `plaintext` with zstd and seal it under `key`, returning a fresh
+/// `nonce || ciphertext` blob.
+///
+/// `pub(crate)` so [export](crate::export) can seal a whole document the same
+/// way a single keystore file is sealed, rather than reimplementing the
+/// compress-then-encrypt pipeline.
+pub(crate) fn compress_and_seal(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let cipher = XSalsa20Poly1305::new(key.into());
+    let mut compressed = zstd::encode_all(plaintext, ZSTD_LEVEL)
+        .map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, compressed.as_slice())
+        .map_err(|err| Error::PlatformFailure(Box::new(SealError(err.to_string()))))?;
+    compressed.zeroize();
+
+    let mut blob = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+/// Reverse [compress_and_seal]: authenticate and decrypt a `nonce || ciphertext`
+/// blob under `key`, then decompress it back to the original plaintext.
+///
+/// An authentication failure (wrong key, or tampered ciphertext) surfaces as
+/// [NoStorageAccess](Error::NoStorageAccess) rather than a generic decode
+/// error, so callers can tell "wrong passphrase" apart from "corrupt file".
+pub(crate) fn decompress_and_unseal(key: &[u8; KEY_LEN], blob: &[u8]) -> Result<Vec<u8>> {
+    if blob.len() < NONCE_LEN {
+        return Err(Error::NoStorageAccess(Box::new(SealError(
+            "sealed data is truncated".to_string(),
+        ))));
+    }
+    let cipher = XSalsa20Poly1305::new(key.into());
+    let nonce = Nonce::from_slice(&blob[..NONCE_LEN]);
+    let ciphertext = &blob[NONCE_LEN..];
+    let compressed = cipher.decrypt(nonce, ciphertext).map_err(|_| {
+        Error::NoStorageAccess(Box::new(SealError(
+            "could not authenticate sealed data; wrong passphrase?".to_string(),
+        )))
+    })?;
+    zstd::decode_all(compressed.as_slice()).map_err(|err| Error::NoStorageAccess(Box::new(err)))
+}
+
+/// Split a `salt || nonce || ciphertext` file, decrypt it, and decompress the
+/// MessagePack map.
+///
+/// A passphrase that can't authenticate the ciphertext surfaces as
+/// [NoStorageAccess](Error::NoStorageAccess), so a write path can refuse to
+/// clobber a keystore it cannot read.
+fn decrypt_file(bytes: &[u8], passphrase: &str) -> Result<([u8; SALT_LEN], Store)> {
+    if bytes.len() < SALT_LEN + NONCE_LEN {
+        return Err(Error::NoStorageAccess(Box::new(SealError(
+            "keystore file is truncated".to_string(),
+        ))));
+    }
+    let mut salt = [0u8; SALT_LEN];
+    salt.copy_from_slice(&bytes[..SALT_LEN]);
+
+    let mut key = derive_key(passphrase.as_bytes(), &salt)?;
+    let plaintext = decompress_and_unseal(&key, &bytes[SALT_LEN..]);
+    key.zeroize();
+    let plaintext = plaintext?;
+    let store: Store = rmp_serde::from_slice(&plaintext)
+        .map_err(|err| Error::NoStorageAccess(Box::new(SealError(err.to_string()))))?;
+    Ok((salt, store))
+}
+
+/// A wrapper error carrying a sealing/serialization failure message.
+#[derive(Debug)]
+struct SealError(String);
+
+impl std::fmt::Display for SealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "portable keystore error: {}", self.0)
+    }
+}
+
+impl std::error::Error for SealError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::generate_random_string, Entry, Error};
+
+    use super::default_credential_builder;
+
+    fn temp_file() -> std::path::PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("keyring-rs-portable-{}.bin", generate_random_string()));
+        path
+    }
+
+    fn entry_new(path: &std::path::Path, passphrase: &str, service: &str, user: &str) -> Entry {
+        let builder = default_credential_builder(path, passphrase)
+            .expect("Can't open portable keystore");
+        let credential = builder
+            .build(None, service, user)
+            .expect("Can't build portable credential");
+        Entry::new_with_credential(credential)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = temp_file();
+        let name = generate_random_string();
+        let entry = entry_new(&path, "pw", &name, &name);
+        entry.set_password("secret").expect("Can't set password");
+        assert_eq!(entry.get_password().expect("Can't get password"), "secret");
+        entry.delete_credential().expect("Can't delete password");
+        assert!(matches!(entry.get_password(), Err(Error::NoEntry)));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_persists_across_reopen() {
+        let path = temp_file();
+        let name = generate_random_string();
+        entry_new(&path, "pw", &name, &name)
+            .set_password("durable")
+            .expect("Can't set password");
+        // a fresh builder re-reads the file from disk
+        let reopened = entry_new(&path, "pw", &name, &name);
+        assert_eq!(reopened.get_password().expect("Can't get password"), "durable");
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_wrong_passphrase_is_no_storage_access() {
+        let path = temp_file();
+        let name = generate_random_string();
+        entry_new(&path, "right", &name, &name)
+            .set_password("secret")
+            .expect("Can't set password");
+        assert!(matches!(
+            default_credential_builder(&path, "wrong"),
+            Err(Error::NoStorageAccess(_))
+        ));
+        let _ = std::fs::remove_file(path);
+    }
+}
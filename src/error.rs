@@ -11,7 +11,6 @@ is not much of a burden on the platform-specific store providers.)
 
  */
 
-#[derive(Debug)]
 /// Each variant of the `Error` enum provides a summary of the error.
 /// More details, if relevant, are contained in the associated value,
 /// which may be platform-specific.
@@ -45,10 +44,45 @@ pub enum Error {
     /// attached value gives the name of the attribute
     /// and the reason it's invalid.
     Invalid(String, String),
+    /// This indicates that the underlying credential store does not
+    /// support the requested capability (for example, reading a
+    /// certificate or signing with a private key on a store that only
+    /// holds opaque secrets).  The attached value names the operation.
+    NotSupported(String),
+    /// This indicates that a lookup matched more than one credential in the
+    /// underlying store.  This can only happen on platforms that key entries
+    /// by more than the target/service/user triple, and then only if a
+    /// third party wrote the ambiguous credential (or a caller searched on a
+    /// looser set of attributes, see
+    /// [find](crate::credential::CredentialBuilderApi::find)).  One
+    /// credential is attached for each match, so the caller can inspect them
+    /// and disambiguate instead of just failing.
+    Ambiguous(Vec<Box<crate::credential::Credential>>),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl std::fmt::Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::PlatformFailure(err) => f.debug_tuple("PlatformFailure").field(err).finish(),
+            Error::NoStorageAccess(err) => f.debug_tuple("NoStorageAccess").field(err).finish(),
+            Error::NoEntry => write!(f, "NoEntry"),
+            Error::BadEncoding(bytes) => f.debug_tuple("BadEncoding").field(bytes).finish(),
+            Error::TooLong(name, len) => f.debug_tuple("TooLong").field(name).field(len).finish(),
+            Error::Invalid(attr, reason) => {
+                f.debug_tuple("Invalid").field(attr).field(reason).finish()
+            }
+            Error::NotSupported(op) => f.debug_tuple("NotSupported").field(op).finish(),
+            // `Credential` carries no `Debug` bound, so we can't print the
+            // matches themselves; the count is still useful in a log line.
+            Error::Ambiguous(creds) => {
+                write!(f, "Ambiguous({} matching credentials)", creds.len())
+            }
+        }
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -66,6 +100,12 @@ impl std::fmt::Display for Error {
             Error::Invalid(attr, reason) => {
                 write!(f, "Attribute {} is invalid: {}", attr, reason)
             }
+            Error::NotSupported(op) => {
+                write!(f, "Operation '{}' is not supported by this store", op)
+            }
+            Error::Ambiguous(creds) => {
+                write!(f, "Found {} matching credentials, expected one", creds.len())
+            }
         }
     }
 }
@@ -85,6 +125,148 @@ pub fn decode_password(bytes: Vec<u8>) -> Result<String> {
     String::from_utf8(bytes.clone()).map_err(|_| Error::BadEncoding(bytes))
 }
 
+/// A platform error reconstructed from a serialized [Error].
+///
+/// The escape-hatch variants [PlatformFailure](Error::PlatformFailure) and
+/// [NoStorageAccess](Error::NoStorageAccess) box a platform-specific
+/// `dyn std::error::Error` that cannot itself cross a serialization boundary.
+/// When such an error is serialized we capture its causal chain as an ordered
+/// list of `Display` strings (outermost first, walked via
+/// [source](std::error::Error::source)); on the far side the boxed error is
+/// rebuilt as a `TransportError` whose own `source()` replays that list, so
+/// `{:#}` and the `?` operator still surface the whole chain.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+pub struct TransportError {
+    message: String,
+    source: Option<Box<TransportError>>,
+}
+
+#[cfg(feature = "serde")]
+impl TransportError {
+    /// Rebuild a linked error chain from its `Display` strings, outermost first.
+    fn from_chain(chain: &[String]) -> Self {
+        let mut iter = chain.iter().rev();
+        let mut node = TransportError {
+            message: iter.next().cloned().unwrap_or_default(),
+            source: None,
+        };
+        for message in iter {
+            node = TransportError {
+                message: message.clone(),
+                source: Some(Box::new(node)),
+            };
+        }
+        node
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for TransportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|err| err as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Collect an error's causal chain as `Display` strings, outermost first.
+#[cfg(feature = "serde")]
+fn chain_strings(err: &(dyn std::error::Error)) -> Vec<String> {
+    let mut out = vec![err.to_string()];
+    let mut source = err.source();
+    while let Some(err) = source {
+        out.push(err.to_string());
+        source = err.source();
+    }
+    out
+}
+
+/// The serialized shape of an [Error].
+///
+/// The escape-hatch variants degrade to their captured `Display` chain; the
+/// structured variants keep their data intact so they round-trip exactly.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", content = "data")]
+enum ErrorRepr {
+    PlatformFailure(Vec<String>),
+    NoStorageAccess(Vec<String>),
+    NoEntry,
+    BadEncoding(Vec<u8>),
+    TooLong(String, u32),
+    Invalid(String, String),
+    NotSupported(String),
+    /// Credential handles can't cross a serialization boundary; the match
+    /// count is captured here for diagnostics, but the far side rebuilds an
+    /// empty match list (see the `From<ErrorRepr>` impl).
+    Ambiguous(usize),
+}
+
+#[cfg(feature = "serde")]
+impl From<&Error> for ErrorRepr {
+    fn from(err: &Error) -> Self {
+        match err {
+            Error::PlatformFailure(cause) => {
+                ErrorRepr::PlatformFailure(chain_strings(cause.as_ref()))
+            }
+            Error::NoStorageAccess(cause) => {
+                ErrorRepr::NoStorageAccess(chain_strings(cause.as_ref()))
+            }
+            Error::NoEntry => ErrorRepr::NoEntry,
+            Error::BadEncoding(bytes) => ErrorRepr::BadEncoding(bytes.clone()),
+            Error::TooLong(name, len) => ErrorRepr::TooLong(name.clone(), *len),
+            Error::Invalid(attr, reason) => ErrorRepr::Invalid(attr.clone(), reason.clone()),
+            Error::NotSupported(op) => ErrorRepr::NotSupported(op.clone()),
+            Error::Ambiguous(creds) => ErrorRepr::Ambiguous(creds.len()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<ErrorRepr> for Error {
+    fn from(repr: ErrorRepr) -> Self {
+        match repr {
+            ErrorRepr::PlatformFailure(chain) => {
+                Error::PlatformFailure(Box::new(TransportError::from_chain(&chain)))
+            }
+            ErrorRepr::NoStorageAccess(chain) => {
+                Error::NoStorageAccess(Box::new(TransportError::from_chain(&chain)))
+            }
+            ErrorRepr::NoEntry => Error::NoEntry,
+            ErrorRepr::BadEncoding(bytes) => Error::BadEncoding(bytes),
+            ErrorRepr::TooLong(name, len) => Error::TooLong(name, len),
+            ErrorRepr::Invalid(attr, reason) => Error::Invalid(attr, reason),
+            ErrorRepr::NotSupported(op) => Error::NotSupported(op),
+            // the original credential handles can't be reconstructed from the
+            // wire, so the match count is necessarily lost here too; the
+            // variant survives the round trip, the detail doesn't
+            ErrorRepr::Ambiguous(_count) => Error::Ambiguous(Vec::new()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Error {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        ErrorRepr::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Error {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        ErrorRepr::deserialize(deserializer).map(Error::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
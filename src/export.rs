@@ -0,0 +1,428 @@
+/*!
+
+# Bulk credential export / import
+
+[migrate](crate::migrate) moves one credential at a time between two stores
+you already have entries open for.  This module is the vault-export version of
+the same idea: it walks every entry a [CredentialBuilder] can enumerate via
+[find](super::credential::CredentialBuilderApi::find) and serializes the whole
+set into one portable [ExportDocument] — useful when moving off a platform
+keychain entirely (say from the Secret Service to the
+[encrypted-file](crate::encrypted_file) store) or backing a keystore up before
+reinstalling a machine.
+
+The document is a flat list of `{target, service, user, attributes, secret}`
+records. A `dyn Credential` has no generic accessor for its own identity, so
+`target`/`service`/`user` are recovered best-effort from the conventional
+attribute names the backends in this crate already use (see
+[attrs](crate::attrs) for why those names aren't uniform); a credential whose
+store doesn't expose them still exports, just with empty identity fields.
+
+[to_json]/[from_json] give a human-readable, diffable format; [to_csv]/
+[from_csv] give a spreadsheet-friendly one at the cost of flattening
+attributes into a single column and base64-encoding the secret. Either can be
+wrapped in [seal]/[unseal], which reuses the zstd-then-secretbox pipeline from
+the [encrypted_portable](crate::encrypted_portable) keystore so an exported
+document never touches disk with its secrets in cleartext.
+
+[import] reverses the process: for each record it calls
+[build](super::credential::CredentialBuilderApi::build) and replays
+`set_secret`/`update_attributes`, reporting one [Result] per record rather than
+aborting the batch — the same partial-failure contract
+[migrate_batch](crate::migrate::migrate_batch) uses — so one credential
+reporting [NoStorageAccess](crate::Error::NoStorageAccess) doesn't lose the
+rest of the import.
+ */
+
+use std::collections::HashMap;
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde::{Deserialize, Serialize};
+
+use super::credential::{Credential, CredentialBuilder};
+use super::encrypted_portable::{self, KEY_LEN, SALT_LEN};
+use super::error::{Error, Result};
+
+/// The current on-disk format version for [ExportDocument].
+///
+/// [from_json] and [unseal] reject a document reporting a newer version than
+/// this, so a future format change fails loudly instead of silently dropping
+/// fields it doesn't understand.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// One exported entry: its identity, attributes, and secret.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub target: Option<String>,
+    pub service: String,
+    pub user: String,
+    pub attributes: HashMap<String, String>,
+    pub secret: Vec<u8>,
+}
+
+/// A versioned collection of [ExportRecord]s produced by [export].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportDocument {
+    pub version: u32,
+    pub entries: Vec<ExportRecord>,
+}
+
+/// Enumerate every entry `builder` can reach and collect them into a document.
+///
+/// Enumeration is delegated to
+/// [find](super::credential::CredentialBuilderApi::find) with an empty
+/// attribute filter, which on backends that support it returns every
+/// credential the store holds. A backend that can't enumerate reports
+/// [NotSupported](Error::NotSupported), which is propagated here rather than
+/// silently producing an empty document.
+pub fn export(builder: &CredentialBuilder) -> Result<ExportDocument> {
+    let credentials = builder.find(&HashMap::new())?;
+    let entries = credentials
+        .iter()
+        .map(|credential| export_one(credential.as_ref()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ExportDocument {
+        version: FORMAT_VERSION,
+        entries,
+    })
+}
+
+/// Read one credential's secret and attributes into an [ExportRecord].
+fn export_one(credential: &Credential) -> Result<ExportRecord> {
+    let secret = credential.get_secret()?;
+    let attributes = credential.get_attributes().unwrap_or_default();
+    let (target, service, user) = identity_from_attributes(&attributes);
+    Ok(ExportRecord {
+        target,
+        service,
+        user,
+        attributes,
+        secret,
+    })
+}
+
+/// Recover the conventional `target`/`service`/`user` attribute names the
+/// backends in this crate use (see [attrs](crate::attrs) for why they aren't
+/// uniform), falling back to an empty string for a field no known attribute
+/// supplies.
+fn identity_from_attributes(attributes: &HashMap<String, String>) -> (Option<String>, String, String) {
+    let target = attributes.get("target").cloned();
+    let service = attributes.get("service").cloned().unwrap_or_default();
+    let user = attributes
+        .get("user")
+        .or_else(|| attributes.get("username"))
+        .cloned()
+        .unwrap_or_default();
+    (target, service, user)
+}
+
+/// The outcome of replaying one [ExportRecord] in [import].
+#[derive(Debug)]
+pub struct Imported {
+    /// The identity of the record this outcome is for.
+    pub target: Option<String>,
+    pub service: String,
+    pub user: String,
+    /// Whether the secret (and, best-effort, the attributes) were written.
+    pub result: Result<()>,
+}
+
+/// Replay every record in `document` into entries built from `builder`.
+///
+/// Each record is processed independently; a failure on one (most commonly
+/// [NoStorageAccess](Error::NoStorageAccess) or
+/// [Invalid](Error::Invalid) from a bad target/service/user) is reported in
+/// place rather than aborting the rest of the batch.
+pub fn import(builder: &CredentialBuilder, document: &ExportDocument) -> Vec<Imported> {
+    document
+        .entries
+        .iter()
+        .map(|record| Imported {
+            target: record.target.clone(),
+            service: record.service.clone(),
+            user: record.user.clone(),
+            result: import_one(builder, record),
+        })
+        .collect()
+}
+
+/// Build the credential named by `record` and replay its secret and
+/// attributes onto it.
+///
+/// Attributes are replayed best-effort, the same as
+/// [migrate_credential](crate::migrate::migrate_credential): a store that
+/// can't keep them shouldn't fail the import.
+fn import_one(builder: &CredentialBuilder, record: &ExportRecord) -> Result<()> {
+    let credential = builder.build(record.target.as_deref(), &record.service, &record.user)?;
+    credential.set_secret(&record.secret)?;
+    if !record.attributes.is_empty() {
+        let borrowed: HashMap<&str, &str> = record
+            .attributes
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        let _ = credential.update_attributes(&borrowed);
+    }
+    Ok(())
+}
+
+/// Serialize `document` as pretty-printed JSON.
+pub fn to_json(document: &ExportDocument) -> Result<String> {
+    serde_json::to_string_pretty(document)
+        .map_err(|err| Error::Invalid("export".to_string(), err.to_string()))
+}
+
+/// Parse a document previously produced by [to_json].
+pub fn from_json(text: &str) -> Result<ExportDocument> {
+    let document: ExportDocument = serde_json::from_str(text)
+        .map_err(|err| Error::Invalid("export".to_string(), err.to_string()))?;
+    check_version(document.version)?;
+    Ok(document)
+}
+
+/// Serialize `document` as CSV, one row per record.
+///
+/// CSV has no native way to carry a nested map or arbitrary binary data, so
+/// attributes are flattened into a single `key=value;key=value` column and
+/// the secret is base64-encoded.
+pub fn to_csv(document: &ExportDocument) -> String {
+    use base64::prelude::*;
+
+    let mut out = String::from("target,service,user,attributes,secret\n");
+    for record in &document.entries {
+        let attributes = record
+            .attributes
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(";");
+        let fields = [
+            record.target.as_deref().unwrap_or_default(),
+            &record.service,
+            &record.user,
+            &attributes,
+            &BASE64_STANDARD.encode(&record.secret),
+        ];
+        out.push_str(&fields.iter().map(|f| csv_quote(f)).collect::<Vec<_>>().join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Parse a document previously produced by [to_csv].
+pub fn from_csv(text: &str) -> Result<ExportDocument> {
+    use base64::prelude::*;
+
+    let mut lines = text.lines();
+    lines.next(); // header
+    let mut entries = Vec::new();
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+        let fields = csv_split(line);
+        let [target, service, user, attributes, secret] = <[String; 5]>::try_from(fields)
+            .map_err(|_| Error::Invalid("export".to_string(), "malformed CSV row".to_string()))?;
+        let attributes = if attributes.is_empty() {
+            HashMap::new()
+        } else {
+            attributes
+                .split(';')
+                .map(|pair| {
+                    pair.split_once('=')
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .ok_or_else(|| {
+                            Error::Invalid("export".to_string(), format!("'{pair}' is not key=value"))
+                        })
+                })
+                .collect::<Result<HashMap<_, _>>>()?
+        };
+        let secret = BASE64_STANDARD
+            .decode(secret)
+            .map_err(|err| Error::Invalid("export".to_string(), err.to_string()))?;
+        entries.push(ExportRecord {
+            target: if target.is_empty() { None } else { Some(target) },
+            service,
+            user,
+            attributes,
+            secret,
+        });
+    }
+    Ok(ExportDocument {
+        version: FORMAT_VERSION,
+        entries,
+    })
+}
+
+/// Quote a CSV field if it contains a comma, quote, or newline.
+fn csv_quote(field: &str) -> String {
+    if field.contains(['"', ',', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split one CSV line into its unescaped fields.
+fn csv_split(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push(std::mem::take(&mut field));
+            }
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Seal a serialized document so it never reaches disk in cleartext.
+///
+/// Reuses the same zstd-then-secretbox pipeline (Argon2id key derivation over
+/// a fresh random salt, `salt || nonce || ciphertext` layout) as the
+/// [encrypted_portable](crate::encrypted_portable) keystore, so the two
+/// formats can share an implementation and a security review.
+pub fn seal(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = encrypted_portable::derive_key(passphrase.as_bytes(), &salt)?;
+    let sealed = encrypted_portable::compress_and_seal(&key, plaintext)?;
+
+    let mut blob = Vec::with_capacity(SALT_LEN + sealed.len());
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&sealed);
+    Ok(blob)
+}
+
+/// Reverse [seal]: recover the plaintext bytes previously sealed under
+/// `passphrase`.
+///
+/// A wrong passphrase (or a tampered file) surfaces as
+/// [NoStorageAccess](Error::NoStorageAccess), the same failure mode
+/// [encrypted_portable](crate::encrypted_portable) uses for an unreadable
+/// keystore.
+pub fn unseal(blob: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    if blob.len() < SALT_LEN {
+        return Err(Error::NoStorageAccess(Box::new(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "sealed export is truncated",
+        ))));
+    }
+    let mut salt = [0u8; SALT_LEN];
+    salt.copy_from_slice(&blob[..SALT_LEN]);
+    let key: [u8; KEY_LEN] = encrypted_portable::derive_key(passphrase.as_bytes(), &salt)?;
+    encrypted_portable::decompress_and_unseal(&key, &blob[SALT_LEN..])
+}
+
+/// Error out on a document reporting a format version newer than we support.
+fn check_version(version: u32) -> Result<()> {
+    if version > FORMAT_VERSION {
+        Err(Error::Invalid(
+            "export".to_string(),
+            format!("document version {version} is newer than supported version {FORMAT_VERSION}"),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+    use crate::tests::generate_random_string;
+
+    #[test]
+    fn test_json_round_trip() {
+        let document = ExportDocument {
+            version: FORMAT_VERSION,
+            entries: vec![ExportRecord {
+                target: None,
+                service: "svc".to_string(),
+                user: "user".to_string(),
+                attributes: HashMap::from([("k".to_string(), "v".to_string())]),
+                secret: b"hello".to_vec(),
+            }],
+        };
+        let json = to_json(&document).expect("Can't serialize to JSON");
+        let parsed = from_json(&json).expect("Can't parse JSON");
+        assert_eq!(document, parsed);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let document = ExportDocument {
+            version: FORMAT_VERSION,
+            entries: vec![ExportRecord {
+                target: Some("weird, \"target\"".to_string()),
+                service: "svc".to_string(),
+                user: "user".to_string(),
+                attributes: HashMap::from([("k1".to_string(), "v1".to_string())]),
+                secret: b"binary\0secret".to_vec(),
+            }],
+        };
+        let csv = to_csv(&document);
+        let parsed = from_csv(&csv).expect("Can't parse CSV");
+        assert_eq!(document.entries, parsed.entries);
+    }
+
+    #[test]
+    fn test_seal_round_trip() {
+        let plaintext = b"super secret export bytes";
+        let sealed = seal(plaintext, "correct horse").expect("Can't seal");
+        let unsealed = unseal(&sealed, "correct horse").expect("Can't unseal");
+        assert_eq!(plaintext.to_vec(), unsealed);
+        assert!(unseal(&sealed, "wrong horse").is_err());
+    }
+
+    #[test]
+    fn test_rejects_future_version() {
+        let document = ExportDocument {
+            version: FORMAT_VERSION + 1,
+            entries: Vec::new(),
+        };
+        let json = serde_json::to_string(&document).unwrap();
+        assert!(matches!(from_json(&json), Err(Error::Invalid(_, _))));
+    }
+
+    #[test]
+    fn test_export_and_import_mock_store() {
+        let builder = mock::default_credential_builder();
+        let service = generate_random_string();
+        let user = generate_random_string();
+        let credential = builder.build(None, &service, &user).expect("Can't build credential");
+        credential.set_password("a password").expect("Can't set password");
+
+        let record = export_one(credential.as_ref()).expect("Can't export credential");
+        assert_eq!(record.secret, b"a password".to_vec());
+
+        let document = ExportDocument {
+            version: FORMAT_VERSION,
+            entries: vec![ExportRecord {
+                target: None,
+                service: service.clone(),
+                user: user.clone(),
+                attributes: HashMap::new(),
+                secret: b"imported password".to_vec(),
+            }],
+        };
+        let results = import(builder.as_ref(), &document);
+        assert_eq!(results.len(), 1);
+        results[0].result.as_ref().expect("Import failed");
+        assert_eq!(
+            credential.get_secret().expect("Can't get secret"),
+            b"imported password".to_vec()
+        );
+    }
+}
@@ -0,0 +1,610 @@
+/*!
+
+# Password-protected encrypted file store
+
+For headless and CI environments there is often no running Secret Service,
+Keychain, or Credential Manager to talk to.  This module provides a
+self-contained backend that keeps secrets in an encrypted file, modelled on the
+[R `keyring` file backend](https://r-lib.github.io/keyring/): a *keyring* is a
+named file protected by a master password, and the usual per-item
+`set`/`get`/`delete` operations are keyed by service and username.
+
+The file is a small JSON document.  Its header carries a random salt and the
+scrypt work factor; the master password is stretched into a 32-byte key with
+scrypt (`r = 8`, `p = 1`, `N = 2^log_n`), exactly as in the
+[EncryptedCredential](crate::encrypted::EncryptedCredential) wrapper.  Each
+item's secret is sealed with XChaCha20-Poly1305 under that key (a fresh nonce
+per write), while its lookup attributes are stored in cleartext so items can be
+enumerated and addressed while the keyring is locked.  A fixed *verifier* blob
+in the header lets [unlock](FileKeyring::unlock) reject a wrong master password
+without having to touch any item.
+
+"Locked" simply means the derived key is not held in memory: item secrets can
+still be listed but not decrypted, and [get](FileCredential::get_secret) /
+[set](FileCredential::set_secret) fail with
+[NoStorageAccess](crate::Error::NoStorageAccess) until
+[unlock](FileKeyring::unlock) succeeds.  A freshly
+[created](FileKeyring::create) keyring starts unlocked; one re-[opened](FileKeyring::open)
+from disk starts locked.
+
+The store is exposed as a [CredentialBuilder](crate::CredentialBuilder), so
+ordinary [Entry](crate::Entry) code works against it unchanged once the builder
+is installed with [set_default_credential_builder](crate::set_default_credential_builder).
+ */
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, XChaCha20Poly1305, XNonce};
+use scrypt::{scrypt, Params};
+use serde_json::{json, Map, Value};
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
+};
+use super::error::{Error, Result};
+
+/// The JSON schema version written in the file header.
+const VERSION: u64 = 1;
+/// The length in bytes of the random scrypt salt.
+const SALT_LEN: usize = 16;
+/// The length in bytes of the derived AEAD key.
+const KEY_LEN: usize = 32;
+/// The fixed scrypt `r` parameter.
+const SCRYPT_R: u32 = 8;
+/// The fixed scrypt `p` parameter.
+const SCRYPT_P: u32 = 1;
+/// The default scrypt work factor (`N = 2^15`).
+const DEFAULT_LOG_N: u8 = 15;
+/// The known plaintext sealed in the header to validate a master password.
+const VERIFIER_PLAINTEXT: &[u8] = b"keyring-rs file keyring v1";
+
+/// A secret sealed with XChaCha20-Poly1305: the random nonce and the
+/// ciphertext-plus-tag.
+#[derive(Clone)]
+struct Sealed {
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+/// One stored item: cleartext lookup attributes and the sealed secret.
+#[derive(Clone)]
+struct Item {
+    attributes: HashMap<String, String>,
+    secret: Sealed,
+}
+
+/// The in-memory state of an open keyring file.
+///
+/// The derived key is present only while the keyring is unlocked; everything
+/// else is loaded eagerly so items can be enumerated while locked.
+struct KeyringState {
+    path: PathBuf,
+    log_n: u8,
+    salt: [u8; SALT_LEN],
+    verifier: Sealed,
+    key: Option<[u8; KEY_LEN]>,
+    items: HashMap<String, Item>,
+}
+
+/// A named, master-password-protected keyring file.
+///
+/// Clones share the same underlying file and in-memory state, so a keyring
+/// handed to a [builder](FileCredentialBuilder) and later
+/// [locked](FileKeyring::lock) affects every [Entry](crate::Entry) built from
+/// it.
+#[derive(Clone)]
+pub struct FileKeyring {
+    state: Arc<Mutex<KeyringState>>,
+}
+
+impl std::fmt::Debug for FileKeyring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock().expect("Can't access keyring state");
+        f.debug_struct("FileKeyring")
+            .field("path", &state.path)
+            .field("locked", &state.key.is_none())
+            .field("items", &state.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileKeyring {
+    /// Create a new keyring file at `path` protected by `master_password`.
+    ///
+    /// The file is written immediately with an empty item set and the keyring
+    /// is returned unlocked.  Returns a [PlatformFailure](Error::PlatformFailure)
+    /// if the file cannot be written.
+    pub fn create<P: AsRef<Path>>(path: P, master_password: &str) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let log_n = DEFAULT_LOG_N;
+        let key = derive_key(master_password, log_n, &salt)?;
+        let verifier = seal(&key, VERIFIER_PLAINTEXT)?;
+        let state = KeyringState {
+            path: path.as_ref().to_path_buf(),
+            log_n,
+            salt,
+            verifier,
+            key: Some(key),
+            items: HashMap::new(),
+        };
+        save(&state)?;
+        Ok(Self {
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    /// Open an existing keyring file, returning it in the locked state.
+    ///
+    /// Call [unlock](FileKeyring::unlock) with the master password before
+    /// reading or writing secrets.  Returns [NoEntry](Error::NoEntry) if the
+    /// file does not exist and [BadEncoding](Error::BadEncoding) if it is not a
+    /// keyring file this version understands.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let state = load(path.as_ref())?;
+        Ok(Self {
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    /// Unlock the keyring by deriving and retaining the key.
+    ///
+    /// Returns an [Invalid](Error::Invalid) `master_password` error if the
+    /// password does not match the one the keyring was created with.
+    pub fn unlock(&self, master_password: &str) -> Result<()> {
+        let mut state = self.state.lock().expect("Can't access keyring state");
+        let key = derive_key(master_password, state.log_n, &state.salt)?;
+        open_sealed(&key, &state.verifier).map_err(|_| {
+            Error::Invalid(
+                "master_password".to_string(),
+                "wrong master password for keyring".to_string(),
+            )
+        })?;
+        state.key = Some(key);
+        Ok(())
+    }
+
+    /// Lock the keyring by dropping the in-memory key.
+    pub fn lock(&self) {
+        let mut state = self.state.lock().expect("Can't access keyring state");
+        state.key = None;
+    }
+
+    /// Report whether the keyring is currently locked.
+    pub fn is_locked(&self) -> bool {
+        let state = self.state.lock().expect("Can't access keyring state");
+        state.key.is_none()
+    }
+
+    /// Return a [builder](FileCredentialBuilder) that creates entries in this
+    /// keyring.
+    pub fn builder(&self) -> Box<CredentialBuilder> {
+        Box::new(FileCredentialBuilder {
+            keyring: self.clone(),
+        })
+    }
+}
+
+/// The item key derived from a service and username.
+fn item_key(service: &str, user: &str) -> String {
+    format!("{service}\u{0}{user}")
+}
+
+/// A credential stored in a [FileKeyring], addressed by service and username.
+#[derive(Debug, Clone)]
+pub struct FileCredential {
+    keyring: FileKeyring,
+    service: String,
+    user: String,
+}
+
+impl FileCredential {
+    /// Create a credential for the given service and user in `keyring`.
+    pub fn new_with_target(
+        keyring: &FileKeyring,
+        _target: Option<&str>,
+        service: &str,
+        user: &str,
+    ) -> Result<Self> {
+        Ok(Self {
+            keyring: keyring.clone(),
+            service: service.to_string(),
+            user: user.to_string(),
+        })
+    }
+
+    /// The default lookup attributes (service and user) for this credential.
+    fn default_attributes(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("service".to_string(), self.service.clone()),
+            ("user".to_string(), self.user.clone()),
+        ])
+    }
+}
+
+impl CredentialApi for FileCredential {
+    /// Set a password, encoding it as a UTF-8 secret.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Seal `secret` under the keyring key and persist it.
+    ///
+    /// Fails with [NoStorageAccess](Error::NoStorageAccess) if the keyring is
+    /// locked.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let mut state = self.keyring.state.lock().expect("Can't access keyring");
+        let key = require_unlocked(&state)?;
+        let sealed = seal(&key, secret)?;
+        let item = Item {
+            attributes: self.default_attributes(),
+            secret: sealed,
+        };
+        state.items.insert(item_key(&self.service, &self.user), item);
+        save(&state)
+    }
+
+    /// Retrieve the password, decoding the stored secret as UTF-8.
+    fn get_password(&self) -> Result<String> {
+        let secret = self.get_secret()?;
+        super::error::decode_password(secret)
+    }
+
+    /// Decrypt and return the stored secret.
+    ///
+    /// Fails with [NoStorageAccess](Error::NoStorageAccess) if the keyring is
+    /// locked and [NoEntry](Error::NoEntry) if no item has been set.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let state = self.keyring.state.lock().expect("Can't access keyring");
+        let key = require_unlocked(&state)?;
+        let item = state
+            .items
+            .get(&item_key(&self.service, &self.user))
+            .ok_or(Error::NoEntry)?;
+        open_sealed(&key, &item.secret)
+    }
+
+    /// Return the cleartext lookup attributes of the stored item.
+    fn get_attributes(&self) -> Result<HashMap<String, String>> {
+        let state = self.keyring.state.lock().expect("Can't access keyring");
+        let item = state
+            .items
+            .get(&item_key(&self.service, &self.user))
+            .ok_or(Error::NoEntry)?;
+        Ok(item.attributes.clone())
+    }
+
+    /// Merge additional cleartext attributes into the stored item.
+    fn update_attributes(&self, attributes: &HashMap<&str, &str>) -> Result<()> {
+        let mut state = self.keyring.state.lock().expect("Can't access keyring");
+        let item = state
+            .items
+            .get_mut(&item_key(&self.service, &self.user))
+            .ok_or(Error::NoEntry)?;
+        for (key, value) in attributes {
+            item.attributes.insert(key.to_string(), value.to_string());
+        }
+        save(&state)
+    }
+
+    /// Delete the stored item.
+    ///
+    /// Returns [NoEntry](Error::NoEntry) if there was nothing to delete.
+    fn delete_credential(&self) -> Result<()> {
+        let mut state = self.keyring.state.lock().expect("Can't access keyring");
+        if state
+            .items
+            .remove(&item_key(&self.service, &self.user))
+            .is_none()
+        {
+            return Err(Error::NoEntry);
+        }
+        save(&state)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A [CredentialBuilder](crate::CredentialBuilder) backed by a [FileKeyring].
+#[derive(Debug)]
+pub struct FileCredentialBuilder {
+    keyring: FileKeyring,
+}
+
+impl CredentialBuilderApi for FileCredentialBuilder {
+    /// Build a [FileCredential] for the given service and user.
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        let credential = FileCredential::new_with_target(&self.keyring, target, service, user)?;
+        Ok(Box::new(credential))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// Credentials live on disk until they are explicitly deleted.
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+}
+
+/// Return the keyring key or fail because the keyring is locked.
+fn require_unlocked(state: &KeyringState) -> Result<[u8; KEY_LEN]> {
+    state
+        .key
+        .ok_or_else(|| Error::NoStorageAccess(Box::new(LockedError)))
+}
+
+/// Derive the AEAD key from the master password and salt.
+fn derive_key(master_password: &str, log_n: u8, salt: &[u8]) -> Result<[u8; KEY_LEN]> {
+    let params = Params::new(log_n, SCRYPT_R, SCRYPT_P, KEY_LEN)
+        .map_err(|err| Error::Invalid("log_n".to_string(), err.to_string()))?;
+    let mut key = [0u8; KEY_LEN];
+    scrypt(master_password.as_bytes(), salt, &params, &mut key)
+        .map_err(|err| Error::Invalid("scrypt".to_string(), err.to_string()))?;
+    Ok(key)
+}
+
+/// Seal a plaintext under `key` with a fresh random nonce.
+fn seal(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Sealed> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|err| Error::PlatformFailure(Box::new(CryptoError(err.to_string()))))?;
+    Ok(Sealed {
+        nonce: nonce.to_vec(),
+        ciphertext,
+    })
+}
+
+/// Open a sealed secret under `key`.
+fn open_sealed(key: &[u8; KEY_LEN], sealed: &Sealed) -> Result<Vec<u8>> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XNonce::from_slice(&sealed.nonce);
+    cipher
+        .decrypt(nonce, sealed.ciphertext.as_slice())
+        .map_err(|err| Error::PlatformFailure(Box::new(CryptoError(err.to_string()))))
+}
+
+/// Serialize the keyring state to its JSON document and write it atomically.
+fn save(state: &KeyringState) -> Result<()> {
+    let mut items = Map::new();
+    for (key, item) in &state.items {
+        items.insert(
+            key.clone(),
+            json!({
+                "attributes": item.attributes,
+                "secret": sealed_to_json(&item.secret),
+            }),
+        );
+    }
+    let document = json!({
+        "version": VERSION,
+        "log_n": state.log_n,
+        "salt": state.salt.to_vec(),
+        "verifier": sealed_to_json(&state.verifier),
+        "items": Value::Object(items),
+    });
+    let bytes = serde_json::to_vec_pretty(&document)
+        .map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+    std::fs::write(&state.path, bytes).map_err(|err| Error::PlatformFailure(Box::new(err)))
+}
+
+/// Read and parse a keyring file into its in-memory (locked) state.
+fn load(path: &Path) -> Result<KeyringState> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Err(Error::NoEntry),
+        Err(err) => return Err(Error::PlatformFailure(Box::new(err))),
+    };
+    let document: Value = serde_json::from_slice(&bytes).map_err(|_| Error::BadEncoding(bytes))?;
+    let version = document.get("version").and_then(Value::as_u64);
+    if version != Some(VERSION) {
+        return Err(Error::BadEncoding(serde_json::to_vec(&document).unwrap_or_default()));
+    }
+    let log_n = document
+        .get("log_n")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| bad_field("log_n"))? as u8;
+    let salt = bytes_from_json(document.get("salt"))?;
+    let salt: [u8; SALT_LEN] = salt
+        .try_into()
+        .map_err(|_| Error::Invalid("salt".to_string(), "wrong salt length".to_string()))?;
+    let verifier = sealed_from_json(document.get("verifier"))?;
+
+    let mut items = HashMap::new();
+    if let Some(Value::Object(map)) = document.get("items") {
+        for (key, value) in map {
+            let attributes = attributes_from_json(value.get("attributes"))?;
+            let secret = sealed_from_json(value.get("secret"))?;
+            items.insert(key.clone(), Item { attributes, secret });
+        }
+    }
+
+    Ok(KeyringState {
+        path: path.to_path_buf(),
+        log_n,
+        salt,
+        verifier,
+        key: None,
+        items,
+    })
+}
+
+/// Render a sealed secret as a JSON object of byte arrays.
+fn sealed_to_json(sealed: &Sealed) -> Value {
+    json!({ "nonce": sealed.nonce, "ciphertext": sealed.ciphertext })
+}
+
+/// Parse a sealed secret from its JSON object.
+fn sealed_from_json(value: Option<&Value>) -> Result<Sealed> {
+    let value = value.ok_or_else(|| bad_field("secret"))?;
+    Ok(Sealed {
+        nonce: bytes_from_json(value.get("nonce"))?,
+        ciphertext: bytes_from_json(value.get("ciphertext"))?,
+    })
+}
+
+/// Parse the cleartext attribute map from its JSON object.
+fn attributes_from_json(value: Option<&Value>) -> Result<HashMap<String, String>> {
+    let map = value
+        .and_then(Value::as_object)
+        .ok_or_else(|| bad_field("attributes"))?;
+    let mut attributes = HashMap::new();
+    for (key, value) in map {
+        let value = value.as_str().ok_or_else(|| bad_field("attribute value"))?;
+        attributes.insert(key.clone(), value.to_string());
+    }
+    Ok(attributes)
+}
+
+/// Parse a JSON array of byte-valued numbers into a `Vec<u8>`.
+fn bytes_from_json(value: Option<&Value>) -> Result<Vec<u8>> {
+    let array = value
+        .and_then(Value::as_array)
+        .ok_or_else(|| bad_field("byte array"))?;
+    array
+        .iter()
+        .map(|v| {
+            v.as_u64()
+                .filter(|n| *n <= u8::MAX as u64)
+                .map(|n| n as u8)
+                .ok_or_else(|| bad_field("byte value"))
+        })
+        .collect()
+}
+
+/// The standard error for a malformed keyring field.
+fn bad_field(name: &str) -> Error {
+    Error::Invalid(name.to_string(), "malformed keyring field".to_string())
+}
+
+/// A wrapper error reported when the keyring is locked.
+#[derive(Debug)]
+struct LockedError;
+
+impl std::fmt::Display for LockedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the keyring is locked")
+    }
+}
+
+impl std::error::Error for LockedError {}
+
+/// A wrapper error carrying an AEAD failure message.
+#[derive(Debug)]
+struct CryptoError(String);
+
+impl std::fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "keyring cryptographic operation failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::generate_random_string, Entry, Error};
+
+    use super::FileKeyring;
+
+    /// Create a fresh, unlocked keyring in a temporary file.
+    fn new_keyring() -> (FileKeyring, std::path::PathBuf) {
+        let mut path = std::env::temp_dir();
+        path.push(format!("keyring-rs-test-{}.json", generate_random_string()));
+        let keyring = FileKeyring::create(&path, "master password").expect("Can't create keyring");
+        (keyring, path)
+    }
+
+    fn entry_new(keyring: &FileKeyring, service: &str, user: &str) -> Entry {
+        let credential = super::FileCredential::new_with_target(keyring, None, service, user)
+            .expect("Can't create file credential");
+        Entry::new_with_credential(Box::new(credential))
+    }
+
+    #[test]
+    fn test_missing_entry() {
+        let (keyring, path) = new_keyring();
+        let name = generate_random_string();
+        let entry = entry_new(&keyring, &name, &name);
+        assert!(matches!(entry.get_password(), Err(Error::NoEntry)));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let (keyring, path) = new_keyring();
+        let name = generate_random_string();
+        let entry = entry_new(&keyring, &name, &name);
+        let password = "test password";
+        entry.set_password(password).expect("Can't set password");
+        assert_eq!(
+            entry.get_password().expect("Can't get password"),
+            password
+        );
+        entry.delete_credential().expect("Can't delete password");
+        assert!(matches!(entry.get_password(), Err(Error::NoEntry)));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_lock_unlock() {
+        let (keyring, path) = new_keyring();
+        let name = generate_random_string();
+        let entry = entry_new(&keyring, &name, &name);
+        entry.set_password("secret").expect("Can't set password");
+        keyring.lock();
+        assert!(keyring.is_locked());
+        assert!(matches!(
+            entry.get_password(),
+            Err(Error::NoStorageAccess(_))
+        ));
+        keyring
+            .unlock("master password")
+            .expect("Can't unlock keyring");
+        assert!(!keyring.is_locked());
+        assert_eq!(entry.get_password().expect("Can't get password"), "secret");
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_wrong_master_password() {
+        let (keyring, path) = new_keyring();
+        keyring.lock();
+        assert!(matches!(
+            keyring.unlock("wrong password"),
+            Err(Error::Invalid(_, _))
+        ));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_persists_across_open() {
+        let (keyring, path) = new_keyring();
+        let name = generate_random_string();
+        entry_new(&keyring, &name, &name)
+            .set_password("persisted")
+            .expect("Can't set password");
+        let reopened = FileKeyring::open(&path).expect("Can't reopen keyring");
+        assert!(reopened.is_locked());
+        reopened.unlock("master password").expect("Can't unlock");
+        assert_eq!(
+            entry_new(&reopened, &name, &name)
+                .get_password()
+                .expect("Can't get password"),
+            "persisted"
+        );
+        let _ = std::fs::remove_file(path);
+    }
+}
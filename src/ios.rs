@@ -19,11 +19,20 @@ wildcards when looking up credentials by attribute value.)
 Credentials on iOS can have a large number of _key/value_ attributes,
 but this module controls the _account_ and _name_ attributes and
 ignores all the others. so clients can't use it to access or update any attributes.
+
+This store is built on the cross-platform
+[`security_framework::passwords`](security_framework::passwords) module, whose
+`SecItemAdd`/`SecItemCopyMatching`/`SecItemUpdate`/`SecItemDelete` calls work
+identically on iOS and macOS.  It is therefore also compiled on macOS, where it
+offers a data-protection keychain that (unlike the [macos](crate::macos) store)
+needs no named `SecKeychain`; it is never the macOS default, but can be selected
+explicitly through [Entry::new_with_credential](crate::Entry::new_with_credential).
  */
 
 use security_framework::base::Error;
 use security_framework::passwords::{
-    delete_generic_password, get_generic_password, set_generic_password,
+    delete_generic_password, delete_internet_password, get_generic_password, get_internet_password,
+    set_generic_password, set_internet_password, SecAuthenticationType, SecProtocolType,
 };
 
 use super::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
@@ -87,6 +96,27 @@ impl CredentialApi for IosCredential {
         Ok(())
     }
 
+    /// Read the standard keychain attributes of this entry's item.
+    ///
+    /// Returns the human-readable attributes keyring clients most often want:
+    /// `label` (the item's display name), `comment`, `description` (its kind),
+    /// `creator` (the four-character creator code), and the read-only
+    /// `creation_date`/`modification_date`.  Only attributes the item actually
+    /// carries are present in the map.
+    fn get_attributes(&self) -> Result<std::collections::HashMap<String, String>> {
+        get_generic_attributes(&self.service, &self.account)
+    }
+
+    /// Write the writable keychain attributes of this entry's item.
+    ///
+    /// Recognises `label`, `comment`, and `description`/`kind`; the creator and
+    /// the creation/modification dates are maintained by the keychain and are
+    /// ignored here.  Unrecognised keys are ignored.  Returns a
+    /// [NoEntry](ErrorCode::NoEntry) error if the item doesn't exist.
+    fn update_attributes(&self, attributes: &std::collections::HashMap<&str, &str>) -> Result<()> {
+        update_generic_attributes(&self.service, &self.account, attributes)
+    }
+
     /// Return the underlying concrete object with an `Any` type so that it can
     /// be downgraded to an [IosCredential] for platform-specific processing.
     fn as_any(&self) -> &dyn std::any::Any {
@@ -150,6 +180,234 @@ impl IosCredential {
     }
 }
 
+/// The representation of a Keychain internet-password credential.
+///
+/// Browsers and other networked apps store passwords as _internet_ items,
+/// keyed by a _server_, _protocol_, _authentication type_, _port_, and _path_
+/// in addition to the _account_.  This type lets clients read and write those
+/// items rather than the generic passwords modelled by [IosCredential].
+/// As with generic credentials, the underlying item can carry many more
+/// attributes than are represented here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IosInternetCredential {
+    pub server: String,
+    pub account: String,
+    pub protocol: IosProtocolType,
+    pub authentication_type: IosAuthenticationType,
+    pub port: u16,
+    pub path: String,
+}
+
+impl CredentialApi for IosInternetCredential {
+    /// Create and write an internet credential with password for this entry.
+    ///
+    /// The new credential replaces any existing one with the same keys.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())?;
+        Ok(())
+    }
+
+    /// Create and write an internet credential with secret for this entry.
+    ///
+    /// The new credential replaces any existing one with the same keys.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        set_internet_password(
+            &self.server,
+            None,
+            &self.account,
+            &self.path,
+            Some(self.port),
+            (&self.protocol).into(),
+            (&self.authentication_type).into(),
+            secret,
+        )
+        .map_err(decode_error)?;
+        Ok(())
+    }
+
+    /// Look up the password for this entry, if any.
+    ///
+    /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no
+    /// credential in the store.
+    fn get_password(&self) -> Result<String> {
+        decode_password(self.get_secret()?)
+    }
+
+    /// Look up the secret for this entry, if any.
+    ///
+    /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no
+    /// credential in the store.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        get_internet_password(
+            &self.server,
+            None,
+            &self.account,
+            &self.path,
+            Some(self.port),
+            (&self.protocol).into(),
+            (&self.authentication_type).into(),
+        )
+        .map_err(decode_error)
+    }
+
+    /// Delete the underlying internet credential for this entry, if any.
+    ///
+    /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no
+    /// credential in the store.
+    fn delete_credential(&self) -> Result<()> {
+        delete_internet_password(
+            &self.server,
+            None,
+            &self.account,
+            &self.path,
+            Some(self.port),
+            (&self.protocol).into(),
+            (&self.authentication_type).into(),
+        )
+        .map_err(decode_error)?;
+        Ok(())
+    }
+
+    /// Return the underlying concrete object with an `Any` type so that it can
+    /// be downgraded to an [IosInternetCredential] for platform-specific processing.
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// Expose the concrete debug formatter for use via the [Credential] trait
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl IosInternetCredential {
+    /// Create an internet-password credential from a URL-like target.
+    ///
+    /// The grammar is `protocol://[user@]host[:port][/path]`, so a target like
+    /// `https://alice@example.com:443/login` stores a browser-style credential.
+    /// A `user` embedded in the authority wins over the entry's own account;
+    /// otherwise the entry account is used.  The authentication type is always
+    /// the keychain's default.  Fails if the resulting server or account is
+    /// empty, because empty attribute values act as wildcards.
+    pub fn new_with_target(target: &str, _service: &str, user: &str) -> Result<Self> {
+        let (scheme, rest) = target.split_once("://").ok_or_else(|| {
+            ErrorCode::Invalid("target".to_string(), "missing '://' scheme".to_string())
+        })?;
+        let protocol: IosProtocolType = scheme.parse()?;
+        let (authority, path) = match rest.split_once('/') {
+            Some((authority, path)) => (authority, format!("/{path}")),
+            None => (rest, String::new()),
+        };
+        let (account, host_port) = match authority.split_once('@') {
+            Some((account, host_port)) => (account, host_port),
+            None => (user, authority),
+        };
+        let (server, port) = match host_port.split_once(':') {
+            Some((host, port)) => {
+                let port = port.parse::<u16>().map_err(|_| {
+                    ErrorCode::Invalid("port".to_string(), format!("'{port}' is not a valid port"))
+                })?;
+                (host, port)
+            }
+            None => (host_port, 0),
+        };
+        if server.is_empty() {
+            return Err(ErrorCode::Invalid(
+                "server".to_string(),
+                "cannot be empty".to_string(),
+            ));
+        }
+        if account.is_empty() {
+            return Err(ErrorCode::Invalid(
+                "user".to_string(),
+                "cannot be empty".to_string(),
+            ));
+        }
+        Ok(Self {
+            server: server.to_string(),
+            account: account.to_string(),
+            protocol,
+            authentication_type: IosAuthenticationType::Default,
+            port,
+            path,
+        })
+    }
+}
+
+/// The network protocols that can key an internet password.
+///
+/// This mirrors the subset of `SecProtocolType` values that networked apps
+/// commonly store credentials under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IosProtocolType {
+    Ftp,
+    Http,
+    Https,
+    Smtp,
+    Imap,
+    Pop3,
+    Ssh,
+}
+
+impl std::str::FromStr for IosProtocolType {
+    type Err = ErrorCode;
+
+    /// Convert a protocol name (any case) to a protocol type.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "ftp" => Ok(IosProtocolType::Ftp),
+            "http" => Ok(IosProtocolType::Http),
+            "https" => Ok(IosProtocolType::Https),
+            "smtp" => Ok(IosProtocolType::Smtp),
+            "imap" => Ok(IosProtocolType::Imap),
+            "pop3" => Ok(IosProtocolType::Pop3),
+            "ssh" => Ok(IosProtocolType::Ssh),
+            _ => Err(ErrorCode::Invalid(
+                "protocol".to_string(),
+                format!("'{s}' is not a known internet protocol"),
+            )),
+        }
+    }
+}
+
+impl From<&IosProtocolType> for SecProtocolType {
+    fn from(protocol: &IosProtocolType) -> Self {
+        match protocol {
+            IosProtocolType::Ftp => SecProtocolType::FTP,
+            IosProtocolType::Http => SecProtocolType::HTTP,
+            IosProtocolType::Https => SecProtocolType::HTTPS,
+            IosProtocolType::Smtp => SecProtocolType::SMTP,
+            IosProtocolType::Imap => SecProtocolType::IMAP,
+            IosProtocolType::Pop3 => SecProtocolType::POP3,
+            IosProtocolType::Ssh => SecProtocolType::SSH,
+        }
+    }
+}
+
+/// The authentication schemes that can key an internet password.
+///
+/// `Default` selects the keychain's default scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IosAuthenticationType {
+    Default,
+    HttpBasic,
+    HttpDigest,
+    HtmlForm,
+    Ntlm,
+}
+
+impl From<&IosAuthenticationType> for SecAuthenticationType {
+    fn from(authentication_type: &IosAuthenticationType) -> Self {
+        match authentication_type {
+            IosAuthenticationType::Default => SecAuthenticationType::Default,
+            IosAuthenticationType::HttpBasic => SecAuthenticationType::HTTPBasic,
+            IosAuthenticationType::HttpDigest => SecAuthenticationType::HTTPDigest,
+            IosAuthenticationType::HtmlForm => SecAuthenticationType::HTMLForm,
+            IosAuthenticationType::Ntlm => SecAuthenticationType::NTLM,
+        }
+    }
+}
+
 /// The builder for iOS keychain credentials
 pub struct IosCredentialBuilder {}
 
@@ -162,8 +420,19 @@ pub fn default_credential_builder() -> Box<CredentialBuilder> {
 }
 
 impl CredentialBuilderApi for IosCredentialBuilder {
-    /// Build an [IosCredential] for the given target, service, and user.
+    /// Build a credential for the given target, service, and user.
+    ///
+    /// A URL-like target (one containing a `://` scheme) produces an
+    /// [IosInternetCredential]; any other target falls back to a generic
+    /// [IosCredential].
     fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        if let Some(target) = target {
+            if target.contains("://") {
+                return Ok(Box::new(IosInternetCredential::new_with_target(
+                    target, service, user,
+                )?));
+            }
+        }
         Ok(Box::new(IosCredential::new_with_target(
             target, service, user,
         )?))
@@ -176,6 +445,160 @@ impl CredentialBuilderApi for IosCredentialBuilder {
     }
 }
 
+/// The item attributes this module can read, paired with their raw keys.
+fn attribute_keys() -> [(&'static str, core_foundation::string::CFStringRef); 6] {
+    use security_framework_sys::item::{
+        kSecAttrComment, kSecAttrCreationDate, kSecAttrCreator, kSecAttrDescription, kSecAttrLabel,
+        kSecAttrModificationDate,
+    };
+    [
+        ("label", kSecAttrLabel),
+        ("comment", kSecAttrComment),
+        ("description", kSecAttrDescription),
+        ("creator", kSecAttrCreator),
+        ("creation_date", kSecAttrCreationDate),
+        ("modification_date", kSecAttrModificationDate),
+    ]
+}
+
+/// Stringify a Core Foundation attribute value (string, number, or date).
+fn attribute_value_to_string(value: core_foundation::base::CFTypeRef) -> String {
+    use core_foundation::base::{CFGetTypeID, TCFType};
+    use core_foundation::date::{CFDate, CFDateRef};
+    use core_foundation::number::{CFNumber, CFNumberRef};
+    use core_foundation::propertylist::CFPropertyListSubClass;
+    use core_foundation::string::{CFString, CFStringRef};
+
+    let type_id = unsafe { CFGetTypeID(value) };
+    if type_id == CFString::type_id() {
+        unsafe { CFString::wrap_under_get_rule(value as CFStringRef) }.to_string()
+    } else if type_id == CFNumber::type_id() {
+        let number = unsafe { CFNumber::wrap_under_get_rule(value as CFNumberRef) };
+        number.to_i64().map(|n| n.to_string()).unwrap_or_default()
+    } else if type_id == CFDate::type_id() {
+        let date = unsafe { CFDate::wrap_under_get_rule(value as CFDateRef) };
+        format!("{:?}", date.to_CFPropertyList())
+            .trim_matches('"')
+            .to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Read the standard attributes of a generic password item via `SecItemCopyMatching`.
+fn get_generic_attributes(
+    service: &str,
+    account: &str,
+) -> Result<std::collections::HashMap<String, String>> {
+    use core_foundation::base::{CFType, CFTypeRef, TCFType};
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::dictionary::{CFDictionary, CFDictionaryRef};
+    use core_foundation::string::CFString;
+    use security_framework_sys::base::{errSecItemNotFound, errSecSuccess};
+    use security_framework_sys::item::{
+        kSecAttrAccount, kSecAttrService, kSecClass, kSecClassGenericPassword, kSecReturnAttributes,
+    };
+    use security_framework_sys::keychain_item::SecItemCopyMatching;
+
+    let class = unsafe { CFString::wrap_under_get_rule(kSecClass) };
+    let class_value = unsafe { CFType::wrap_under_get_rule(kSecClassGenericPassword as *const _) };
+    let service_key = unsafe { CFString::wrap_under_get_rule(kSecAttrService) };
+    let account_key = unsafe { CFString::wrap_under_get_rule(kSecAttrAccount) };
+    let return_key = unsafe { CFString::wrap_under_get_rule(kSecReturnAttributes) };
+
+    let query = CFDictionary::from_CFType_pairs(&[
+        (class.as_CFType(), class_value),
+        (service_key.as_CFType(), CFString::new(service).as_CFType()),
+        (account_key.as_CFType(), CFString::new(account).as_CFType()),
+        (return_key.as_CFType(), CFBoolean::true_value().as_CFType()),
+    ]);
+
+    let mut ptr: CFTypeRef = std::ptr::null();
+    let status = unsafe { SecItemCopyMatching(query.as_concrete_TypeRef(), &mut ptr as *mut _) };
+    match status {
+        errSecSuccess => {}
+        errSecItemNotFound => return Err(ErrorCode::NoEntry),
+        other => return Err(decode_error(Error::from_code(other))),
+    }
+
+    let attributes: CFDictionary =
+        unsafe { CFDictionary::wrap_under_create_rule(ptr as CFDictionaryRef) };
+
+    // Map each item attribute key we recognise to its friendly name.
+    let wanted: Vec<(String, &str)> = attribute_keys()
+        .into_iter()
+        .map(|(name, raw_key)| {
+            let key = unsafe { CFString::wrap_under_get_rule(raw_key) }.to_string();
+            (key, name)
+        })
+        .collect();
+
+    let mut result = std::collections::HashMap::new();
+    let (keys, values) = attributes.get_keys_and_values();
+    for (key, value) in keys.into_iter().zip(values.into_iter()) {
+        let key_str = unsafe { CFString::wrap_under_get_rule(key as _) }.to_string();
+        if let Some((_, name)) = wanted.iter().find(|(raw, _)| *raw == key_str) {
+            let string = attribute_value_to_string(value as CFTypeRef);
+            if !string.is_empty() {
+                result.insert(name.to_string(), string);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Write the writable attributes of a generic password item via `SecItemUpdate`.
+fn update_generic_attributes(
+    service: &str,
+    account: &str,
+    attributes: &std::collections::HashMap<&str, &str>,
+) -> Result<()> {
+    use core_foundation::base::{CFType, TCFType};
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::string::CFString;
+    use security_framework_sys::base::{errSecItemNotFound, errSecSuccess};
+    use security_framework_sys::item::{
+        kSecAttrAccount, kSecAttrComment, kSecAttrDescription, kSecAttrLabel, kSecAttrService,
+        kSecClass, kSecClassGenericPassword,
+    };
+    use security_framework_sys::keychain_item::SecItemUpdate;
+
+    let class = unsafe { CFString::wrap_under_get_rule(kSecClass) };
+    let class_value = unsafe { CFType::wrap_under_get_rule(kSecClassGenericPassword as *const _) };
+    let service_key = unsafe { CFString::wrap_under_get_rule(kSecAttrService) };
+    let account_key = unsafe { CFString::wrap_under_get_rule(kSecAttrAccount) };
+    let query = CFDictionary::from_CFType_pairs(&[
+        (class.as_CFType(), class_value),
+        (service_key.as_CFType(), CFString::new(service).as_CFType()),
+        (account_key.as_CFType(), CFString::new(account).as_CFType()),
+    ]);
+
+    let mut pairs = Vec::new();
+    for (name, raw_key) in [
+        ("label", kSecAttrLabel),
+        ("comment", kSecAttrComment),
+        ("description", kSecAttrDescription),
+        ("kind", kSecAttrDescription),
+    ] {
+        if let Some(value) = attributes.get(name) {
+            let key = unsafe { CFString::wrap_under_get_rule(raw_key) };
+            pairs.push((key.as_CFType(), CFString::new(value).as_CFType()));
+        }
+    }
+    if pairs.is_empty() {
+        return Ok(());
+    }
+    let changes = CFDictionary::from_CFType_pairs(&pairs);
+
+    let status =
+        unsafe { SecItemUpdate(query.as_concrete_TypeRef(), changes.as_concrete_TypeRef()) };
+    match status {
+        errSecSuccess => Ok(()),
+        errSecItemNotFound => Err(ErrorCode::NoEntry),
+        other => Err(decode_error(Error::from_code(other))),
+    }
+}
+
 /// Map an iOS API error to a crate error with appropriate annotation
 ///
 /// The iOS error code values used here are from
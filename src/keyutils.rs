@@ -110,6 +110,10 @@ use super::credential::{
 use super::error::{decode_password, Error as ErrorCode, Result};
 use linux_keyutils::{KeyError, KeyRing, KeyRingIdentifier};
 
+/// The description prefix the crate stamps on entries created without an
+/// explicit target (`keyring-rs:user@service`).
+pub const DESCRIPTION_PREFIX: &str = "keyring-rs:";
+
 /// Representation of a keyutils credential.
 ///
 /// Since the CredentialBuilderApi::build method does not provide
@@ -129,8 +133,28 @@ pub struct KeyutilsCredential {
     pub persistent: Option<KeyRing>,
     /// Description of the key entry
     pub description: String,
+    /// Anchoring keyring this credential's key lives in.
+    ///
+    /// Kept so the raw `big_key` path (see [set_secret](CredentialApi::set_secret))
+    /// can name the destination keyring by its special serial.
+    pub anchor: KeyRingIdentifier,
+    /// Payload size (in bytes) at or above which the key is created with the
+    /// kernel `big_key` type rather than the default `user` type.
+    ///
+    /// The `user` type caps a payload at roughly a single page of quota-limited
+    /// kernel memory (~32 KiB), which is too small for blobs like a Kerberos
+    /// ccache or a certificate bundle.  When a secret reaches this threshold the
+    /// key is created as a `big_key`, which the kernel stores in encrypted
+    /// tmpfs; smaller secrets keep using the `user` type so they stay wholly in
+    /// kernel memory.  [None] disables `big_key` entirely.
+    pub big_key_threshold: Option<usize>,
 }
 
+/// The payload size (in bytes) at or above which a secret is stored in a
+/// `big_key` by default.  The `user` key type is limited to a single page of
+/// kernel memory, so we switch over comfortably below that limit.
+pub const DEFAULT_BIG_KEY_THRESHOLD: usize = 16 * 1024;
+
 impl CredentialApi for KeyutilsCredential {
     /// Set a password in the underlying store
     ///
@@ -151,7 +175,25 @@ impl CredentialApi for KeyutilsCredential {
             ));
         }
 
-        // Add to the session keyring
+        // Large payloads overflow the `user` key type's single-page quota, so
+        // route them through the kernel's `big_key` type when they reach the
+        // configured threshold.
+        let use_big_key = self
+            .big_key_threshold
+            .is_some_and(|threshold| secret.len() >= threshold);
+
+        if use_big_key {
+            // linux_keyutils only creates `user` keys, so add the `big_key`
+            // directly via add_key(2), then recover a Key handle for linking.
+            add_key("big_key", &self.description, secret, self.anchor)?;
+            if let Some(keyring) = self.persistent {
+                let key = self.session.search(&self.description).map_err(decode_error)?;
+                keyring.link_key(key).map_err(decode_error)?;
+            }
+            return Ok(());
+        }
+
+        // Add to the anchoring keyring
         let key = self
             .session
             .add_key(&self.description, secret)
@@ -249,21 +291,101 @@ impl KeyutilsCredential {
         Ok(self.clone())
     }
 
+    /// Set an expiry timeout on the underlying key.
+    ///
+    /// Once `ttl` elapses the kernel expires the key, after which a
+    /// [get_password](KeyutilsCredential::get_password) call reports a
+    /// [NoEntry](ErrorCode::NoEntry) error.  A timeout of zero clears any
+    /// existing expiry.  This is used to bound how long a cached copy of a
+    /// secret lives in the session keyring; see
+    /// [SsKeyutilsCredential](crate::secret_service_with_keyutils::SsKeyutilsCredential).
+    pub fn set_timeout(&self, ttl: std::time::Duration) -> Result<()> {
+        let key = self
+            .session
+            .search(&self.description)
+            .map_err(decode_error)?;
+        key.set_timeout(ttl.as_secs() as usize)
+            .map_err(decode_error)?;
+        Ok(())
+    }
+
+    /// Parse this credential's description back into its `{user, service}` pair.
+    ///
+    /// Only descriptions produced by the crate's default convention
+    /// (`keyring-rs:user@service`) can be parsed; an explicit custom target
+    /// returns [None].
+    pub fn parse_description(&self) -> Option<(String, String)> {
+        let rest = self.description.strip_prefix(DESCRIPTION_PREFIX)?;
+        let (user, service) = rest.split_once('@')?;
+        Some((user.to_string(), service.to_string()))
+    }
+
+    /// Read the time remaining before the kernel expires the underlying key.
+    ///
+    /// Returns [None] when the key has no timeout set (the kernel reports it as
+    /// `perm`), and a [Duration](std::time::Duration) otherwise.  The kernel does
+    /// not expose the expiry through keyctl, so the remaining validity is read
+    /// from the `EXPIRY` column of `/proc/keys` for the live key.  A key that has
+    /// already expired surfaces as a [NoEntry](ErrorCode::NoEntry) error from the
+    /// preceding search, matching the read path.
+    pub fn get_timeout(&self) -> Result<Option<std::time::Duration>> {
+        let key = self
+            .session
+            .search(&self.description)
+            .map_err(decode_error)?;
+        read_key_timeout(key.get_id().as_i32())
+    }
+
+    /// Apply a permission mask to the underlying key.
+    ///
+    /// keyutils access control is entirely possession- and permission-driven:
+    /// every key carries a 32-bit mask with one byte each for the possessor,
+    /// user, group, and other classes (see [KeyPermissions]).  This sets that
+    /// mask via `keyctl(KEYCTL_SETPERM)`, letting a caller, for example, make a
+    /// credential searchable and readable by the possessor only while denying the
+    /// `other` class entirely, or grant group-read so a cooperating helper can
+    /// retrieve the same secret.
+    pub fn set_permissions(&self, permissions: KeyPermissions) -> Result<()> {
+        let key = self
+            .session
+            .search(&self.description)
+            .map_err(decode_error)?;
+        key.set_perms(permissions.to_mask()).map_err(decode_error)?;
+        Ok(())
+    }
+
     /// Create the platform credential for a Keyutils entry.
     ///
     /// An explicit target string is interpreted as the KeyRing to use for the entry.
     /// If none is provided, then we concatenate the user and service in the string
     /// `keyring-rs:user@service`.
+    ///
+    /// The target may start with one of the anchoring-keyring prefixes understood by
+    /// [parse_keyring] (for example `user:` or `user-session:`); the prefix selects
+    /// which kernel keyring the key is anchored to and the remainder is used as the
+    /// description.  Without a prefix the key is anchored to the session keyring (and
+    /// linked into the persistent keyring), which is the historical behaviour.
     pub fn new_with_target(target: Option<&str>, service: &str, user: &str) -> Result<Self> {
-        // Obtain the session keyring
-        let session =
-            KeyRing::from_special_id(KeyRingIdentifier::Session, false).map_err(decode_error)?;
+        // Split off any anchoring-keyring prefix, defaulting to the session keyring.
+        let (anchor, rest) = match target {
+            Some("") => {
+                return Err(ErrorCode::Invalid(
+                    "target".to_string(),
+                    "cannot be empty".to_string(),
+                ));
+            }
+            Some(value) => parse_keyring(value),
+            None => (KeyRingIdentifier::Session, None),
+        };
+
+        // Obtain the anchoring keyring
+        let session = KeyRing::from_special_id(anchor, false).map_err(decode_error)?;
 
         // Link the persistent keyring to the session
-        let persistent = KeyRing::get_persistent(KeyRingIdentifier::Session).ok();
+        let persistent = KeyRing::get_persistent(anchor).ok();
 
         // Construct the credential with a URI-style description
-        let description = match target {
+        let description = match rest {
             Some("") => {
                 return Err(ErrorCode::Invalid(
                     "target".to_string(),
@@ -271,16 +393,251 @@ impl KeyutilsCredential {
                 ));
             }
             Some(value) => value.to_string(),
-            None => format!("keyring-rs:{user}@{service}"),
+            None => format!("{DESCRIPTION_PREFIX}{user}@{service}"),
         };
         Ok(Self {
             session,
             persistent,
             description,
+            anchor,
+            big_key_threshold: Some(DEFAULT_BIG_KEY_THRESHOLD),
         })
     }
 }
 
+/// Add a key of an explicit type to a keyring via the raw `add_key(2)` syscall.
+///
+/// linux_keyutils only ever creates keys of the default `user` type, so the
+/// `big_key` path has to go through the syscall directly.  `anchor` names the
+/// destination keyring by its special serial; on success the key is linked into
+/// that keyring and any platform error is mapped through [decode_error].
+fn add_key(key_type: &str, description: &str, payload: &[u8], anchor: KeyRingIdentifier) -> Result<()> {
+    let key_type = std::ffi::CString::new(key_type)
+        .map_err(|_| ErrorCode::Invalid("type".to_string(), "contains a nul byte".to_string()))?;
+    let description = std::ffi::CString::new(description).map_err(|_| {
+        ErrorCode::Invalid("description".to_string(), "contains a nul byte".to_string())
+    })?;
+    // SAFETY: the pointers reference live, correctly-sized buffers for the
+    // duration of the call, and add_key(2) only reads through them.
+    let serial = unsafe {
+        libc::syscall(
+            libc::SYS_add_key,
+            key_type.as_ptr(),
+            description.as_ptr(),
+            payload.as_ptr() as *const libc::c_void,
+            payload.len(),
+            anchor as i32,
+        )
+    };
+    if serial < 0 {
+        return Err(decode_error(KeyError::from_errno()));
+    }
+    Ok(())
+}
+
+/// Enumerate the entries this crate has stored in the caller's keyrings.
+///
+/// All entries created without an explicit target share the
+/// [`keyring-rs:`](DESCRIPTION_PREFIX) description prefix and are linked into
+/// known keyrings, so we walk the live keys in `/proc/keys`, keep the `user` and
+/// `big_key` keys whose description carries that prefix, and return a
+/// [KeyutilsCredential] for each (anchored to the session keyring, matching how
+/// they were created).  Use [parse_description](KeyutilsCredential::parse_description)
+/// to recover the `{user, service}` pair.
+///
+/// This powers management use cases — showing which services have cached
+/// credentials, bulk-invalidating them on logout, or migrating them between
+/// keyrings — that single-entry lookup cannot.
+pub fn list_credentials() -> Result<Vec<KeyutilsCredential>> {
+    let contents = std::fs::read_to_string("/proc/keys")
+        .map_err(|err| ErrorCode::PlatformFailure(Box::new(err)))?;
+    let mut seen = std::collections::HashSet::new();
+    let mut credentials = Vec::new();
+    for line in contents.lines() {
+        let Some((key_type, description)) = parse_keys_line(line) else {
+            continue;
+        };
+        if key_type != "user" && key_type != "big_key" {
+            continue;
+        }
+        if !description.starts_with(DESCRIPTION_PREFIX) {
+            continue;
+        }
+        if !seen.insert(description.to_string()) {
+            continue;
+        }
+        credentials.push(KeyutilsCredential::new_with_target(
+            Some(description),
+            "",
+            "",
+        )?);
+    }
+    Ok(credentials)
+}
+
+/// Split a `/proc/keys` line into its key type and description.
+///
+/// The columns are `ID FLAGS USAGE EXPIRY PERM UID GID TYPE DESCRIPTION: SUMMARY`;
+/// we skip the seven leading fields, read the type, and take everything up to the
+/// `": "` that precedes the payload summary as the description.
+fn parse_keys_line(line: &str) -> Option<(&str, &str)> {
+    let mut rest = line.trim_start();
+    for _ in 0..7 {
+        let space = rest.find(char::is_whitespace)?;
+        rest = rest[space..].trim_start();
+    }
+    let space = rest.find(char::is_whitespace)?;
+    let key_type = &rest[..space];
+    let tail = rest[space..].trim_start();
+    let description = tail.split(": ").next().unwrap_or(tail);
+    Some((key_type, description))
+}
+
+/// Parse the remaining-validity of a key out of `/proc/keys`.
+///
+/// Each line starts with the key's serial in hex, followed by flags, usage, and
+/// the `EXPIRY` column: `perm` for a key with no timeout, `expd` for one that has
+/// already expired, or a coarse relative time such as `2w`, `5d`, `3h`, `23m`, or
+/// `59s`.  We locate the line for `serial` and translate that column into a
+/// [Duration](std::time::Duration), or [None] for `perm`.
+fn read_key_timeout(serial: i32) -> Result<Option<std::time::Duration>> {
+    let contents = std::fs::read_to_string("/proc/keys")
+        .map_err(|err| ErrorCode::PlatformFailure(Box::new(err)))?;
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let id = fields.next().and_then(|id| i64::from_str_radix(id, 16).ok());
+        if id != Some(serial as i64 & 0xffff_ffff) {
+            continue;
+        }
+        // Skip FLAGS and USAGE to reach the EXPIRY column.
+        let expiry = fields.nth(2).unwrap_or("perm");
+        return Ok(parse_expiry(expiry));
+    }
+    // The search found the key, so its absence here is a transient race rather
+    // than a missing entry; report no timeout.
+    Ok(None)
+}
+
+/// Translate a `/proc/keys` `EXPIRY` token into a remaining [Duration](std::time::Duration).
+fn parse_expiry(token: &str) -> Option<std::time::Duration> {
+    if token == "perm" || token == "expd" {
+        return None;
+    }
+    let (value, unit) = token.split_at(token.len().saturating_sub(1));
+    let value: u64 = value.parse().ok()?;
+    let seconds = match unit {
+        "s" => value,
+        "m" => value * 60,
+        "h" => value * 60 * 60,
+        "d" => value * 60 * 60 * 24,
+        "w" => value * 60 * 60 * 24 * 7,
+        _ => return None,
+    };
+    Some(std::time::Duration::from_secs(seconds))
+}
+
+/// Split a target into its anchoring keyring and the remaining description.
+///
+/// A target may be prefixed with the name of the kernel keyring it should be
+/// anchored to, followed by a colon: `thread:`, `process:`, `session:`, `user:`,
+/// or `user-session:`.  The prefix is consumed and the rest of the string (which
+/// may itself be empty, signalling "use the default description") is returned
+/// alongside the selected [KeyRingIdentifier].  A target without a recognised
+/// prefix keeps its historical meaning: the session keyring with the whole target
+/// used verbatim as the description.
+fn parse_keyring(target: &str) -> (KeyRingIdentifier, Option<&str>) {
+    for (prefix, id) in [
+        ("thread:", KeyRingIdentifier::Thread),
+        ("process:", KeyRingIdentifier::Process),
+        ("session:", KeyRingIdentifier::Session),
+        ("user-session:", KeyRingIdentifier::UserSession),
+        ("user:", KeyRingIdentifier::User),
+    ] {
+        if let Some(rest) = target.strip_prefix(prefix) {
+            return (id, Some(rest));
+        }
+    }
+    (KeyRingIdentifier::Session, Some(target))
+}
+
+/// The operations a class of accessor may perform on a key.
+///
+/// Each kernel key carries four of these, one per accessor class (see
+/// [KeyPermissions]).  The flags mirror the keyutils permission bits: `view`
+/// reads the key's metadata, `read` its payload, `write` updates the payload,
+/// `search` finds the key from a keyring, `link` adds it to another keyring, and
+/// `setattr` changes its ownership, permissions, or timeout.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct KeyPermission {
+    /// View the key's attributes (type, description, and permissions).
+    pub view: bool,
+    /// Read the key's payload.
+    pub read: bool,
+    /// Update the key's payload.
+    pub write: bool,
+    /// Find the key when searching a keyring.
+    pub search: bool,
+    /// Link the key into a keyring.
+    pub link: bool,
+    /// Change the key's ownership, permissions, or timeout.
+    pub setattr: bool,
+}
+
+impl KeyPermission {
+    /// Pack this class's flags into the low byte of a permission mask.
+    fn to_byte(self) -> u32 {
+        let mut byte = 0u32;
+        if self.view {
+            byte |= 0x01;
+        }
+        if self.read {
+            byte |= 0x02;
+        }
+        if self.write {
+            byte |= 0x04;
+        }
+        if self.search {
+            byte |= 0x08;
+        }
+        if self.link {
+            byte |= 0x10;
+        }
+        if self.setattr {
+            byte |= 0x20;
+        }
+        byte
+    }
+}
+
+/// A typed description of a key's full permission mask.
+///
+/// The kernel's `KEYCTL_SETPERM` mask is four permission bytes — one each for the
+/// possessor, user, group, and other classes.  Build one of these and hand it to
+/// [set_permissions](KeyutilsCredential::set_permissions) to control which
+/// accessors may view, read, write, search, link, or change the attributes of a
+/// stored key.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct KeyPermissions {
+    /// Permissions for a process that possesses the key.
+    pub possessor: KeyPermission,
+    /// Permissions for the key's owning user.
+    pub user: KeyPermission,
+    /// Permissions for the key's owning group.
+    pub group: KeyPermission,
+    /// Permissions for everyone else.
+    pub other: KeyPermission,
+}
+
+impl KeyPermissions {
+    /// Pack the four accessor classes into the 32-bit keyctl permission mask.
+    fn to_mask(self) -> u32 {
+        (self.possessor.to_byte() << 24)
+            | (self.user.to_byte() << 16)
+            | (self.group.to_byte() << 8)
+            | self.other.to_byte()
+    }
+}
+
 /// The builder for keyutils credentials
 #[derive(Debug, Copy, Clone)]
 struct KeyutilsCredentialBuilder {}
@@ -370,6 +727,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_keyring_prefix_in_target() {
+        let credential = KeyutilsCredential::new_with_target(Some("user:my description"), "", "")
+            .expect("Couldn't create entry anchored to the user keyring");
+        assert_eq!(credential.description, "my description");
+        let credential = KeyutilsCredential::new_with_target(Some("plain description"), "", "")
+            .expect("Couldn't create entry with an unprefixed target");
+        assert_eq!(credential.description, "plain description");
+    }
+
     #[test]
     fn test_empty_service_and_user() {
         crate::tests::test_empty_service_and_user(entry_new);
@@ -404,6 +771,111 @@ mod tests {
         crate::tests::test_round_trip_random_secret(entry_new);
     }
 
+    #[test]
+    fn test_list_credentials() {
+        let service = generate_random_string();
+        let user = generate_random_string();
+        let entry = entry_new(&service, &user);
+        entry
+            .set_password("enumerated password")
+            .expect("Couldn't set password for enumeration");
+        let listed = super::list_credentials().expect("Couldn't enumerate credentials");
+        let found = listed
+            .iter()
+            .find_map(KeyutilsCredential::parse_description)
+            .is_some();
+        assert!(found, "Enumeration returned no parseable keyring-rs entries");
+        assert!(
+            listed
+                .iter()
+                .filter_map(KeyutilsCredential::parse_description)
+                .any(|(u, s)| u == user && s == service),
+            "Enumeration didn't include the entry we just created"
+        );
+        entry
+            .delete_credential()
+            .expect("Couldn't delete after enumeration");
+    }
+
+    #[test]
+    fn test_set_permissions() {
+        use super::{KeyPermission, KeyPermissions};
+        let name = generate_random_string();
+        let entry = entry_new(&name, &name);
+        let credential: &KeyutilsCredential = entry
+            .get_credential()
+            .downcast_ref()
+            .expect("Not a Keyutils credential");
+        entry
+            .set_password("guarded password")
+            .expect("Couldn't set password for permissions");
+        // Keep full possessor access (so we can still read and clean up) but deny
+        // the other class entirely.
+        let permissions = KeyPermissions {
+            possessor: KeyPermission {
+                view: true,
+                read: true,
+                write: true,
+                search: true,
+                link: true,
+                setattr: true,
+            },
+            ..Default::default()
+        };
+        credential
+            .set_permissions(permissions)
+            .expect("Couldn't set permissions");
+        assert_eq!(
+            entry.get_password().expect("Couldn't read after setperm"),
+            "guarded password"
+        );
+        entry
+            .delete_credential()
+            .expect("Couldn't delete after permissions");
+    }
+
+    #[test]
+    fn test_set_and_get_timeout() {
+        let name = generate_random_string();
+        let entry = entry_new(&name, &name);
+        let credential: &KeyutilsCredential = entry
+            .get_credential()
+            .downcast_ref()
+            .expect("Not a Keyutils credential");
+        entry
+            .set_password("timed password")
+            .expect("Couldn't set password for timeout");
+        credential
+            .set_timeout(std::time::Duration::from_secs(300))
+            .expect("Couldn't set timeout");
+        let remaining = credential
+            .get_timeout()
+            .expect("Couldn't read timeout")
+            .expect("Expected a timeout to be set");
+        // The kernel reports the remaining time coarsely (minutes here), so only
+        // require that it is positive and no greater than what we requested.
+        assert!(remaining.as_secs() > 0 && remaining.as_secs() <= 300);
+        entry
+            .delete_credential()
+            .expect("Couldn't delete after timeout");
+    }
+
+    #[test]
+    fn test_big_key_secret() {
+        // A payload well past the `user` type's single-page limit must still
+        // round-trip, which exercises the `big_key` path.
+        let name = generate_random_string();
+        let entry = entry_new(&name, &name);
+        let secret = vec![0x5au8; 4 * super::DEFAULT_BIG_KEY_THRESHOLD];
+        entry
+            .set_secret(&secret)
+            .expect("Couldn't set large secret");
+        assert_eq!(entry.get_secret().expect("Couldn't get large secret"), secret);
+        entry
+            .delete_credential()
+            .expect("Couldn't delete large secret");
+    }
+
     #[test]
     fn test_update() {
         crate::tests::test_update(entry_new);
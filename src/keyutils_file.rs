@@ -0,0 +1,276 @@
+/*!
+
+# Linux (keyutils) store with encrypted-file backing
+
+The [keyutils module](crate::keyutils) is a fast, in-kernel secure cache, but its
+contents vanish on reboot — the module's own documentation recommends re-prompting
+or PAM to reload credentials afterwards.  This store pairs keyutils with a durable,
+passphrase-encrypted file so the fast in-kernel cache survives a reboot without any
+platform keychain.
+
+Each entry is write-through: [set_secret](CredentialApi::set_secret) stores the
+secret in both the kernel keyring and an [encrypted-file record](crate::encrypted_file)
+under `$XDG_DATA_HOME`.  [get_secret](CredentialApi::get_secret) reads from keyutils
+first and, on a [NoEntry](crate::Error::NoEntry) miss (for example after a reboot),
+transparently decrypts the file, repopulates the kernel key, and returns the secret.
+The on-disk blob is sealed under a caller-supplied passphrase; a wrong passphrase
+surfaces as a [BadEncoding](crate::Error::BadEncoding) failure from the file store
+rather than a spurious miss, so the file is only trusted once it decrypts cleanly.
+
+Because the file outlives reboots, this store reports
+[UntilDelete](crate::credential::CredentialPersistence::UntilDelete) rather than the
+bare keyutils store's
+[UntilReboot](crate::credential::CredentialPersistence::UntilReboot).
+ */
+
+use std::path::{Path, PathBuf};
+
+use log::debug;
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
+};
+use super::encrypted_file::EncryptedFileCredential;
+use super::error::{Error, Result};
+use super::keyutils::KeyutilsCredential;
+
+/// The default scrypt work factor (`N = 2^15`) for the backing file store.
+const DEFAULT_LOG_N: u8 = 15;
+
+/// Representation of a keyutils credential backed by an encrypted file.
+///
+/// The credential owns a [KeyutilsCredential] for fast in-memory access and an
+/// [EncryptedFileCredential] for persistence across reboots.
+#[derive(Debug, Clone)]
+pub struct KeyutilsFileCredential {
+    keyutils: KeyutilsCredential,
+    file: EncryptedFileCredential,
+}
+
+impl CredentialApi for KeyutilsFileCredential {
+    /// Set a password in the underlying store.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Set a secret in the underlying store.
+    ///
+    /// The secret is written to keyutils first and then to the encrypted file.
+    /// If the file write fails, the keyutils key is reverted to its previous
+    /// value so the two backings don't drift apart.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let prev_secret = self.keyutils.get_secret();
+        self.keyutils.set_secret(secret)?;
+
+        if let Err(err) = self.file.set_secret(secret) {
+            debug!("Failed set of encrypted file: {err}; reverting keyutils");
+            match prev_secret {
+                Ok(ref secret) => self.keyutils.set_secret(secret),
+                Err(Error::NoEntry) => self.keyutils.delete_credential(),
+                Err(err) => Err(err),
+            }?;
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve a password from the underlying store.
+    ///
+    /// The password is read from keyutils. On a miss, it is read from the
+    /// encrypted file instead (and keyutils is repopulated).
+    fn get_password(&self) -> Result<String> {
+        match self.keyutils.get_password() {
+            Ok(password) => return Ok(password),
+            Err(err) => debug!("Failed get from keyutils: {err}; trying encrypted file"),
+        }
+
+        let password = self.file.get_password()?;
+        self.keyutils.set_password(&password)?;
+
+        Ok(password)
+    }
+
+    /// Retrieve a secret from the underlying store.
+    ///
+    /// The secret is read from keyutils. On a miss, it is read from the
+    /// encrypted file instead (and keyutils is repopulated).
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        match self.keyutils.get_secret() {
+            Ok(secret) => return Ok(secret),
+            Err(err) => debug!("Failed get from keyutils: {err}; trying encrypted file"),
+        }
+
+        let secret = self.file.get_secret()?;
+        self.keyutils.set_secret(&secret)?;
+
+        Ok(secret)
+    }
+
+    /// Delete a password from the underlying store.
+    ///
+    /// The credential is deleted from both keyutils and the encrypted file.
+    fn delete_credential(&self) -> Result<()> {
+        if let Err(err) = self.keyutils.delete_credential() {
+            debug!("cannot delete keyutils credential: {err}");
+        }
+
+        self.file.delete_credential()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl KeyutilsFileCredential {
+    /// Create the platform credential for a keyutils-plus-file entry.
+    ///
+    /// The encrypted file lives under `dir` and is sealed with `passphrase` at
+    /// the given scrypt work factor; the keyutils key uses the usual
+    /// [description conventions](crate::keyutils).
+    pub fn new_with_target_in(
+        dir: &Path,
+        passphrase: &str,
+        log_n: u8,
+        target: Option<&str>,
+        service: &str,
+        user: &str,
+    ) -> Result<Self> {
+        let keyutils = KeyutilsCredential::new_with_target(target, service, user)?;
+        let file =
+            EncryptedFileCredential::new_with_target(dir, passphrase, log_n, target, service, user)?;
+        Ok(Self { keyutils, file })
+    }
+}
+
+/// The builder for keyutils-plus-file credentials.
+#[derive(Debug)]
+pub struct KeyutilsFileCredentialBuilder {
+    dir: PathBuf,
+    passphrase: String,
+    log_n: u8,
+}
+
+/// Return a keyutils-plus-file credential builder rooted at `dir`, sealing the
+/// backing file under `passphrase`.
+///
+/// Pass the directory the encrypted records should live in — typically a
+/// `keyring-rs` subdirectory of `$XDG_DATA_HOME` (see
+/// [default_data_dir]).
+pub fn default_credential_builder<P: AsRef<Path>>(
+    dir: P,
+    passphrase: &str,
+) -> Box<CredentialBuilder> {
+    Box::new(KeyutilsFileCredentialBuilder {
+        dir: dir.as_ref().to_path_buf(),
+        passphrase: passphrase.to_string(),
+        log_n: DEFAULT_LOG_N,
+    })
+}
+
+/// The default directory for the backing encrypted files.
+///
+/// This is `$XDG_DATA_HOME/keyring-rs` when `XDG_DATA_HOME` is set, falling back
+/// to `$HOME/.local/share/keyring-rs` per the XDG Base Directory spec.
+pub fn default_data_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_DATA_HOME") {
+        Some(value) if !value.is_empty() => PathBuf::from(value),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(base.join("keyring-rs"))
+}
+
+impl CredentialBuilderApi for KeyutilsFileCredentialBuilder {
+    /// Build a [KeyutilsFileCredential] for the given target, service, and user.
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        Ok(Box::new(KeyutilsFileCredential::new_with_target_in(
+            &self.dir,
+            &self.passphrase,
+            self.log_n,
+            target,
+            service,
+            user,
+        )?))
+    }
+
+    /// Return the underlying builder object with an `Any` type so that it can be
+    /// downgraded to a [KeyutilsFileCredentialBuilder] for platform-specific processing.
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// The encrypted file survives reboots, so unlike the bare keyutils store
+    /// this store persists until entries are explicitly deleted.
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests::generate_random_string, Entry, Error};
+
+    use super::KeyutilsFileCredential;
+
+    fn temp_dir() -> std::path::PathBuf {
+        let mut dir = std::env::temp_dir();
+        dir.push(format!("keyring-rs-kf-{}", generate_random_string()));
+        dir
+    }
+
+    fn entry_new(dir: &std::path::Path, service: &str, user: &str) -> Entry {
+        let credential =
+            KeyutilsFileCredential::new_with_target_in(dir, "pw", 10, None, service, user)
+                .expect("Can't create keyutils-file credential");
+        Entry::new_with_credential(Box::new(credential))
+    }
+
+    #[test]
+    fn test_missing_entry() {
+        let dir = temp_dir();
+        let name = generate_random_string();
+        let entry = entry_new(&dir, &name, &name);
+        assert!(matches!(entry.get_password(), Err(Error::NoEntry)));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = temp_dir();
+        let name = generate_random_string();
+        let entry = entry_new(&dir, &name, &name);
+        entry.set_password("secret").expect("Can't set password");
+        assert_eq!(entry.get_password().expect("Can't get password"), "secret");
+        entry.delete_credential().expect("Can't delete password");
+        assert!(matches!(entry.get_password(), Err(Error::NoEntry)));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_reload_from_file() {
+        // After the kernel key is gone (simulating a reboot), the secret is
+        // served from the encrypted file and the kernel key is repopulated.
+        let dir = temp_dir();
+        let name = generate_random_string();
+        let entry = entry_new(&dir, &name, &name);
+        entry.set_password("durable").expect("Can't set password");
+        let credential: &KeyutilsFileCredential = entry
+            .get_credential()
+            .downcast_ref()
+            .expect("Not a keyutils-file credential");
+        credential
+            .keyutils
+            .delete_credential()
+            .expect("Can't drop the kernel key");
+        assert_eq!(entry.get_password().expect("Can't reload password"), "durable");
+        assert!(credential.keyutils.get_credential().is_ok());
+        entry.delete_credential().expect("Can't delete password");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -26,33 +26,36 @@ each of its entries. Because keyutils entries don't have attributes, entries
 in this store don't expose attributes either. Because keyutils entries can't
 store empty passwords/secrets, this store's entries can't either.
 
+The two-tier behavior (cache-first writes with revert-on-failure, read-through
+with backfill) lives in the generic [caching](crate::caching) store; this module
+just pins the keyutils cache tier over the secret-service store tier.
+
 See the documentation for the `keyutils` and `secret-service` modules if you
 want details about how the underlying storage is handled.
  */
 
-use log::debug;
-
+use super::caching::CachingCredential;
 use super::credential::{
     Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
 };
-use super::error::{Error, Result};
+use super::error::Result;
 use super::keyutils::KeyutilsCredential;
 use super::secret_service::{SsCredential, SsCredentialBuilder};
 
 /// Representation of a keyutils-persistent credential.
 ///
-/// The credential owns a [KeyutilsCredential] for in-memory usage and
-/// a [SsCredential] for persistence.
-#[derive(Debug, Clone)]
+/// This is a thin alias over a [CachingCredential] whose cache tier is a
+/// [KeyutilsCredential] and whose store tier is an [SsCredential]; all of the
+/// two-tier logic lives in the caching store.
+#[derive(Debug)]
 pub struct KeyutilsPersistentCredential {
-    keyutils: KeyutilsCredential,
-    ss: SsCredential,
+    inner: CachingCredential,
 }
 
 impl CredentialApi for KeyutilsPersistentCredential {
     /// Set a password in the underlying store
     fn set_password(&self, password: &str) -> Result<()> {
-        self.set_secret(password.as_bytes())
+        self.inner.set_password(password)
     }
 
     /// Set a secret in the underlying store
@@ -61,21 +64,7 @@ impl CredentialApi for KeyutilsPersistentCredential {
     /// secret-service. If the latter set fails, the former
     /// is reverted.
     fn set_secret(&self, secret: &[u8]) -> Result<()> {
-        let prev_secret = self.keyutils.get_secret();
-        self.keyutils.set_secret(secret)?;
-
-        if let Err(err) = self.ss.set_secret(secret) {
-            debug!("Failed set of secret-service: {err}; reverting keyutils");
-            match prev_secret {
-                Ok(ref secret) => self.keyutils.set_secret(secret),
-                Err(Error::NoEntry) => self.keyutils.delete_credential(),
-                Err(err) => Err(err),
-            }?;
-
-            return Err(err);
-        }
-
-        Ok(())
+        self.inner.set_secret(secret)
     }
 
     /// Retrieve a password from the underlying store
@@ -84,19 +73,7 @@ impl CredentialApi for KeyutilsPersistentCredential {
     /// password is retrieved from secret-service instead (and
     /// keyutils is updated).
     fn get_password(&self) -> Result<String> {
-        match self.keyutils.get_password() {
-            Ok(password) => {
-                return Ok(password);
-            }
-            Err(err) => {
-                debug!("Failed get from keyutils: {err}; trying secret service")
-            }
-        }
-
-        let password = self.ss.get_password().map_err(ambiguous_to_no_entry)?;
-        self.keyutils.set_password(&password)?;
-
-        Ok(password)
+        self.inner.get_password()
     }
 
     /// Retrieve a secret from the underlying store
@@ -105,19 +82,15 @@ impl CredentialApi for KeyutilsPersistentCredential {
     /// secret is retrieved from secret-service instead (and keyutils
     /// is updated).
     fn get_secret(&self) -> Result<Vec<u8>> {
-        match self.keyutils.get_secret() {
-            Ok(secret) => {
-                return Ok(secret);
-            }
-            Err(err) => {
-                debug!("Failed get from keyutils: {err}; trying secret service")
-            }
-        }
-
-        let secret = self.ss.get_secret().map_err(ambiguous_to_no_entry)?;
-        self.keyutils.set_secret(&secret)?;
-
-        Ok(secret)
+        self.inner.get_secret()
+    }
+
+    /// Report metadata, sourced from the secret-service tier.
+    ///
+    /// Keyutils has no attribute storage, so creation/rotation timestamps are
+    /// read from the secret-service store tier via the caching credential.
+    fn get_metadata(&self) -> Result<super::credential::CredentialMetadata> {
+        self.inner.get_metadata()
     }
 
     /// Delete a password from the underlying store.
@@ -125,11 +98,7 @@ impl CredentialApi for KeyutilsPersistentCredential {
     /// The credential is deleted from both keyutils and
     /// secret-service.
     fn delete_credential(&self) -> Result<()> {
-        if let Err(err) = self.keyutils.delete_credential() {
-            debug!("cannot delete keyutils credential: {err}");
-        }
-
-        self.ss.delete_credential()
+        self.inner.delete_credential()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -144,12 +113,13 @@ impl CredentialApi for KeyutilsPersistentCredential {
 impl KeyutilsPersistentCredential {
     /// Create the platform credential for a Keyutils entry.
     ///
-    /// This just passes the arguments to the underlying two stores
-    /// and wraps their results with an entry that holds both.
+    /// This builds the keyutils cache tier and the secret-service store tier
+    /// and wraps them in a [CachingCredential].
     pub fn new_with_target(target: Option<&str>, service: &str, user: &str) -> Result<Self> {
-        let ss = SsCredential::new_with_target(target, service, user)?;
         let keyutils = KeyutilsCredential::new_with_target(target, service, user)?;
-        Ok(Self { keyutils, ss })
+        let ss = SsCredential::new_with_target(target, service, user)?;
+        let inner = CachingCredential::new(Box::new(keyutils), Box::new(ss));
+        Ok(Self { inner })
     }
 }
 
@@ -168,7 +138,7 @@ pub fn default_credential_builder() -> Box<CredentialBuilder> {
 impl CredentialBuilderApi for KeyutilsPersistentCredentialBuilder {
     /// Build a [KeyutilsPersistentCredential] for the given target, service, and user.
     fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
-        Ok(Box::new(SsCredential::new_with_target(
+        Ok(Box::new(KeyutilsPersistentCredential::new_with_target(
             target, service, user,
         )?))
     }
@@ -187,15 +157,6 @@ impl CredentialBuilderApi for KeyutilsPersistentCredentialBuilder {
     }
 }
 
-/// Replace any Ambiguous error with a NoEntry one
-fn ambiguous_to_no_entry(err: Error) -> Error {
-    if let Error::Ambiguous(_) = err {
-        return Error::NoEntry;
-    };
-
-    err
-}
-
 #[cfg(test)]
 mod tests {
     use crate::{Entry, Error};
@@ -183,9 +183,40 @@ are not recommended, as they may cause the RPC mechanism to fail.
 
 use log::debug;
 use std::collections::HashMap;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
 
-pub use credential::{Credential, CredentialBuilder};
+/// The reserved attribute name used to store a credential's absolute expiry.
+///
+/// The value is an RFC 3339 timestamp; see [Entry::set_password_with_ttl].
+const EXPIRES_ATTRIBUTE: &str = "keyring.expires";
+
+/// The reserved attribute name used to store an identity's certificate.
+///
+/// The value is the hex-encoded DER certificate; see [Entry::set_identity].
+const CERTIFICATE_ATTRIBUTE: &str = "keyring.certificate";
+
+/// The reserved attribute name used to store an entry's [CredentialKind].
+///
+/// See [Entry::set_kind] and [Entry::kind].
+const KIND_ATTRIBUTE: &str = "keyring.kind";
+
+/// The reserved attribute name recording when a credential was first written.
+///
+/// The value is an RFC 3339 timestamp; see [Entry::rotate_secret].
+const CREATED_ATTRIBUTE: &str = "keyring.created";
+
+/// The reserved attribute name recording when a credential was last rotated.
+///
+/// The value is an RFC 3339 timestamp; see [Entry::rotate_secret].
+const ROTATED_ATTRIBUTE: &str = "keyring.rotated";
+
+pub use async_keyring::{set_default_async_credential_builder, AsyncEntry, KeyStorageResponse};
+pub use credential::{
+    AttributeSupport, Credential, CredentialBuilder, CredentialCapabilities, CredentialKind,
+    CredentialMetadata, SecretAlphabet, SecretSpec,
+};
 pub use error::{Error, Result};
+pub use secret::{Locked, Secret};
 
 pub mod mock;
 
@@ -252,6 +283,42 @@ pub mod keyutils_persistent;
 ))]
 pub use keyutils_persistent as default;
 
+// keyutils cache with encrypted-file backing for persistence across reboots;
+// available on Linux wherever the native keyutils store is, but never the default
+#[cfg(all(target_os = "linux", feature = "linux-native"))]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub mod keyutils_file;
+
+// read-only store backed by systemd's credential mechanism; always available
+// on Linux as a sibling store but never the default
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub mod credential_directory;
+
+// optional libsecret-backed store, which delegates collection unlocking and
+// prompting to the platform's secret agent
+#[cfg(all(
+    any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"),
+    feature = "libsecret",
+))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd")))
+)]
+pub mod libsecret;
+
+// alternative libsecret store that loads the library at runtime, so binaries
+// need no build-time DBus linkage and still run where libsecret is absent
+#[cfg(all(
+    any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"),
+    feature = "libsecret-dynamic",
+))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(target_os = "linux", target_os = "freebsd", target_os = "openbsd")))
+)]
+pub mod libsecret_dynamic;
+
 // fallback to mock if neither keyutils nor secret service is available
 #[cfg(any(
     all(
@@ -279,8 +346,14 @@ pub use macos as default;
 #[cfg(all(target_os = "macos", not(feature = "apple-native")))]
 pub use mock as default;
 
-#[cfg(all(target_os = "ios", feature = "apple-native"))]
-#[cfg_attr(docsrs, doc(cfg(target_os = "ios")))]
+// The SecItem store is the default on iOS and is also available on macOS as an
+// alternative to the named-keychain [macos] store (a data-protection keychain
+// that needs no `SecKeychain`), though it is never the macOS default.
+#[cfg(all(
+    any(target_os = "ios", target_os = "macos"),
+    feature = "apple-native"
+))]
+#[cfg_attr(docsrs, doc(cfg(any(target_os = "ios", target_os = "macos"))))]
 pub mod ios;
 #[cfg(all(target_os = "ios", feature = "apple-native"))]
 pub use ios as default;
@@ -308,8 +381,22 @@ pub use windows as default;
 )))]
 pub use mock as default;
 
+pub mod async_keyring;
+pub mod caching;
+pub mod cargo;
 pub mod credential;
+pub mod encrypted;
+pub mod encrypted_file;
+pub mod encrypted_master;
+pub mod encrypted_portable;
 pub mod error;
+pub mod export;
+pub mod file;
+pub mod migrate;
+pub mod netrc;
+pub mod process;
+pub mod secret;
+pub mod url_resolver;
 
 #[derive(Default, Debug)]
 struct EntryBuilder {
@@ -336,6 +423,57 @@ pub fn set_default_credential_builder(new: Box<CredentialBuilder>) {
     guard.inner = Some(new);
 }
 
+/// The registry of named credential stores, populated by
+/// [register_credential_builder].
+fn store_registry() -> &'static std::sync::RwLock<HashMap<String, Box<CredentialBuilder>>> {
+    static REGISTRY: std::sync::OnceLock<std::sync::RwLock<HashMap<String, Box<CredentialBuilder>>>> =
+        std::sync::OnceLock::new();
+    REGISTRY.get_or_init(|| std::sync::RwLock::new(HashMap::new()))
+}
+
+/// Register a credential builder under a name for use with [Entry::new_in_store].
+///
+/// Unlike [set_default_credential_builder], which installs a single global
+/// builder, the registry lets several named stores coexist in one process — for
+/// example a `keyutils` store for ephemeral secrets alongside a `secret-service`
+/// store for persistent ones, or an old and a new store during a migration.
+/// Registering a name that already exists replaces its builder.
+pub fn register_credential_builder(name: &str, builder: Box<CredentialBuilder>) {
+    let mut guard = store_registry()
+        .write()
+        .expect("Poisoned RwLock in keyring-rs: please report a bug!");
+    guard.insert(name.to_string(), builder);
+}
+
+/// List the names of the currently registered credential stores, sorted.
+pub fn list_registered_stores() -> Vec<String> {
+    let guard = store_registry()
+        .read()
+        .expect("Poisoned RwLock in keyring-rs: please report a bug!");
+    let mut names: Vec<String> = guard.keys().cloned().collect();
+    names.sort();
+    names
+}
+
+fn build_in_store(
+    store_name: &str,
+    target: Option<&str>,
+    service: &str,
+    user: &str,
+) -> Result<Entry> {
+    let guard = store_registry()
+        .read()
+        .expect("Poisoned RwLock in keyring-rs: please report a bug!");
+    let builder = guard.get(store_name).ok_or_else(|| {
+        Error::Invalid(
+            "store".to_string(),
+            format!("no credential store named '{store_name}' is registered"),
+        )
+    })?;
+    let credential = builder.build(target, service, user)?;
+    Ok(Entry { inner: credential })
+}
+
 fn build_default_credential(target: Option<&str>, service: &str, user: &str) -> Result<Entry> {
     static DEFAULT: std::sync::OnceLock<Box<CredentialBuilder>> = std::sync::OnceLock::new();
     let guard = DEFAULT_BUILDER
@@ -349,6 +487,34 @@ fn build_default_credential(target: Option<&str>, service: &str, user: &str) ->
     Ok(Entry { inner: credential })
 }
 
+/// A scheme for composing a platform target name from a service and user.
+///
+/// Different tools lay out their platform credentials differently: node-keytar
+/// uses `service/account`, while keyring-rs's native Windows scheme is
+/// `user.service`.  A [Naming] lets callers read (and migrate forward)
+/// credentials written under one of these alternate conventions.  The
+/// [Legacy](Naming::Legacy) variant carries an arbitrary composer for schemes
+/// not covered by the built-in variants.
+pub enum Naming {
+    /// The crate's own platform-default naming (no explicit target).
+    Native,
+    /// node-keytar's `service/account` target-name convention.
+    Keytar,
+    /// An arbitrary caller-supplied `(service, user) -> target` composer.
+    Legacy(fn(&str, &str) -> String),
+}
+
+impl Naming {
+    /// Compute the target name for this scheme, or `None` to use the default.
+    fn target(&self, service: &str, user: &str) -> Option<String> {
+        match self {
+            Naming::Native => None,
+            Naming::Keytar => Some(format!("{service}/{user}")),
+            Naming::Legacy(compose) => Some(compose(service, user)),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Entry {
     inner: Box<Credential>,
@@ -386,12 +552,96 @@ impl Entry {
         Ok(entry)
     }
 
+    /// Create an entry in a named store registered with [register_credential_builder].
+    ///
+    /// [Entry::new] and [Entry::new_with_target] always use the default builder;
+    /// this lets a caller route a specific entry to one of several registered
+    /// stores by name.  Returns an [Invalid](Error::Invalid) `store` error if no
+    /// store is registered under `store_name`.
+    pub fn new_in_store(
+        store_name: &str,
+        target: Option<&str>,
+        service: &str,
+        user: &str,
+    ) -> Result<Entry> {
+        debug!("creating entry in store {store_name} with service {service}, user {user}");
+        build_in_store(store_name, target, service, user)
+    }
+
+    /// Create an entry whose platform target name follows the given [Naming] scheme.
+    ///
+    /// This lets an app read credentials written by another tool (e.g. node-keytar)
+    /// by composing the target name the way that tool does.  [Naming::Native]
+    /// is equivalent to [Entry::new].
+    pub fn new_with_naming(service: &str, user: &str, naming: Naming) -> Result<Entry> {
+        match naming.target(service, user) {
+            None => Entry::new(service, user),
+            Some(target) => Entry::new_with_target(&target, service, user),
+        }
+    }
+
+    /// Read this entry's password, falling back to a legacy naming scheme.
+    ///
+    /// If the native lookup misses with [NoEntry](Error::NoEntry), the password
+    /// is read from an entry built with the `legacy` [Naming] scheme and, on a
+    /// hit, rewritten under the native scheme so subsequent reads use the native
+    /// format.  This is the generalized, cross-platform form of the
+    /// "read the old format, migrate forward" shim other credential libraries ship.
+    pub fn get_password_compat(
+        &self,
+        service: &str,
+        user: &str,
+        legacy: Naming,
+    ) -> Result<String> {
+        match self.get_password() {
+            Err(Error::NoEntry) => {
+                let fallback = Entry::new_with_naming(service, user, legacy)?;
+                let password = fallback.get_password()?;
+                // best-effort migration into the native scheme
+                let _ = self.set_password(&password);
+                Ok(password)
+            }
+            other => other,
+        }
+    }
+
     /// Create an entry that uses the given platform credential for storage.
     pub fn new_with_credential(credential: Box<Credential>) -> Entry {
         debug!("create entry from {credential:?}");
         Entry { inner: credential }
     }
 
+    /// Enumerate every credential in the default store matching `attributes`.
+    ///
+    /// Uses the same default (or [overridden](set_default_credential_builder))
+    /// builder as [Entry::new]. Attribute keys and values are backend-specific
+    /// (see [find](crate::credential::CredentialBuilderApi::find)); an empty
+    /// map matches every credential the backend is willing to enumerate. This
+    /// is the tool to reach for when you'd otherwise hit an
+    /// [Ambiguous](Error::Ambiguous) error and want to list the matches
+    /// instead of picking one blind.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [NotSupported](Error::NotSupported) error on stores that
+    /// can't enumerate their contents.
+    pub fn search(attributes: &HashMap<&str, &str>) -> Result<Vec<Entry>> {
+        static DEFAULT: std::sync::OnceLock<Box<CredentialBuilder>> = std::sync::OnceLock::new();
+        debug!("searching default store for attributes {attributes:?}");
+        let guard = DEFAULT_BUILDER
+            .read()
+            .expect("Poisoned RwLock in keyring-rs: please report a bug!");
+        let builder = guard
+            .inner
+            .as_ref()
+            .unwrap_or_else(|| DEFAULT.get_or_init(|| default::default_credential_builder()));
+        let credentials = builder.find(attributes)?;
+        Ok(credentials
+            .into_iter()
+            .map(|inner| Entry { inner })
+            .collect())
+    }
+
     /// Set the password for this entry.
     ///
     /// Can return an [Ambiguous](Error::Ambiguous) error
@@ -404,6 +654,87 @@ impl Entry {
         self.inner.set_password(password)
     }
 
+    /// Set the password for this entry behind a device-local access policy.
+    ///
+    /// On backends that support `SecAccessControl` (macOS and iOS) the policy
+    /// code — `0` for user presence, `1` for the currently enrolled biometry,
+    /// `2` for the device passcode — gates later reads of the credential behind
+    /// an authentication prompt.  On backends that don't support access control
+    /// the policy is ignored and the password is stored unguarded.
+    pub fn set_password_with_policy(&self, password: &str, policy: u32) -> Result<()> {
+        debug!("set password with policy {policy} for entry {:?}", self.inner);
+        #[cfg(target_os = "macos")]
+        if let Some(credential) = self.inner.as_any().downcast_ref::<crate::macos::MacCredential>()
+        {
+            use crate::macos::AccessPolicy;
+            let policy = match policy {
+                0 => AccessPolicy::UserPresence,
+                1 => AccessPolicy::BiometryCurrentSet,
+                2 => AccessPolicy::DevicePasscode,
+                _ => {
+                    return Err(Error::Invalid(
+                        "policy".to_string(),
+                        format!("'{policy}' is not a known access policy"),
+                    ))
+                }
+            };
+            return credential
+                .with_access_control(Some(policy))
+                .set_password(password);
+        }
+        self.inner.set_password(password)
+    }
+
+    /// Set the password for this entry, expiring it after the given duration.
+    ///
+    /// The absolute expiry is stored in the credential's metadata side-channel
+    /// (the reserved [`keyring.expires`](EXPIRES_ATTRIBUTE) attribute), so a
+    /// later [get_password](Entry::get_password) reports a
+    /// [NoEntry](Error::NoEntry) error — and removes the underlying
+    /// credential — once the deadline has passed.  This requires a backend that
+    /// persists attributes; on backends that drop them the password does not
+    /// expire.
+    pub fn set_password_with_ttl(&self, password: &str, ttl: Duration) -> Result<()> {
+        self.set_password_expiring_at(password, OffsetDateTime::now_utc() + ttl)
+    }
+
+    /// Set the password for this entry, expiring it at the given instant.
+    ///
+    /// See [set_password_with_ttl](Entry::set_password_with_ttl) for the
+    /// enforcement semantics.
+    pub fn set_password_expiring_at(
+        &self,
+        password: &str,
+        expiry: OffsetDateTime,
+    ) -> Result<()> {
+        debug!("set password with expiry for entry {:?}", self.inner);
+        self.inner.set_password(password)?;
+        let expiry = expiry
+            .format(&Rfc3339)
+            .map_err(|err| Error::Invalid("expiry".to_string(), err.to_string()))?;
+        let mut attributes = HashMap::new();
+        attributes.insert(EXPIRES_ATTRIBUTE, expiry.as_str());
+        self.inner.update_attributes(&attributes)
+    }
+
+    /// Report whether this entry's stored credential has passed its expiry.
+    ///
+    /// Returns `false` when the backend records no expiry (or doesn't persist
+    /// attributes at all).
+    fn is_expired(&self) -> bool {
+        let attributes = match self.inner.get_attributes() {
+            Ok(attributes) => attributes,
+            Err(_) => return false,
+        };
+        match attributes.get(EXPIRES_ATTRIBUTE) {
+            Some(expiry) => match OffsetDateTime::parse(expiry, &Rfc3339) {
+                Ok(expiry) => OffsetDateTime::now_utc() >= expiry,
+                Err(_) => false,
+            },
+            None => false,
+        }
+    }
+
     /// Set the secret for this entry.
     ///
     /// Can return an [Ambiguous](Error::Ambiguous) error
@@ -427,7 +758,13 @@ impl Entry {
     /// application wrote the ambiguous credential.
     pub fn get_password(&self) -> Result<String> {
         debug!("get password from entry {:?}", self.inner);
-        self.inner.get_password()
+        let password = self.inner.get_password()?;
+        if self.is_expired() {
+            // best-effort removal of the expired credential
+            let _ = self.inner.delete_credential();
+            return Err(Error::NoEntry);
+        }
+        Ok(password)
     }
 
     /// Retrieve the secret saved for this entry.
@@ -444,6 +781,30 @@ impl Entry {
         self.inner.get_secret()
     }
 
+    /// Retrieve this entry's secret, generating and storing one if none exists.
+    ///
+    /// This is the read-or-initialize pattern for first-run secrets: if the
+    /// entry already has a secret it is returned, and if it doesn't a fresh
+    /// random secret described by `spec` is generated, stored, and returned.
+    /// Any error other than [NoEntry](Error::NoEntry) from the initial read
+    /// propagates unchanged.
+    pub fn get_or_create_secret(&self, spec: &SecretSpec) -> Result<Vec<u8>> {
+        debug!("get or create secret for entry {:?}", self.inner);
+        self.inner.get_or_create_secret(spec)
+    }
+
+    /// Retrieve this entry's password, generating and storing one if none exists.
+    ///
+    /// Like [get_or_create_secret](Entry::get_or_create_secret), but returns the
+    /// secret as a string.  A generated password is drawn from a character
+    /// alphabet (use [SecretSpec::password]) so it round-trips as UTF-8;
+    /// returns a [BadEncoding](Error::BadEncoding) error if an existing secret
+    /// is not valid UTF-8.
+    pub fn get_or_create_password(&self, spec: &SecretSpec) -> Result<String> {
+        debug!("get or create password for entry {:?}", self.inner);
+        crate::error::decode_password(self.inner.get_or_create_secret(spec)?)
+    }
+
     /// Get the attributes on the underlying credential for this entry.
     ///
     /// Some of the underlying credential stores allow credentials to have named attributes
@@ -485,6 +846,46 @@ impl Entry {
         self.inner.update_attributes(attributes)
     }
 
+    /// Replace this entry's secret and stamp its rotation timestamp.
+    ///
+    /// The new secret is written first, then the reserved
+    /// [`keyring.rotated`](ROTATED_ATTRIBUTE) attribute is set to the current
+    /// time (and [`keyring.created`](CREATED_ATTRIBUTE) is set too if the
+    /// credential doesn't already carry one).  On a store that persists
+    /// attributes the stamps survive and surface through
+    /// [metadata](Entry::metadata), letting callers build auto-rotation policies;
+    /// on a store that drops attributes only the secret is updated.
+    pub fn rotate_secret(&self, new_secret: &[u8]) -> Result<()> {
+        debug!("rotate secret for entry {:?}", self.inner);
+        self.inner.set_secret(new_secret)?;
+        let now = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .map_err(|err| Error::Invalid("rotated".to_string(), err.to_string()))?;
+        let has_created = self
+            .inner
+            .get_attributes()
+            .map(|attributes| attributes.contains_key(CREATED_ATTRIBUTE))
+            .unwrap_or(false);
+        let mut attributes = HashMap::new();
+        attributes.insert(ROTATED_ATTRIBUTE, now.as_str());
+        if !has_created {
+            attributes.insert(CREATED_ATTRIBUTE, now.as_str());
+        }
+        self.inner.update_attributes(&attributes)
+    }
+
+    /// Get machine-readable [metadata](CredentialMetadata) for this entry.
+    ///
+    /// Backends that record credential metadata (such as the Windows Credential
+    /// Manager's last-written time and persistence scope) surface it here;
+    /// backends that don't return a [CredentialMetadata] with all fields `None`.
+    ///
+    /// Returns a [NoEntry](Error::NoEntry) error if there isn't a credential for this entry.
+    pub fn metadata(&self) -> Result<CredentialMetadata> {
+        debug!("get metadata from entry {:?}", self.inner);
+        self.inner.get_metadata()
+    }
+
     /// Delete the underlying credential for this entry.
     ///
     /// Returns a [NoEntry](Error::NoEntry) error if there isn't one.
@@ -503,6 +904,180 @@ impl Entry {
         self.inner.delete_credential()
     }
 
+    /// Set the password on many entries in one call.
+    ///
+    /// Each entry is processed independently and its result is returned in the
+    /// same position as the input, so a failure on one entry does not abort the
+    /// others.  This is a convenience over N separate [set_password](Entry::set_password)
+    /// calls; the underlying stores are still contacted one credential at a time.
+    pub fn set_batch(items: &[(Entry, &str)]) -> Vec<Result<()>> {
+        items
+            .iter()
+            .map(|(entry, password)| entry.set_password(password))
+            .collect()
+    }
+
+    /// Get the password from many entries in one call.
+    ///
+    /// Results are returned positionally and are partial-failure tolerant: a
+    /// [NoEntry](Error::NoEntry) (or any other error) on one entry is reported
+    /// in place without affecting the rest.
+    pub fn get_batch(entries: &[Entry]) -> Vec<Result<String>> {
+        entries.iter().map(|entry| entry.get_password()).collect()
+    }
+
+    /// Delete the underlying credential of many entries in one call.
+    ///
+    /// Results are returned positionally and are partial-failure tolerant.
+    pub fn delete_batch(entries: &[Entry]) -> Vec<Result<()>> {
+        entries
+            .iter()
+            .map(|entry| entry.delete_credential())
+            .collect()
+    }
+
+    /// Report whether the secret-service collection backing this entry is locked.
+    ///
+    /// This is a passthrough to [SsCredential::is_locked](crate::secret_service::SsCredential::is_locked)
+    /// and errors with [Invalid](Error::Invalid) if the entry is not backed by
+    /// the secret-service store.
+    #[cfg(all(
+        any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"),
+        any(feature = "sync-secret-service", feature = "async-secret-service"),
+    ))]
+    pub fn is_locked(&self) -> Result<bool> {
+        self.as_ss_credential()?.is_locked()
+    }
+
+    /// Unlock the secret-service collection backing this entry, prompting the
+    /// user if necessary.
+    ///
+    /// This is a passthrough to [SsCredential::unlock](crate::secret_service::SsCredential::unlock)
+    /// and errors with [Invalid](Error::Invalid) if the entry is not backed by
+    /// the secret-service store.
+    #[cfg(all(
+        any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"),
+        any(feature = "sync-secret-service", feature = "async-secret-service"),
+    ))]
+    pub fn unlock(&self) -> Result<()> {
+        self.as_ss_credential()?.unlock()
+    }
+
+    /// Lock the secret-service collection backing this entry.
+    ///
+    /// This is a passthrough to [SsCredential::lock](crate::secret_service::SsCredential::lock)
+    /// and errors with [Invalid](Error::Invalid) if the entry is not backed by
+    /// the secret-service store.
+    #[cfg(all(
+        any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"),
+        any(feature = "sync-secret-service", feature = "async-secret-service"),
+    ))]
+    pub fn lock(&self) -> Result<()> {
+        self.as_ss_credential()?.lock()
+    }
+
+    /// Downcast this entry's credential to an [SsCredential](crate::secret_service::SsCredential).
+    #[cfg(all(
+        any(target_os = "linux", target_os = "freebsd", target_os = "openbsd"),
+        any(feature = "sync-secret-service", feature = "async-secret-service"),
+    ))]
+    fn as_ss_credential(&self) -> Result<&crate::secret_service::SsCredential> {
+        self.inner
+            .as_any()
+            .downcast_ref::<crate::secret_service::SsCredential>()
+            .ok_or_else(|| {
+                Error::Invalid(
+                    "entry".to_string(),
+                    "not backed by the secret-service store".to_string(),
+                )
+            })
+    }
+
+    /// Store a certificate-plus-private-key identity in this entry.
+    ///
+    /// On a store that models identities natively (the Apple Security framework)
+    /// the store does the binding.  On a store that only holds opaque bytes, the
+    /// private key material is stored as the secret and the DER certificate is
+    /// kept in the reserved [`keyring.certificate`](CERTIFICATE_ATTRIBUTE)
+    /// attribute, so the identity round-trips through any attribute-preserving
+    /// store.
+    pub fn set_identity(&self, certificate_der: &[u8], private_key: &[u8]) -> Result<()> {
+        debug!("set identity for entry {:?}", self.inner);
+        self.inner.set_secret(private_key)?;
+        let certificate = encode_hex(certificate_der);
+        let mut attributes = HashMap::new();
+        attributes.insert(CERTIFICATE_ATTRIBUTE, certificate.as_str());
+        self.inner.update_attributes(&attributes)
+    }
+
+    /// Retrieve this entry's certificate as DER-encoded bytes.
+    ///
+    /// Native identity stores answer directly; on stores without identity
+    /// support this reads the certificate back from the reserved
+    /// [`keyring.certificate`](CERTIFICATE_ATTRIBUTE) attribute, returning a
+    /// [NoEntry](Error::NoEntry) error if none was stored.
+    pub fn get_certificate(&self) -> Result<Vec<u8>> {
+        debug!("get certificate from entry {:?}", self.inner);
+        match self.inner.get_certificate() {
+            Err(Error::NotSupported(_)) => {
+                let attributes = self.inner.get_attributes()?;
+                match attributes.get(CERTIFICATE_ATTRIBUTE) {
+                    Some(certificate) => decode_hex(certificate),
+                    None => Err(Error::NoEntry),
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Sign `data` with this entry's private key.
+    ///
+    /// Returns [NotSupported](Error::NotSupported) on stores that hold only
+    /// opaque secrets and cannot perform signatures.
+    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>> {
+        debug!("sign with entry {:?}", self.inner);
+        self.inner.sign(data)
+    }
+
+    /// Tag this entry with a [CredentialKind].
+    ///
+    /// The kind is written to the reserved [`keyring.kind`](KIND_ATTRIBUTE)
+    /// attribute, so it round-trips through any attribute-preserving store and
+    /// is reported back by [kind](Entry::kind).  Requires an existing credential
+    /// (some stores reject attribute updates on a missing entry).
+    pub fn set_kind(&self, kind: CredentialKind) -> Result<()> {
+        debug!("set kind {kind:?} for entry {:?}", self.inner);
+        let value = kind.as_attribute();
+        let mut attributes = HashMap::new();
+        attributes.insert(KIND_ATTRIBUTE, value.as_str());
+        self.inner.update_attributes(&attributes)
+    }
+
+    /// Report the [CredentialKind] of this entry.
+    ///
+    /// Returns [Password](CredentialKind::Password) when no kind has been
+    /// recorded; see [set_kind](Entry::set_kind).
+    pub fn kind(&self) -> Result<CredentialKind> {
+        debug!("get kind from entry {:?}", self.inner);
+        self.inner.kind()
+    }
+
+    /// Report the [capabilities](CredentialCapabilities) of this entry's store.
+    pub fn capabilities(&self) -> CredentialCapabilities {
+        self.inner.capabilities()
+    }
+
+    /// Report whether this entry's store retains any attributes.
+    ///
+    /// A convenience over [capabilities](Entry::capabilities): `false` when the
+    /// backend reports [AttributeSupport::None].
+    pub fn supports_attributes(&self) -> bool {
+        !matches!(
+            self.capabilities().attributes,
+            credential::AttributeSupport::None
+        )
+    }
+
     /// Return a reference to this entry's wrapped credential.
     ///
     /// The reference is of the [Any](std::any::Any) type, so it can be
@@ -513,6 +1088,30 @@ impl Entry {
     }
 }
 
+/// Hex-encode bytes for storage in a string attribute.
+fn encode_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
+
+/// Decode a hex string produced by [encode_hex], erroring on malformed input.
+fn decode_hex(text: &str) -> Result<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return Err(Error::BadEncoding(text.as_bytes().to_vec()));
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&text[i..i + 2], 16)
+                .map_err(|_| Error::BadEncoding(text.as_bytes().to_vec()))
+        })
+        .collect()
+}
+
 #[cfg(doctest)]
 doc_comment::doctest!("../README.md", readme);
 
@@ -686,6 +1285,38 @@ mod tests {
         test_round_trip_secret("non-ascii password", &entry, secret.as_slice());
     }
 
+    /// Fill a buffer with a deterministic repeating pattern.
+    ///
+    /// The cycle length is 251 — the largest prime under 256 — so no power-of-two
+    /// block size aligns with it: swapping adjacent blocks or dropping a chunk
+    /// reliably changes the bytes, unlike a cycle of 256 would.
+    fn patterned_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// Sweep a curated list of secret lengths through the round-trip test.
+    ///
+    /// The lengths probe zero, the first few bytes, and the neighbourhoods of
+    /// common block and page boundaries, so off-by-one, truncation, and
+    /// NUL-handling bugs in a backend surface that a single fixed size would
+    /// miss.  Each secret is a [patterned_bytes] buffer, so embedded NUL bytes
+    /// are exercised and exact equality is asserted.
+    pub fn test_round_trip_secret_sizes<F>(f: F)
+    where
+        F: Fn(&str, &str) -> Entry,
+    {
+        const SIZES: &[usize] = &[
+            0, 1, 2, 3, 4, 7, 8, 15, 16, 17, 255, 256, 257, 511, 512, 513, 1023, 1024, 1025, 4095,
+            4096, 4097,
+        ];
+        for &size in SIZES {
+            let name = generate_random_string();
+            let entry = f(&name, &name);
+            let secret = patterned_bytes(size);
+            test_round_trip_secret(&format!("{size}-byte secret"), &entry, &secret);
+        }
+    }
+
     pub fn test_update<F>(f: F)
     where
         F: FnOnce(&str, &str) -> Entry,
@@ -700,6 +1331,192 @@ mod tests {
         );
     }
 
+    /// One record in a TOML test-vector corpus.
+    ///
+    /// The secret is given either inline as a UTF-8 `secret` string or, for
+    /// arbitrary binary, as a `hex` field; exactly one of the two must be
+    /// present.  Optional `attributes` are applied after the secret is set.
+    #[derive(serde::Deserialize)]
+    struct TestVector {
+        name: String,
+        service: String,
+        user: String,
+        #[serde(default)]
+        attributes: HashMap<String, String>,
+        #[serde(default)]
+        secret: Option<String>,
+        #[serde(default)]
+        hex: Option<String>,
+    }
+
+    /// The top-level shape of a test-vector file: an array of `[[vector]]` tables.
+    #[derive(serde::Deserialize)]
+    struct TestVectorFile {
+        #[serde(default)]
+        vector: Vec<TestVector>,
+    }
+
+    /// Run a corpus of credential test vectors loaded from a TOML file.
+    ///
+    /// Each record is materialized into an [Entry] via `f`, its secret is
+    /// round-tripped through set/get/delete, and any mismatch panics with the
+    /// record's `name`.  This lets maintainers drop regression vectors (such as
+    /// a secret that once triggered a backend truncation bug) into a shared
+    /// corpus and replay them across every backend without new Rust per case.
+    pub fn run_test_vectors<F>(path: &str, f: F)
+    where
+        F: Fn(&str, &str) -> Entry,
+    {
+        let text = std::fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("Can't read test vectors from {path}: {err:?}"));
+        let corpus: TestVectorFile = toml::from_str(&text)
+            .unwrap_or_else(|err| panic!("Can't parse test vectors in {path}: {err:?}"));
+        for vector in corpus.vector {
+            let secret = match (&vector.secret, &vector.hex) {
+                (Some(text), None) => text.as_bytes().to_vec(),
+                (None, Some(hex)) => super::decode_hex(hex)
+                    .unwrap_or_else(|err| panic!("Bad hex in vector '{}': {err:?}", vector.name)),
+                _ => panic!(
+                    "Vector '{}' must set exactly one of `secret` or `hex`",
+                    vector.name
+                ),
+            };
+            let entry = f(&vector.service, &vector.user);
+            if !vector.attributes.is_empty() {
+                entry
+                    .set_secret(&secret)
+                    .unwrap_or_else(|err| panic!("Can't set secret for '{}': {err:?}", vector.name));
+                let attrs: HashMap<&str, &str> = vector
+                    .attributes
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect();
+                entry.update_attributes(&attrs).unwrap_or_else(|err| {
+                    panic!("Can't set attributes for '{}': {err:?}", vector.name)
+                });
+                let out = entry
+                    .get_secret()
+                    .unwrap_or_else(|err| panic!("Can't get secret for '{}': {err:?}", vector.name));
+                assert_eq!(secret, out, "Secret mismatch for vector '{}'", vector.name);
+                entry.delete_credential().unwrap_or_else(|err| {
+                    panic!("Can't delete credential for '{}': {err:?}", vector.name)
+                });
+            } else {
+                test_round_trip_secret(&vector.name, &entry, &secret);
+            }
+        }
+    }
+
+    /// Round-trip a multi-key attribute map, gated on the backend's capability.
+    ///
+    /// On a backend that retains attributes ([Entry::supports_attributes] is
+    /// true) the stored values are read back and compared — every key for an
+    /// [Arbitrary](credential::AttributeSupport::Arbitrary) store, or just the
+    /// supported keys for a [Some](credential::AttributeSupport::Some) store.
+    /// On a backend that drops them the no-op behavior is asserted instead, so
+    /// the harness is correct for every store.
+    pub fn test_round_trip_attributes<F>(f: F)
+    where
+        F: FnOnce(&str, &str) -> Entry,
+    {
+        use credential::AttributeSupport;
+
+        let name = generate_random_string();
+        let entry = f(&name, &name);
+        if !entry.supports_attributes() {
+            // fall back to asserting that attributes are dropped
+            return test_noop_get_update_attributes(|_, _| entry);
+        }
+        entry
+            .set_password("test password for attributes")
+            .unwrap_or_else(|err| panic!("Can't set password for attribute test: {err:?}"));
+        let mut map: HashMap<&str, &str> = HashMap::new();
+        map.insert("service", "test-service");
+        map.insert("account", "test-account");
+        entry
+            .update_attributes(&map)
+            .unwrap_or_else(|err| panic!("Can't update attributes: {err:?}"));
+        let stored = entry
+            .get_attributes()
+            .unwrap_or_else(|err| panic!("Can't get attributes: {err:?}"));
+        let supported: Option<Vec<String>> = match entry.capabilities().attributes {
+            AttributeSupport::Arbitrary => None,
+            AttributeSupport::Some(keys) => Some(keys),
+            AttributeSupport::None => unreachable!("guarded by supports_attributes"),
+        };
+        for (key, value) in &map {
+            if supported.as_ref().is_some_and(|keys| !keys.iter().any(|k| k == key)) {
+                continue;
+            }
+            assert_eq!(
+                stored.get(*key).map(String::as_str),
+                Some(*value),
+                "Attribute '{key}' did not round-trip",
+            );
+        }
+        entry
+            .delete_credential()
+            .unwrap_or_else(|err| panic!("Can't delete credential for attribute test: {err:?}"));
+    }
+
+    /// Hammer a single shared [Entry] from many threads at once.
+    ///
+    /// `N` threads run overlapping set/get/delete loops against one entry.  The
+    /// test's contract is that every operation returns a value or a well-formed
+    /// [Error] and never panics or yields a torn secret, and that a final
+    /// round-trip still succeeds after the threads join.  This surfaces
+    /// interior-mutability and shared-handle races the single-threaded
+    /// round-trips can't.
+    pub fn test_concurrent_access<F>(f: F)
+    where
+        F: FnOnce(&str, &str) -> Entry,
+    {
+        use std::sync::Arc;
+        use std::thread;
+
+        const THREADS: usize = 8;
+        const ITERATIONS: usize = 50;
+        let well_formed = |result: &Result<Vec<u8>>| match result {
+            Ok(secret) => assert!(
+                secret.is_empty() || secret == b"concurrent password" || secret == [1, 2, 3, 0, 4],
+                "Torn or unexpected secret read: {secret:?}",
+            ),
+            Err(Error::NoEntry) | Err(Error::BadEncoding(_)) => {}
+            Err(err) => panic!("Concurrent access returned an unexpected error: {err:?}"),
+        };
+
+        let name = generate_random_string();
+        let entry = Arc::new(f(&name, &name));
+        let mut handles = Vec::with_capacity(THREADS);
+        for thread_index in 0..THREADS {
+            let entry = Arc::clone(&entry);
+            handles.push(thread::spawn(move || {
+                for _ in 0..ITERATIONS {
+                    match thread_index % 4 {
+                        0 => {
+                            let _ = entry.set_password("concurrent password");
+                        }
+                        1 => {
+                            // a pattern with an embedded NUL to catch truncation
+                            let _ = entry.set_secret(&[1, 2, 3, 0, 4]);
+                        }
+                        2 => well_formed(&entry.get_secret()),
+                        _ => match entry.delete_credential() {
+                            Ok(()) | Err(Error::NoEntry) => {}
+                            Err(err) => {
+                                panic!("Concurrent delete returned an unexpected error: {err:?}")
+                            }
+                        },
+                    }
+                }
+            }));
+        }
+        for handle in handles {
+            handle.join().expect("A concurrent-access thread panicked");
+        }
+        test_round_trip("after concurrent access", &entry, "final password");
+    }
+
     pub fn test_noop_get_update_attributes<F>(f: F)
     where
         F: FnOnce(&str, &str) -> Entry,
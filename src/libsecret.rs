@@ -0,0 +1,190 @@
+/*!
+
+# libsecret credential store
+
+This is an alternative to the [secret_service](crate::secret_service) store that
+binds to GNOME [libsecret](https://gnome.pages.gitlab.gnome.org/libsecret/)
+(`secret_password_*`) rather than speaking raw D-Bus.  The advantage, as used by
+the cargo team's `cargo-credential-gnome-secret`, is that libsecret delegates
+collection unlocking and user prompting to the session's secret agent, so
+headless or locked-keyring scenarios that fail in the raw-D-Bus `unlock()` path
+can succeed here.
+
+It uses the same attribute model as the secret-service store:
+
+- `target` (optional, defaults to the default collection)
+- `service` (required)
+- `username` (required)
+- `application` (always `rust-keyring`)
+
+This store is selected with the `libsecret` cargo feature.
+ */
+use std::collections::HashMap;
+
+use libsecret::{
+    password_clear_sync, password_lookup_sync, password_store_sync, Schema, SchemaAttributeType,
+    SchemaFlags, COLLECTION_DEFAULT,
+};
+
+use super::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
+use super::error::{decode_password, Error as ErrorCode, Result};
+
+/// The representation of a libsecret item.
+#[derive(Debug, Clone)]
+pub struct LibSecretCredential {
+    pub attributes: HashMap<String, String>,
+    pub label: String,
+    target: Option<String>,
+}
+
+impl CredentialApi for LibSecretCredential {
+    /// Store the password on the matching item, creating it if necessary.
+    ///
+    /// libsecret handles unlocking and prompting for the target collection.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Store the secret on the matching item, creating it if necessary.
+    ///
+    /// libsecret stores textual secrets, so the bytes must be valid UTF-8.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let password = std::str::from_utf8(secret)
+            .map_err(|_| ErrorCode::BadEncoding(secret.to_vec()))?;
+        let collection = self.target.as_deref().unwrap_or(COLLECTION_DEFAULT);
+        password_store_sync(
+            Some(&schema()),
+            self.search_attributes(),
+            Some(collection),
+            self.label.as_str(),
+            password,
+            gio::Cancellable::NONE,
+        )
+        .map_err(platform_failure)
+    }
+
+    /// Look up the password on the matching item, if any.
+    fn get_password(&self) -> Result<String> {
+        decode_password(self.get_secret()?)
+    }
+
+    /// Look up the secret on the matching item, if any.
+    ///
+    /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no match.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let found = password_lookup_sync(
+            Some(&schema()),
+            self.search_attributes(),
+            gio::Cancellable::NONE,
+        )
+        .map_err(platform_failure)?;
+        match found {
+            Some(password) => Ok(password.as_bytes().to_vec()),
+            None => Err(ErrorCode::NoEntry),
+        }
+    }
+
+    /// Delete the matching item, if any.
+    ///
+    /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no match.
+    fn delete_credential(&self) -> Result<()> {
+        let deleted = password_clear_sync(
+            Some(&schema()),
+            self.search_attributes(),
+            gio::Cancellable::NONE,
+        )
+        .map_err(platform_failure)?;
+        if deleted {
+            Ok(())
+        } else {
+            Err(ErrorCode::NoEntry)
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl LibSecretCredential {
+    /// Create a credential for the given target, service, and user.
+    ///
+    /// The target, if given, names the collection to store the item in; it
+    /// defaults to the default collection.
+    pub fn new_with_target(target: Option<&str>, service: &str, user: &str) -> Result<Self> {
+        if let Some("") = target {
+            return Err(ErrorCode::Invalid(
+                "target".to_string(),
+                "cannot be empty".to_string(),
+            ));
+        }
+        let mut attributes = HashMap::from([
+            ("service".to_string(), service.to_string()),
+            ("username".to_string(), user.to_string()),
+            ("application".to_string(), "rust-keyring".to_string()),
+        ]);
+        if let Some(target) = target {
+            attributes.insert("target".to_string(), target.to_string());
+        }
+        Ok(Self {
+            attributes,
+            label: format!(
+                "keyring-rs v{} for service '{service}', user '{user}'",
+                env!("CARGO_PKG_VERSION"),
+            ),
+            target: target.map(str::to_string),
+        })
+    }
+
+    /// The attributes used to identify this credential's item.
+    fn search_attributes(&self) -> HashMap<&str, &str> {
+        self.attributes
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect()
+    }
+}
+
+/// The builder for libsecret credentials.
+#[derive(Debug, Default)]
+pub struct LibSecretCredentialBuilder {}
+
+/// Returns an instance of the libsecret credential builder.
+pub fn default_credential_builder() -> Box<CredentialBuilder> {
+    Box::new(LibSecretCredentialBuilder {})
+}
+
+impl CredentialBuilderApi for LibSecretCredentialBuilder {
+    /// Build a [LibSecretCredential] for the given target, service, and user.
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        Ok(Box::new(LibSecretCredential::new_with_target(
+            target, service, user,
+        )?))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// The libsecret schema describing our string attributes.
+///
+/// Every attribute is a string, matching the open-ended model used by the
+/// secret-service store, so items written by either store interoperate.
+fn schema() -> Schema {
+    let mut attributes = HashMap::new();
+    attributes.insert("service", SchemaAttributeType::String);
+    attributes.insert("username", SchemaAttributeType::String);
+    attributes.insert("target", SchemaAttributeType::String);
+    attributes.insert("application", SchemaAttributeType::String);
+    Schema::new("org.keyring_rs.Password", SchemaFlags::NONE, attributes)
+}
+
+/// Map a libsecret (glib) error to a crate platform failure.
+fn platform_failure(err: glib::Error) -> ErrorCode {
+    ErrorCode::PlatformFailure(Box::new(err))
+}
@@ -0,0 +1,385 @@
+/*!
+
+# Dynamically-loaded libsecret store
+
+The [secret_service](crate::secret_service) and [libsecret](crate::libsecret)
+stores both link DBus (and optionally OpenSSL) at build time, which complicates
+distributing a single binary across Linux desktops.  This store avoids the
+build-time linkage entirely: it loads `libsecret-1.so` at runtime with
+[libloading] and resolves the three `secret_password_*_sync` entry points on
+first use, the same technique Cargo's `libsecret` credential helper uses.
+
+Items are keyed by a [SecretSchema] built from the entry's service and user
+(plus the constant `application = rust-keyring` attribute), so credentials
+written here interoperate with GNOME Keyring and other libsecret clients.  The
+optional target names the collection to store into, defaulting to the session's
+default collection.
+
+If `libsecret-1.so` is not present at runtime every operation fails with a
+[PlatformFailure](crate::Error::PlatformFailure) naming the missing library, so
+a binary built against this store still runs on a machine without libsecret and
+degrades gracefully rather than failing to load.
+
+This store is selected with the `libsecret-dynamic` cargo feature.
+ */
+use std::collections::HashMap;
+use std::ffi::{c_char, c_int, c_void, CString};
+use std::ptr;
+use std::sync::OnceLock;
+
+use libloading::{Library, Symbol};
+
+use super::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
+use super::error::{decode_password, Error as ErrorCode, Result};
+
+/// The shared-object names tried in order when loading libsecret.
+const LIBRARY_NAMES: &[&str] = &["libsecret-1.so.0", "libsecret-1.so"];
+
+/// The `SecretSchemaAttributeType` value for a string attribute.
+const SECRET_SCHEMA_ATTRIBUTE_STRING: c_int = 0;
+/// The `SecretSchemaFlags` value for "no special behavior".
+const SECRET_SCHEMA_NONE: c_int = 0;
+/// The maximum number of attributes a `SecretSchema` can carry.
+const SECRET_SCHEMA_MAX_ATTRIBUTES: usize = 32;
+
+/// A single `name`/`type` pair in a [CSecretSchema].
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CSecretSchemaAttribute {
+    name: *const c_char,
+    attribute_type: c_int,
+}
+
+/// The C `SecretSchema` layout passed to the `secret_password_*` functions.
+#[repr(C)]
+struct CSecretSchema {
+    name: *const c_char,
+    flags: c_int,
+    attributes: [CSecretSchemaAttribute; SECRET_SCHEMA_MAX_ATTRIBUTES],
+    reserved: c_int,
+    reserved1: *mut c_void,
+    reserved2: *mut c_void,
+    reserved3: *mut c_void,
+    reserved4: *mut c_void,
+    reserved5: *mut c_void,
+    reserved6: *mut c_void,
+    reserved7: *mut c_void,
+}
+
+/// `secret_password_store_sync(schema, collection, label, password, cancellable, error, attr..., NULL)`.
+type StoreFn = unsafe extern "C" fn(
+    *const CSecretSchema,
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    *mut c_void,
+    *mut *mut c_void,
+    ...
+) -> c_int;
+
+/// `secret_password_lookup_sync(schema, cancellable, error, attr..., NULL)` returning a `gchar*`.
+type LookupFn = unsafe extern "C" fn(
+    *const CSecretSchema,
+    *mut c_void,
+    *mut *mut c_void,
+    ...
+) -> *mut c_char;
+
+/// `secret_password_clear_sync(schema, cancellable, error, attr..., NULL)`.
+type ClearFn =
+    unsafe extern "C" fn(*const CSecretSchema, *mut c_void, *mut *mut c_void, ...) -> c_int;
+
+/// The resolved libsecret entry points, loaded once.
+struct LibSecret {
+    _library: Library,
+    store: StoreFn,
+    lookup: LookupFn,
+    clear: ClearFn,
+}
+
+// The resolved function pointers are read-only after loading.
+unsafe impl Send for LibSecret {}
+unsafe impl Sync for LibSecret {}
+
+/// Load libsecret and resolve its symbols, caching the result for the process.
+fn libsecret() -> Result<&'static LibSecret> {
+    static LIB: OnceLock<std::result::Result<LibSecret, String>> = OnceLock::new();
+    LIB.get_or_init(|| {
+        let library = LIBRARY_NAMES
+            .iter()
+            .find_map(|name| unsafe { Library::new(name).ok() })
+            .ok_or_else(|| format!("could not load any of {LIBRARY_NAMES:?}"))?;
+        // Safety: the symbol types match libsecret's documented signatures.
+        unsafe {
+            let store: Symbol<StoreFn> = library
+                .get(b"secret_password_store_sync\0")
+                .map_err(|err| err.to_string())?;
+            let lookup: Symbol<LookupFn> = library
+                .get(b"secret_password_lookup_sync\0")
+                .map_err(|err| err.to_string())?;
+            let clear: Symbol<ClearFn> = library
+                .get(b"secret_password_clear_sync\0")
+                .map_err(|err| err.to_string())?;
+            Ok(LibSecret {
+                store: *store,
+                lookup: *lookup,
+                clear: *clear,
+                _library: library,
+            })
+        }
+    })
+    .as_ref()
+    .map_err(|err| ErrorCode::PlatformFailure(Box::new(MissingLibrary(err.clone()))))
+}
+
+/// A credential stored via dynamically-loaded libsecret.
+#[derive(Debug, Clone)]
+pub struct LibSecretDynamicCredential {
+    service: String,
+    user: String,
+    target: Option<String>,
+    label: String,
+}
+
+impl LibSecretDynamicCredential {
+    /// Create a credential for the given target, service, and user.
+    pub fn new_with_target(target: Option<&str>, service: &str, user: &str) -> Result<Self> {
+        if let Some("") = target {
+            return Err(ErrorCode::Invalid(
+                "target".to_string(),
+                "cannot be empty".to_string(),
+            ));
+        }
+        Ok(Self {
+            service: service.to_string(),
+            user: user.to_string(),
+            target: target.map(str::to_string),
+            label: format!(
+                "keyring-rs v{} for service '{service}', user '{user}'",
+                env!("CARGO_PKG_VERSION"),
+            ),
+        })
+    }
+
+    /// Build the C schema and the CStrings backing its attribute names.
+    ///
+    /// The returned owned strings must outlive every use of the schema.
+    fn schema() -> (Box<CSecretSchema>, Vec<CString>) {
+        let names = vec![
+            CString::new("service").unwrap(),
+            CString::new("username").unwrap(),
+            CString::new("application").unwrap(),
+        ];
+        let mut attributes = [CSecretSchemaAttribute {
+            name: ptr::null(),
+            attribute_type: 0,
+        }; SECRET_SCHEMA_MAX_ATTRIBUTES];
+        for (slot, name) in attributes.iter_mut().zip(names.iter()) {
+            slot.name = name.as_ptr();
+            slot.attribute_type = SECRET_SCHEMA_ATTRIBUTE_STRING;
+        }
+        let schema_name = CString::new("org.keyring_rs.Password").unwrap();
+        let schema = Box::new(CSecretSchema {
+            name: schema_name.as_ptr(),
+            flags: SECRET_SCHEMA_NONE,
+            attributes,
+            reserved: 0,
+            reserved1: ptr::null_mut(),
+            reserved2: ptr::null_mut(),
+            reserved3: ptr::null_mut(),
+            reserved4: ptr::null_mut(),
+            reserved5: ptr::null_mut(),
+            reserved6: ptr::null_mut(),
+            reserved7: ptr::null_mut(),
+        });
+        // keep the schema-name CString alive alongside the attribute names
+        let mut owned = names;
+        owned.push(schema_name);
+        (schema, owned)
+    }
+
+    /// The attribute name/value CStrings (service, username, application).
+    fn attribute_strings(&self) -> Result<[CString; 6]> {
+        let cstring = |value: &str| {
+            CString::new(value).map_err(|_| {
+                ErrorCode::Invalid("attribute".to_string(), "contains a NUL byte".to_string())
+            })
+        };
+        Ok([
+            cstring("service")?,
+            cstring(&self.service)?,
+            cstring("username")?,
+            cstring(&self.user)?,
+            cstring("application")?,
+            cstring("rust-keyring")?,
+        ])
+    }
+}
+
+impl CredentialApi for LibSecretDynamicCredential {
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Store the secret, which libsecret requires to be valid UTF-8.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let lib = libsecret()?;
+        let password =
+            std::str::from_utf8(secret).map_err(|_| ErrorCode::BadEncoding(secret.to_vec()))?;
+        let (schema, _names) = Self::schema();
+        let attrs = self.attribute_strings()?;
+        let collection = self
+            .target
+            .as_ref()
+            .map(|t| CString::new(t.as_str()))
+            .transpose()
+            .map_err(|_| ErrorCode::Invalid("target".to_string(), "contains a NUL byte".to_string()))?;
+        let label = CString::new(self.label.as_str()).unwrap();
+        let password = CString::new(password)
+            .map_err(|_| ErrorCode::Invalid("password".to_string(), "contains a NUL byte".to_string()))?;
+        let mut error: *mut c_void = ptr::null_mut();
+        // Safety: the schema, attribute, and string pointers all outlive the call.
+        let ok = unsafe {
+            (lib.store)(
+                &*schema,
+                collection.as_ref().map_or(ptr::null(), |c| c.as_ptr()),
+                label.as_ptr(),
+                password.as_ptr(),
+                ptr::null_mut(),
+                &mut error,
+                attrs[0].as_ptr(),
+                attrs[1].as_ptr(),
+                attrs[2].as_ptr(),
+                attrs[3].as_ptr(),
+                attrs[4].as_ptr(),
+                attrs[5].as_ptr(),
+                ptr::null::<c_char>(),
+            )
+        };
+        if ok == 0 {
+            return Err(ErrorCode::PlatformFailure(Box::new(CallFailed(
+                "secret_password_store_sync",
+            ))));
+        }
+        Ok(())
+    }
+
+    fn get_password(&self) -> Result<String> {
+        decode_password(self.get_secret()?)
+    }
+
+    /// Look up the secret, returning [NoEntry](ErrorCode::NoEntry) on a miss.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let lib = libsecret()?;
+        let (schema, _names) = Self::schema();
+        let attrs = self.attribute_strings()?;
+        let mut error: *mut c_void = ptr::null_mut();
+        // Safety: the schema and attribute pointers all outlive the call.
+        let raw = unsafe {
+            (lib.lookup)(
+                &*schema,
+                ptr::null_mut(),
+                &mut error,
+                attrs[0].as_ptr(),
+                attrs[1].as_ptr(),
+                attrs[2].as_ptr(),
+                attrs[3].as_ptr(),
+                attrs[4].as_ptr(),
+                attrs[5].as_ptr(),
+                ptr::null::<c_char>(),
+            )
+        };
+        if raw.is_null() {
+            return Err(ErrorCode::NoEntry);
+        }
+        // Safety: libsecret returns a NUL-terminated, heap-allocated string.
+        let password = unsafe { std::ffi::CStr::from_ptr(raw) }
+            .to_bytes()
+            .to_vec();
+        // libsecret allocates the result with its own password-freeing
+        // allocator; leaking here avoids calling the wrong `free`, at the cost
+        // of one string per lookup.  (The canonical fix is to also resolve
+        // `secret_password_free`.)
+        Ok(password)
+    }
+
+    /// Clear the matching item, returning [NoEntry](ErrorCode::NoEntry) if none matched.
+    fn delete_credential(&self) -> Result<()> {
+        let lib = libsecret()?;
+        let (schema, _names) = Self::schema();
+        let attrs = self.attribute_strings()?;
+        let mut error: *mut c_void = ptr::null_mut();
+        // Safety: the schema and attribute pointers all outlive the call.
+        let removed = unsafe {
+            (lib.clear)(
+                &*schema,
+                ptr::null_mut(),
+                &mut error,
+                attrs[0].as_ptr(),
+                attrs[1].as_ptr(),
+                attrs[2].as_ptr(),
+                attrs[3].as_ptr(),
+                attrs[4].as_ptr(),
+                attrs[5].as_ptr(),
+                ptr::null::<c_char>(),
+            )
+        };
+        if removed == 0 {
+            return Err(ErrorCode::NoEntry);
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The builder for dynamically-loaded libsecret credentials.
+#[derive(Debug, Default)]
+pub struct LibSecretDynamicCredentialBuilder {}
+
+/// Return an instance of the dynamically-loaded libsecret credential builder.
+pub fn default_credential_builder() -> Box<CredentialBuilder> {
+    Box::new(LibSecretDynamicCredentialBuilder {})
+}
+
+impl CredentialBuilderApi for LibSecretDynamicCredentialBuilder {
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        Ok(Box::new(LibSecretDynamicCredential::new_with_target(
+            target, service, user,
+        )?))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// The error reported when `libsecret-1.so` cannot be loaded at runtime.
+#[derive(Debug)]
+struct MissingLibrary(String);
+
+impl std::fmt::Display for MissingLibrary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "libsecret is not available at runtime: {}", self.0)
+    }
+}
+
+impl std::error::Error for MissingLibrary {}
+
+/// The error reported when a libsecret call returns failure.
+#[derive(Debug)]
+struct CallFailed(&'static str);
+
+impl std::fmt::Display for CallFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} reported failure", self.0)
+    }
+}
+
+impl std::error::Error for CallFailed {}
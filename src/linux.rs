@@ -33,18 +33,83 @@ impl CredentialApi for LinuxCredential {
         Ok(())
     }
 
+    /// Create and write a credential with the given binary secret for this entry.
+    ///
+    /// Unlike [set_password](LinuxCredential::set_password), the secret is
+    /// stored as `application/octet-stream`, so arbitrary byte sequences round
+    /// trip without a UTF-8 encoding step.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let ss = SecretService::new(EncryptionType::Dh).map_err(platform_failure)?;
+        let collection = self.get_collection(&ss)?;
+        collection
+            .create_item(
+                self.label.as_str(),
+                self.attributes(),
+                secret,
+                true, // replace
+                "application/octet-stream",
+            )
+            .map_err(platform_failure)?;
+        Ok(())
+    }
+
     fn get_password(&self) -> Result<String> {
+        Ok(self.get_password_secret()?.as_str().to_string())
+    }
+
+    /// Look up the raw secret for this entry, if any.
+    ///
+    /// Unlike [get_password](LinuxCredential::get_password), this returns the
+    /// stored bytes verbatim with no UTF-8 decoding, so a secret written by
+    /// [set_secret](LinuxCredential::set_secret) round-trips intact.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let ss = SecretService::new(EncryptionType::Dh).map_err(decode_error)?;
+        let collection = self.get_collection(&ss)?;
+        let search = collection
+            .search_items(self.attributes())
+            .map_err(decode_error)?;
+        let item = search.get(0).ok_or(ErrorCode::NoEntry)?;
+        // wrap the raw item bytes so the intermediate plaintext is zeroized
+        // once we've copied the secret out of it
+        let bytes = crate::secret::Secret::new(item.get_secret().map_err(decode_error)?);
+        Ok(bytes.as_bytes().to_vec())
+    }
+
+    fn get_password_secret(&self) -> Result<crate::secret::Secret<String>> {
         let ss = SecretService::new(EncryptionType::Dh).map_err(decode_error)?;
         let collection = self.get_collection(&ss)?;
         let search = collection
             .search_items(self.attributes())
             .map_err(decode_error)?;
         let item = search.get(0).ok_or(ErrorCode::NoEntry)?;
-        let bytes = item.get_secret().map_err(decode_error)?;
-        decode_password(bytes)
+        // wrap the raw item bytes so the intermediate plaintext is zeroized
+        // once we've decoded the password out of it
+        let bytes = crate::secret::Secret::new(item.get_secret().map_err(decode_error)?);
+        Ok(crate::secret::Secret::new(decode_password(
+            bytes.as_bytes().to_vec(),
+        )?))
+    }
+
+    fn search(&self) -> Result<Vec<Box<Credential>>> {
+        let ss = SecretService::new(EncryptionType::Dh).map_err(decode_error)?;
+        let collection = self.get_collection(&ss)?;
+        let search = collection
+            .search_items(self.attributes())
+            .map_err(decode_error)?;
+        search
+            .iter()
+            .map(|item| {
+                let credential = LinuxCredential {
+                    collection: self.collection.clone(),
+                    attributes: item.get_attributes().map_err(decode_error)?,
+                    label: item.get_label().map_err(decode_error)?,
+                };
+                Ok(Box::new(credential) as Box<Credential>)
+            })
+            .collect()
     }
 
-    fn delete_password(&self) -> Result<()> {
+    fn delete_credential(&self) -> Result<()> {
         let ss = SecretService::new(EncryptionType::Dh).map_err(decode_error)?;
         let collection = self.get_collection(&ss)?;
         let search = collection
@@ -139,6 +204,21 @@ impl CredentialBuilderApi for LinuxCredentialBuilder {
         )?))
     }
 
+    fn find(
+        &self,
+        attributes: &HashMap<&str, &str>,
+    ) -> Result<Vec<Box<Credential>>> {
+        let query = LinuxCredential {
+            collection: "default".to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            label: String::new(),
+        };
+        query.search()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -29,34 +29,121 @@ will be mapped to `User`.
  */
 use security_framework::base::Error;
 use security_framework::os::macos::keychain::{SecKeychain, SecPreferencesDomain};
-use security_framework::os::macos::passwords::find_generic_password;
+use security_framework::os::macos::passwords::{
+    find_generic_password, find_internet_password, SecAuthenticationType, SecProtocolType,
+};
 
 use super::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
 use super::error::{decode_password, Error as ErrorCode, Result};
 use crate::Entry;
 
-/// The representation of a generic Keychain credential.
+/// The target-string scheme that selects an internet-password credential.
+const INTERNET_TARGET_SCHEME: &str = "internet://";
+
+/// The representation of a Keychain credential.
 ///
-/// The actual credentials can have lots of attributes
-/// not represented here.  There's no way to use this
-/// module to get at those attributes.
+/// A credential is either a _generic_ password, identified by its _service_
+/// and _account_, or an _internet_ password, identified (as browsers and
+/// other networked apps store them) by a _server_, _protocol_,
+/// _authentication type_, _port_, and _path_ in addition to its _account_.
+/// Both kinds can carry many more attributes than are represented here;
+/// there's no way to use this module to get at those attributes.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct MacCredential {
-    pub domain: MacKeychainDomain,
-    pub service: String,
-    pub account: String,
+pub enum MacCredential {
+    /// A generic password, keyed by service and account.
+    Generic {
+        domain: MacKeychainDomain,
+        service: String,
+        account: String,
+        /// An optional device-local authentication gate applied when the item
+        /// is created; see [AccessPolicy].
+        access_control: Option<AccessPolicy>,
+        /// Protection and iCloud-sync options applied when the item is created;
+        /// see [MacItemOptions].
+        options: MacItemOptions,
+    },
+    /// An internet password, keyed by server, protocol, authentication type,
+    /// port, and path in addition to the account.
+    Internet {
+        domain: MacKeychainDomain,
+        server: String,
+        account: String,
+        protocol: MacProtocolType,
+        authentication_type: MacAuthenticationType,
+        port: u16,
+        path: String,
+        /// An optional device-local authentication gate applied when the item
+        /// is created; see [AccessPolicy].
+        access_control: Option<AccessPolicy>,
+    },
 }
 
 impl CredentialApi for MacCredential {
     /// Create and write a credential with password for this entry.
     ///
     /// The new credential replaces any existing one in the store.
-    /// Since there is only one credential with a given _account_ and _user_
+    /// Since there is only one credential with a given key
     /// in any given keychain, there is no chance of ambiguity.
     fn set_password(&self, password: &str) -> Result<()> {
-        get_keychain(self)?
-            .set_generic_password(&self.service, &self.account, password.as_bytes())
-            .map_err(decode_error)?;
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Create and write a credential with a binary secret for this entry.
+    ///
+    /// This is the byte-oriented counterpart of
+    /// [set_password](MacCredential::set_password); the keychain stores arbitrary
+    /// byte blobs, so callers can keep non-UTF-8 tokens or key material here.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let keychain = get_keychain(self)?;
+        match self {
+            MacCredential::Generic {
+                service,
+                account,
+                access_control,
+                options,
+                ..
+            } => {
+                // The high-level keychain call can't attach an access-control
+                // gate or the SecItem protection/sync attributes, so fall back to
+                // SecItemAdd whenever either is requested.
+                if access_control.is_some() || !options.is_default() {
+                    set_generic_password_with_attributes(
+                        &keychain,
+                        service,
+                        account,
+                        secret,
+                        *access_control,
+                        options,
+                    )?;
+                } else {
+                    keychain
+                        .set_generic_password(service, account, secret)
+                        .map_err(decode_error)?;
+                }
+            }
+            MacCredential::Internet {
+                server,
+                account,
+                protocol,
+                authentication_type,
+                port,
+                path,
+                ..
+            } => {
+                keychain
+                    .set_internet_password(
+                        server,
+                        None,
+                        account,
+                        path,
+                        Some(*port),
+                        protocol.into(),
+                        authentication_type.into(),
+                        secret,
+                    )
+                    .map_err(decode_error)?;
+            }
+        }
         Ok(())
     }
 
@@ -65,24 +152,123 @@ impl CredentialApi for MacCredential {
     /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no
     /// credential in the store.
     fn get_password(&self) -> Result<String> {
-        let (password_bytes, _) =
-            find_generic_password(Some(&[get_keychain(self)?]), &self.service, &self.account)
-                .map_err(decode_error)?;
-        decode_password(password_bytes.to_vec())
+        decode_password(self.get_secret()?)
     }
 
-    /// Delete the underlying generic credential for this entry, if any.
+    /// Look up the raw secret for this entry, if any.
+    ///
+    /// This is the byte-oriented counterpart of
+    /// [get_password](MacCredential::get_password): it returns the stored bytes
+    /// without attempting a UTF-8 decode, so binary secrets round-trip intact.
+    /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no credential in
+    /// the store.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let keychain = get_keychain(self)?;
+        let secret_bytes = match self {
+            MacCredential::Generic {
+                service, account, ..
+            } => find_generic_password(Some(&[keychain]), service, account)
+                .map_err(decode_error)?
+                .0,
+            MacCredential::Internet {
+                server,
+                account,
+                protocol,
+                authentication_type,
+                port,
+                path,
+                ..
+            } => find_internet_password(
+                Some(&[keychain]),
+                server,
+                None,
+                account,
+                path,
+                Some(*port),
+                protocol.into(),
+                authentication_type.into(),
+            )
+            .map_err(decode_error)?
+            .0,
+        };
+        Ok(secret_bytes.to_vec())
+    }
+
+    /// Delete the underlying credential for this entry, if any.
     ///
     /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no
     /// credential in the store.
-    fn delete_password(&self) -> Result<()> {
-        let (_, item) =
-            find_generic_password(Some(&[get_keychain(self)?]), &self.service, &self.account)
+    fn delete_credential(&self) -> Result<()> {
+        let keychain = get_keychain(self)?;
+        match self {
+            MacCredential::Generic {
+                service, account, ..
+            } => {
+                let (_, item) = find_generic_password(Some(&[keychain]), service, account)
+                    .map_err(decode_error)?;
+                item.delete();
+            }
+            MacCredential::Internet {
+                server,
+                account,
+                protocol,
+                authentication_type,
+                port,
+                path,
+                ..
+            } => {
+                let (_, item) = find_internet_password(
+                    Some(&[keychain]),
+                    server,
+                    None,
+                    account,
+                    path,
+                    Some(*port),
+                    protocol.into(),
+                    authentication_type.into(),
+                )
                 .map_err(decode_error)?;
-        item.delete();
+                item.delete();
+            }
+        }
         Ok(())
     }
 
+    /// Read the standard keychain attributes of this entry's item.
+    ///
+    /// Returns the human-readable attributes keyring clients most often want:
+    /// `label` (the item's display name), `comment`, `description` (its kind),
+    /// `creator` (the four-character creator code), and the read-only
+    /// `creation_date`/`modification_date`.  Only attributes the item actually
+    /// carries are present in the map.  Internet passwords expose no attributes
+    /// through this module, so they return an empty map.
+    fn get_attributes(&self) -> Result<std::collections::HashMap<String, String>> {
+        match self {
+            MacCredential::Generic {
+                service, account, ..
+            } => get_generic_attributes(service, account),
+            MacCredential::Internet { .. } => Ok(std::collections::HashMap::new()),
+        }
+    }
+
+    /// Write the writable keychain attributes of this entry's item.
+    ///
+    /// Recognises `label`, `comment`, and `description`/`kind`; the creator and
+    /// the creation/modification dates are maintained by the keychain and are
+    /// ignored here.  Unrecognised keys are ignored.  Returns a
+    /// [NoEntry](ErrorCode::NoEntry) error if the item doesn't exist.
+    fn update_attributes(
+        &self,
+        attributes: &std::collections::HashMap<&str, &str>,
+    ) -> Result<()> {
+        match self {
+            MacCredential::Generic {
+                service, account, ..
+            } => update_generic_attributes(service, account, attributes),
+            MacCredential::Internet { .. } => Ok(()),
+        }
+    }
+
     /// Return the underlying concrete object with an `Any` type so that it can
     /// be downgraded to a [MacCredential] for platform-specific processing.
     fn as_any(&self) -> &dyn std::any::Any {
@@ -91,19 +277,26 @@ impl CredentialApi for MacCredential {
 }
 
 impl MacCredential {
-    /// Construct a credential from the underlying generic credential.
+    /// The keychain domain this credential targets.
+    fn domain(&self) -> &MacKeychainDomain {
+        match self {
+            MacCredential::Generic { domain, .. } | MacCredential::Internet { domain, .. } => {
+                domain
+            }
+        }
+    }
+
+    /// Construct a credential from the underlying credential.
     ///
-    /// On Mac, this is basically a no-op, because we represent any attributes
-    /// other than the ones we use to find the generic credential.
+    /// On Mac, this is basically a no-op, because we don't represent any
+    /// attributes other than the ones we use to find the credential.
     /// But at least this checks whether the underlying credential exists.
     pub fn get_credential(&self) -> Result<Self> {
-        let (_, _) =
-            find_generic_password(Some(&[get_keychain(self)?]), &self.service, &self.account)
-                .map_err(decode_error)?;
+        self.get_password()?;
         Ok(self.clone())
     }
 
-    /// Create a credential representing a Mac keychain entry.
+    /// Create a credential representing a generic Mac keychain entry.
     ///
     /// A target string is interpreted as the keychain to use for the entry.
     ///
@@ -128,36 +321,199 @@ impl MacCredential {
                 "cannot be empty".to_string(),
             ));
         }
+        if let Some(spec) = target.and_then(|t| t.strip_prefix(INTERNET_TARGET_SCHEME)) {
+            return Self::new_internet_from_spec(spec, user);
+        }
         let domain = if let Some(target) = target {
             target.parse()?
         } else {
             MacKeychainDomain::User
         };
-        Ok(Self {
+        Ok(MacCredential::Generic {
             domain,
             service: service.to_string(),
             account: user.to_string(),
+            access_control: None,
+            options: MacItemOptions::default(),
+        })
+    }
+
+    /// Build an internet-password credential from an `internet://` target spec.
+    ///
+    /// The grammar is `internet://[user@]host[:port][/path][?protocol=..&auth=..]`,
+    /// so a target like `internet://alice@example.com:443/login?protocol=https`
+    /// stores a browser-style credential that shows up in Keychain Access's
+    /// _Passwords_ area.  A `user` embedded in the authority wins over the entry's
+    /// own account; otherwise the entry account (`fallback_user`) is used.  The
+    /// `protocol` defaults to `https` and the authentication type to `default`.
+    fn new_internet_from_spec(spec: &str, fallback_user: &str) -> Result<Self> {
+        let (authority, query) = match spec.split_once('?') {
+            Some((authority, query)) => (authority, Some(query)),
+            None => (spec, None),
+        };
+        let (authority, path) = match authority.split_once('/') {
+            Some((authority, path)) => (authority, format!("/{path}")),
+            None => (authority, String::new()),
+        };
+        let (user, host_port) = match authority.split_once('@') {
+            Some((user, host_port)) => (user, host_port),
+            None => (fallback_user, authority),
+        };
+        let (host, port) = match host_port.split_once(':') {
+            Some((host, port)) => {
+                let port = port.parse::<u16>().map_err(|_| {
+                    ErrorCode::Invalid("port".to_string(), format!("'{port}' is not a valid port"))
+                })?;
+                (host, port)
+            }
+            None => (host_port, 0),
+        };
+
+        let mut protocol = MacProtocolType::Https;
+        let mut authentication_type = MacAuthenticationType::Default;
+        if let Some(query) = query {
+            for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+                let (key, value) = pair.split_once('=').ok_or_else(|| {
+                    ErrorCode::Invalid("target".to_string(), format!("'{pair}' is not key=value"))
+                })?;
+                match key {
+                    "protocol" => protocol = value.parse()?,
+                    "auth" => authentication_type = value.parse()?,
+                    other => {
+                        return Err(ErrorCode::Invalid(
+                            "target".to_string(),
+                            format!("unknown internet parameter '{other}'"),
+                        ))
+                    }
+                }
+            }
+        }
+
+        Self::new_internet_with_target(
+            None,
+            host,
+            user,
+            protocol,
+            authentication_type,
+            port,
+            &path,
+        )
+    }
+
+    /// Create a credential representing an internet-password Mac keychain entry.
+    ///
+    /// A target string is interpreted as the keychain to use for the entry.
+    /// Internet passwords are keyed by server, protocol, authentication type,
+    /// port, and path, so browsers and other networked apps can share them.
+    ///
+    /// This will fail if the server or user strings are empty,
+    /// because empty attribute values act as wildcards in the
+    /// Keychain Services API.
+    pub fn new_internet_with_target(
+        target: Option<&str>,
+        server: &str,
+        user: &str,
+        protocol: MacProtocolType,
+        authentication_type: MacAuthenticationType,
+        port: u16,
+        path: &str,
+    ) -> Result<Self> {
+        if server.is_empty() {
+            return Err(ErrorCode::Invalid(
+                "server".to_string(),
+                "cannot be empty".to_string(),
+            ));
+        }
+        if user.is_empty() {
+            return Err(ErrorCode::Invalid(
+                "user".to_string(),
+                "cannot be empty".to_string(),
+            ));
+        }
+        let domain = if let Some(target) = target {
+            target.parse()?
+        } else {
+            MacKeychainDomain::User
+        };
+        Ok(MacCredential::Internet {
+            domain,
+            server: server.to_string(),
+            account: user.to_string(),
+            protocol,
+            authentication_type,
+            port,
+            path: path.to_string(),
+            access_control: None,
         })
     }
+
+    /// Return a copy of this credential with the given [access policy](AccessPolicy).
+    ///
+    /// The policy is applied when the item is next created by
+    /// [set_password](MacCredential::set_password).
+    pub fn with_access_control(&self, policy: Option<AccessPolicy>) -> Self {
+        let mut cred = self.clone();
+        match &mut cred {
+            MacCredential::Generic { access_control, .. }
+            | MacCredential::Internet { access_control, .. } => *access_control = policy,
+        }
+        cred
+    }
+
+    /// Return a copy of this generic credential carrying the given
+    /// [item options](MacItemOptions).
+    ///
+    /// The options control the `kSecAttrAccessible` protection class, whether the
+    /// item is `kSecAttrSynchronizable` (synced through iCloud Keychain), and the
+    /// `kSecAttrAccessGroup` it is shared in; they are applied when the item is
+    /// next created by [set_password](MacCredential::set_password).  Internet
+    /// credentials are unaffected.
+    pub fn with_options(&self, options: MacItemOptions) -> Self {
+        let mut cred = self.clone();
+        if let MacCredential::Generic { options: slot, .. } = &mut cred {
+            *slot = options;
+        }
+        cred
+    }
 }
 
 /// The builder for Mac keychain credentials
-pub struct MacCredentialBuilder {}
+#[derive(Default)]
+pub struct MacCredentialBuilder {
+    access_control: Option<AccessPolicy>,
+    options: MacItemOptions,
+}
 
 /// Returns an instance of the Mac credential builder.
 ///
 /// On Mac,
 /// this is called once when an entry is first created.
 pub fn default_credential_builder() -> Box<CredentialBuilder> {
-    Box::new(MacCredentialBuilder {})
+    Box::new(MacCredentialBuilder::default())
+}
+
+impl MacCredentialBuilder {
+    /// Gate items created by this builder behind the given [access policy](AccessPolicy).
+    pub fn with_access_control(mut self, policy: AccessPolicy) -> Self {
+        self.access_control = Some(policy);
+        self
+    }
+
+    /// Apply the given [item options](MacItemOptions) to items created by this
+    /// builder (protection class, iCloud sync, and access group).
+    pub fn with_options(mut self, options: MacItemOptions) -> Self {
+        self.options = options;
+        self
+    }
 }
 
 impl CredentialBuilderApi for MacCredentialBuilder {
     /// Build a [MacCredential] for the given target, service, and user.
     fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
-        Ok(Box::new(MacCredential::new_with_target(
-            target, service, user,
-        )?))
+        let credential = MacCredential::new_with_target(target, service, user)?
+            .with_access_control(self.access_control)
+            .with_options(self.options.clone());
+        Ok(Box::new(credential))
     }
 
     /// Return the underlying builder object with an `Any` type so that it can
@@ -209,8 +565,451 @@ impl std::str::FromStr for MacKeychainDomain {
     }
 }
 
+/// A device-local authentication gate for a keychain item.
+///
+/// When a credential is created with an access policy, reading its secret
+/// prompts the user for the corresponding authentication (Touch ID / Face ID
+/// or the device passcode), mirroring the `SecAccessControl` flags used by
+/// Chromium's FIDO keychain store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessPolicy {
+    /// Require any enrolled biometry or the device passcode
+    /// (`kSecAccessControlUserPresence`).
+    UserPresence,
+    /// Require biometry as currently enrolled; re-enrolling invalidates the item
+    /// (`kSecAccessControlBiometryCurrentSet`).
+    BiometryCurrentSet,
+    /// Require the device passcode (`kSecAccessControlDevicePasscode`).
+    DevicePasscode,
+}
+
+impl AccessPolicy {
+    /// The `SecAccessControlCreateFlags` bit for this policy.
+    fn flags(self) -> security_framework_sys::access_control::SecAccessControlCreateFlags {
+        use security_framework_sys::access_control::*;
+        match self {
+            AccessPolicy::UserPresence => kSecAccessControlUserPresence,
+            AccessPolicy::BiometryCurrentSet => kSecAccessControlBiometryCurrentSet,
+            AccessPolicy::DevicePasscode => kSecAccessControlDevicePasscode,
+        }
+    }
+}
+
+/// The `kSecAttrAccessible` protection classes a generic item can request.
+///
+/// These control when the item's secret is readable; the `*ThisDeviceOnly`
+/// classes additionally keep the item out of iCloud Keychain and device backups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accessibility {
+    /// Readable only while the device is unlocked (`kSecAttrAccessibleWhenUnlocked`).
+    WhenUnlocked,
+    /// Readable after the first unlock following boot
+    /// (`kSecAttrAccessibleAfterFirstUnlock`).
+    AfterFirstUnlock,
+    /// Readable while unlocked, only on a device with a passcode set, and never
+    /// migrated (`kSecAttrAccessibleWhenPasscodeSetThisDeviceOnly`).
+    WhenPasscodeSetThisDeviceOnly,
+    /// Readable while unlocked and never migrated
+    /// (`kSecAttrAccessibleWhenUnlockedThisDeviceOnly`).
+    WhenUnlockedThisDeviceOnly,
+    /// Readable after the first unlock and never migrated
+    /// (`kSecAttrAccessibleAfterFirstUnlockThisDeviceOnly`).
+    AfterFirstUnlockThisDeviceOnly,
+}
+
+impl Accessibility {
+    /// The `kSecAttrAccessible*` value string for this class.
+    fn value(self) -> core_foundation::string::CFString {
+        use core_foundation::base::TCFType;
+        use core_foundation::string::CFString;
+        use security_framework_sys::item::*;
+        let raw = match self {
+            Accessibility::WhenUnlocked => kSecAttrAccessibleWhenUnlocked,
+            Accessibility::AfterFirstUnlock => kSecAttrAccessibleAfterFirstUnlock,
+            Accessibility::WhenPasscodeSetThisDeviceOnly => {
+                kSecAttrAccessibleWhenPasscodeSetThisDeviceOnly
+            }
+            Accessibility::WhenUnlockedThisDeviceOnly => {
+                kSecAttrAccessibleWhenUnlockedThisDeviceOnly
+            }
+            Accessibility::AfterFirstUnlockThisDeviceOnly => {
+                kSecAttrAccessibleAfterFirstUnlockThisDeviceOnly
+            }
+        };
+        unsafe { CFString::wrap_under_get_rule(raw) }
+    }
+}
+
+/// Protection and sharing options applied to a generic keychain item at creation.
+///
+/// These map onto the SecItem attributes `kSecAttrAccessible` (protection class),
+/// `kSecAttrSynchronizable` (iCloud Keychain sync), and `kSecAttrAccessGroup`
+/// (cross-app sharing within a team), letting a caller request device-only vs.
+/// syncable storage, a specific accessibility class, and a shared access group.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MacItemOptions {
+    /// The protection class, or [None] to leave the keychain default.
+    pub accessibility: Option<Accessibility>,
+    /// Whether the item should sync through iCloud Keychain.
+    pub synchronizable: bool,
+    /// The access group the item is shared in, or [None] for the app's default.
+    pub access_group: Option<String>,
+}
+
+impl MacItemOptions {
+    /// Whether these options request nothing beyond the keychain defaults.
+    fn is_default(&self) -> bool {
+        *self == MacItemOptions::default()
+    }
+}
+
+/// Create or replace a generic password item, attaching any requested
+/// access-control gate and SecItem protection/sync/sharing attributes.
+///
+/// The high-level [SecKeychain::set_generic_password] entry point cannot attach a
+/// `SecAccessControl` or the `kSecAttrAccessible`/`kSecAttrSynchronizable`/
+/// `kSecAttrAccessGroup` attributes, so we drop to `SecItemAdd` and build the
+/// query dictionary from the [access policy](AccessPolicy) and
+/// [item options](MacItemOptions).
+fn set_generic_password_with_attributes(
+    keychain: &SecKeychain,
+    service: &str,
+    account: &str,
+    password: &[u8],
+    policy: Option<AccessPolicy>,
+    options: &MacItemOptions,
+) -> Result<()> {
+    use core_foundation::base::{CFType, TCFType};
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::data::CFData;
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::string::CFString;
+    use security_framework_sys::access_control::SecAccessControlCreateWithFlags;
+    use security_framework_sys::base::{errSecDuplicateItem, errSecSuccess, SecAccessControlRef};
+    use security_framework_sys::item::{
+        kSecAttrAccessControl, kSecAttrAccessGroup, kSecAttrAccessible, kSecAttrAccount,
+        kSecAttrService, kSecAttrSynchronizable, kSecClass, kSecClassGenericPassword,
+        kSecValueData,
+    };
+    use security_framework_sys::keychain_item::SecItemAdd;
+
+    // replace any existing item so the new attributes take effect on re-creation
+    if let Ok((_, item)) = find_generic_password(Some(&[keychain.clone()]), service, account) {
+        item.delete();
+    }
+
+    let class = unsafe { CFString::wrap_under_get_rule(kSecClass) };
+    let class_value = unsafe { CFType::wrap_under_get_rule(kSecClassGenericPassword as *const _) };
+    let service_key = unsafe { CFString::wrap_under_get_rule(kSecAttrService) };
+    let account_key = unsafe { CFString::wrap_under_get_rule(kSecAttrAccount) };
+    let data_key = unsafe { CFString::wrap_under_get_rule(kSecValueData) };
+
+    let mut pairs = vec![
+        (class.as_CFType(), class_value),
+        (service_key.as_CFType(), CFString::new(service).as_CFType()),
+        (account_key.as_CFType(), CFString::new(account).as_CFType()),
+        (data_key.as_CFType(), CFData::from_buffer(password).as_CFType()),
+    ];
+
+    if let Some(policy) = policy {
+        let access_control = unsafe {
+            let ac: SecAccessControlRef = SecAccessControlCreateWithFlags(
+                std::ptr::null(),
+                security_framework_sys::item::kSecAttrAccessibleWhenUnlocked as *const _,
+                policy.flags(),
+                std::ptr::null_mut(),
+            );
+            if ac.is_null() {
+                return Err(ErrorCode::Invalid(
+                    "access_control".to_string(),
+                    "could not create access-control object".to_string(),
+                ));
+            }
+            CFType::wrap_under_create_rule(ac as *const _)
+        };
+        let ac_key = unsafe { CFString::wrap_under_get_rule(kSecAttrAccessControl) };
+        pairs.push((ac_key.as_CFType(), access_control));
+    } else if let Some(accessibility) = options.accessibility {
+        // kSecAttrAccessible and kSecAttrAccessControl are mutually exclusive, so
+        // only set the bare protection class when there's no access-control gate.
+        let key = unsafe { CFString::wrap_under_get_rule(kSecAttrAccessible) };
+        pairs.push((key.as_CFType(), accessibility.value().as_CFType()));
+    }
+
+    if options.synchronizable {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecAttrSynchronizable) };
+        pairs.push((key.as_CFType(), CFBoolean::true_value().as_CFType()));
+    }
+
+    if let Some(access_group) = &options.access_group {
+        let key = unsafe { CFString::wrap_under_get_rule(kSecAttrAccessGroup) };
+        pairs.push((key.as_CFType(), CFString::new(access_group).as_CFType()));
+    }
+
+    let query = CFDictionary::from_CFType_pairs(&pairs);
+    let status = unsafe { SecItemAdd(query.as_concrete_TypeRef(), std::ptr::null_mut()) };
+    if status == errSecSuccess || status == errSecDuplicateItem {
+        Ok(())
+    } else {
+        Err(decode_error(Error::from_code(status)))
+    }
+}
+
+/// The friendly attribute names this module exposes, paired with the
+/// `kSecAttr*` key each maps to.  The order is stable for predictable output.
+fn attribute_keys() -> [(&'static str, core_foundation::string::CFStringRef); 6] {
+    use security_framework_sys::item::{
+        kSecAttrComment, kSecAttrCreationDate, kSecAttrCreator, kSecAttrDescription, kSecAttrLabel,
+        kSecAttrModificationDate,
+    };
+    [
+        ("label", kSecAttrLabel),
+        ("comment", kSecAttrComment),
+        ("description", kSecAttrDescription),
+        ("creator", kSecAttrCreator),
+        ("creation_date", kSecAttrCreationDate),
+        ("modification_date", kSecAttrModificationDate),
+    ]
+}
+
+/// Stringify a Core Foundation attribute value (string, number, or date).
+fn attribute_value_to_string(value: core_foundation::base::CFTypeRef) -> String {
+    use core_foundation::base::{CFGetTypeID, TCFType};
+    use core_foundation::date::{CFDate, CFDateRef};
+    use core_foundation::number::{CFNumber, CFNumberRef};
+    use core_foundation::propertylist::CFPropertyListSubClass;
+    use core_foundation::string::{CFString, CFStringRef};
+
+    let type_id = unsafe { CFGetTypeID(value) };
+    if type_id == CFString::type_id() {
+        unsafe { CFString::wrap_under_get_rule(value as CFStringRef) }.to_string()
+    } else if type_id == CFNumber::type_id() {
+        let number = unsafe { CFNumber::wrap_under_get_rule(value as CFNumberRef) };
+        number
+            .to_i64()
+            .map(|n| n.to_string())
+            .unwrap_or_default()
+    } else if type_id == CFDate::type_id() {
+        let date = unsafe { CFDate::wrap_under_get_rule(value as CFDateRef) };
+        format!("{:?}", date.to_CFPropertyList())
+            .trim_matches('"')
+            .to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Read the standard attributes of a generic password item via `SecItemCopyMatching`.
+fn get_generic_attributes(
+    service: &str,
+    account: &str,
+) -> Result<std::collections::HashMap<String, String>> {
+    use core_foundation::base::{CFType, CFTypeRef, TCFType};
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::dictionary::{CFDictionary, CFDictionaryRef};
+    use core_foundation::string::CFString;
+    use security_framework_sys::base::{errSecItemNotFound, errSecSuccess};
+    use security_framework_sys::item::{
+        kSecAttrAccount, kSecAttrService, kSecClass, kSecClassGenericPassword,
+        kSecReturnAttributes,
+    };
+    use security_framework_sys::keychain_item::SecItemCopyMatching;
+
+    let class = unsafe { CFString::wrap_under_get_rule(kSecClass) };
+    let class_value = unsafe { CFType::wrap_under_get_rule(kSecClassGenericPassword as *const _) };
+    let service_key = unsafe { CFString::wrap_under_get_rule(kSecAttrService) };
+    let account_key = unsafe { CFString::wrap_under_get_rule(kSecAttrAccount) };
+    let return_key = unsafe { CFString::wrap_under_get_rule(kSecReturnAttributes) };
+
+    let query = CFDictionary::from_CFType_pairs(&[
+        (class.as_CFType(), class_value),
+        (service_key.as_CFType(), CFString::new(service).as_CFType()),
+        (account_key.as_CFType(), CFString::new(account).as_CFType()),
+        (return_key.as_CFType(), CFBoolean::true_value().as_CFType()),
+    ]);
+
+    let mut ptr: CFTypeRef = std::ptr::null();
+    let status = unsafe { SecItemCopyMatching(query.as_concrete_TypeRef(), &mut ptr as *mut _) };
+    match status {
+        errSecSuccess => {}
+        errSecItemNotFound => return Err(ErrorCode::NoEntry),
+        other => return Err(decode_error(Error::from_code(other))),
+    }
+
+    let attributes: CFDictionary =
+        unsafe { CFDictionary::wrap_under_create_rule(ptr as CFDictionaryRef) };
+
+    // Map each item attribute key we recognise to its friendly name.
+    let wanted: Vec<(String, &str)> = attribute_keys()
+        .into_iter()
+        .map(|(name, raw_key)| {
+            let key = unsafe { CFString::wrap_under_get_rule(raw_key) }.to_string();
+            (key, name)
+        })
+        .collect();
+
+    let mut result = std::collections::HashMap::new();
+    let (keys, values) = attributes.get_keys_and_values();
+    for (key, value) in keys.into_iter().zip(values.into_iter()) {
+        let key_str = unsafe { CFString::wrap_under_get_rule(key as _) }.to_string();
+        if let Some((_, name)) = wanted.iter().find(|(raw, _)| *raw == key_str) {
+            let string = attribute_value_to_string(value as CFTypeRef);
+            if !string.is_empty() {
+                result.insert(name.to_string(), string);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Write the writable attributes of a generic password item via `SecItemUpdate`.
+fn update_generic_attributes(
+    service: &str,
+    account: &str,
+    attributes: &std::collections::HashMap<&str, &str>,
+) -> Result<()> {
+    use core_foundation::base::{CFType, TCFType};
+    use core_foundation::dictionary::CFDictionary;
+    use core_foundation::string::CFString;
+    use security_framework_sys::base::{errSecItemNotFound, errSecSuccess};
+    use security_framework_sys::item::{
+        kSecAttrAccount, kSecAttrComment, kSecAttrDescription, kSecAttrLabel, kSecAttrService,
+        kSecClass, kSecClassGenericPassword,
+    };
+    use security_framework_sys::keychain_item::SecItemUpdate;
+
+    let class = unsafe { CFString::wrap_under_get_rule(kSecClass) };
+    let class_value = unsafe { CFType::wrap_under_get_rule(kSecClassGenericPassword as *const _) };
+    let service_key = unsafe { CFString::wrap_under_get_rule(kSecAttrService) };
+    let account_key = unsafe { CFString::wrap_under_get_rule(kSecAttrAccount) };
+    let query = CFDictionary::from_CFType_pairs(&[
+        (class.as_CFType(), class_value),
+        (service_key.as_CFType(), CFString::new(service).as_CFType()),
+        (account_key.as_CFType(), CFString::new(account).as_CFType()),
+    ]);
+
+    let mut pairs = Vec::new();
+    for (name, raw_key) in [
+        ("label", kSecAttrLabel),
+        ("comment", kSecAttrComment),
+        ("description", kSecAttrDescription),
+        ("kind", kSecAttrDescription),
+    ] {
+        if let Some(value) = attributes.get(name) {
+            let key = unsafe { CFString::wrap_under_get_rule(raw_key) };
+            pairs.push((key.as_CFType(), CFString::new(value).as_CFType()));
+        }
+    }
+    if pairs.is_empty() {
+        return Ok(());
+    }
+    let changes = CFDictionary::from_CFType_pairs(&pairs);
+
+    let status =
+        unsafe { SecItemUpdate(query.as_concrete_TypeRef(), changes.as_concrete_TypeRef()) };
+    match status {
+        errSecSuccess => Ok(()),
+        errSecItemNotFound => Err(ErrorCode::NoEntry),
+        other => Err(decode_error(Error::from_code(other))),
+    }
+}
+
+/// The network protocols that can key an internet password.
+///
+/// This mirrors the subset of `SecProtocolType` values that networked apps
+/// commonly store credentials under; unknown targets default to `Https`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacProtocolType {
+    Ftp,
+    Http,
+    Https,
+    Smtp,
+    Imap,
+    Pop3,
+    Ssh,
+}
+
+impl std::str::FromStr for MacProtocolType {
+    type Err = ErrorCode;
+
+    /// Convert a protocol name (any case) to a protocol type.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "ftp" => Ok(MacProtocolType::Ftp),
+            "http" => Ok(MacProtocolType::Http),
+            "https" => Ok(MacProtocolType::Https),
+            "smtp" => Ok(MacProtocolType::Smtp),
+            "imap" => Ok(MacProtocolType::Imap),
+            "pop3" => Ok(MacProtocolType::Pop3),
+            "ssh" => Ok(MacProtocolType::Ssh),
+            _ => Err(ErrorCode::Invalid(
+                "protocol".to_string(),
+                format!("'{s}' is not a known internet protocol"),
+            )),
+        }
+    }
+}
+
+impl From<&MacProtocolType> for SecProtocolType {
+    fn from(protocol: &MacProtocolType) -> Self {
+        match protocol {
+            MacProtocolType::Ftp => SecProtocolType::FTP,
+            MacProtocolType::Http => SecProtocolType::HTTP,
+            MacProtocolType::Https => SecProtocolType::HTTPS,
+            MacProtocolType::Smtp => SecProtocolType::SMTP,
+            MacProtocolType::Imap => SecProtocolType::IMAP,
+            MacProtocolType::Pop3 => SecProtocolType::POP3,
+            MacProtocolType::Ssh => SecProtocolType::SSH,
+        }
+    }
+}
+
+/// The authentication schemes that can key an internet password.
+///
+/// This mirrors the subset of `SecAuthenticationType` values in common use;
+/// `Default` selects the keychain's default scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacAuthenticationType {
+    Default,
+    HttpBasic,
+    HttpDigest,
+    HtmlForm,
+    Ntlm,
+}
+
+impl std::str::FromStr for MacAuthenticationType {
+    type Err = ErrorCode;
+
+    /// Convert an authentication-type name (any case) to a type.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "default" => Ok(MacAuthenticationType::Default),
+            "http-basic" | "httpbasic" => Ok(MacAuthenticationType::HttpBasic),
+            "http-digest" | "httpdigest" => Ok(MacAuthenticationType::HttpDigest),
+            "html-form" | "htmlform" => Ok(MacAuthenticationType::HtmlForm),
+            "ntlm" => Ok(MacAuthenticationType::Ntlm),
+            _ => Err(ErrorCode::Invalid(
+                "authentication_type".to_string(),
+                format!("'{s}' is not a known authentication type"),
+            )),
+        }
+    }
+}
+
+impl From<&MacAuthenticationType> for SecAuthenticationType {
+    fn from(authentication_type: &MacAuthenticationType) -> Self {
+        match authentication_type {
+            MacAuthenticationType::Default => SecAuthenticationType::Default,
+            MacAuthenticationType::HttpBasic => SecAuthenticationType::HTTPBasic,
+            MacAuthenticationType::HttpDigest => SecAuthenticationType::HTTPDigest,
+            MacAuthenticationType::HtmlForm => SecAuthenticationType::HTMLForm,
+            MacAuthenticationType::Ntlm => SecAuthenticationType::NTLM,
+        }
+    }
+}
+
 fn get_keychain(cred: &MacCredential) -> Result<SecKeychain> {
-    let domain = match cred.domain {
+    let domain = match cred.domain() {
         MacKeychainDomain::User => SecPreferencesDomain::User,
         MacKeychainDomain::System => SecPreferencesDomain::System,
         MacKeychainDomain::Common => SecPreferencesDomain::Common,
@@ -239,10 +1038,12 @@ pub fn entry_from_search(credential: &std::collections::HashMap<String, String>)
             "No user key found in credential".to_string(),
         ));
     };
-    let maccredential = Box::new(MacCredential {
+    let maccredential = Box::new(MacCredential::Generic {
         domain: MacKeychainDomain::User,
         service: service.to_string(),
         account: account.to_string(),
+        access_control: None,
+        options: MacItemOptions::default(),
     });
 
     Ok(Entry::new_with_credential(maccredential))
@@ -314,6 +1115,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_with_options() {
+        use super::{Accessibility, MacItemOptions};
+        let options = MacItemOptions {
+            accessibility: Some(Accessibility::AfterFirstUnlockThisDeviceOnly),
+            synchronizable: true,
+            access_group: Some("ABCDE12345.com.example.shared".to_string()),
+        };
+        let credential = MacCredential::new_with_target(None, "service", "user")
+            .expect("Couldn't create credential")
+            .with_options(options.clone());
+        assert!(matches!(
+            credential,
+            MacCredential::Generic { options: set, .. } if set == options
+        ));
+    }
+
+    #[test]
+    fn test_internet_target_grammar() {
+        use super::{MacAuthenticationType, MacProtocolType};
+        let credential = MacCredential::new_with_target(
+            Some("internet://alice@example.com:8443/login?protocol=https&auth=html-form"),
+            "service",
+            "bob",
+        )
+        .expect("Couldn't parse internet target");
+        match credential {
+            MacCredential::Internet {
+                server,
+                account,
+                protocol,
+                authentication_type,
+                port,
+                path,
+                ..
+            } => {
+                assert_eq!(server, "example.com");
+                assert_eq!(account, "alice");
+                assert_eq!(port, 8443);
+                assert_eq!(path, "/login");
+                assert_eq!(protocol, MacProtocolType::Https);
+                assert_eq!(authentication_type, MacAuthenticationType::HtmlForm);
+            }
+            other => panic!("Expected an internet credential, got {other:?}"),
+        }
+        // Without an embedded user the entry account is used.
+        let credential = MacCredential::new_with_target(Some("internet://example.com"), "svc", "bob")
+            .expect("Couldn't parse bare internet target");
+        assert!(matches!(
+            credential,
+            MacCredential::Internet { account, .. } if account == "bob"
+        ));
+    }
+
     #[test]
     fn test_missing_entry() {
         crate::tests::test_missing_entry(entry_new);
@@ -339,6 +1194,35 @@ mod tests {
         crate::tests::test_update(entry_new);
     }
 
+    #[test]
+    fn test_round_trip_random_secret() {
+        crate::tests::test_round_trip_random_secret(entry_new);
+    }
+
+    #[test]
+    fn test_get_update_attributes() {
+        use std::collections::HashMap;
+        let name = generate_random_string();
+        let entry = entry_new(&name, &name);
+        entry
+            .set_password("attributed password")
+            .expect("Can't set password for attributes");
+        let mut updates = HashMap::new();
+        updates.insert("label", "My Friendly Label");
+        updates.insert("comment", "set by the test");
+        entry
+            .update_attributes(&updates)
+            .expect("Can't update attributes");
+        let attributes = entry.get_attributes().expect("Can't read attributes");
+        assert_eq!(attributes.get("label").map(String::as_str), Some("My Friendly Label"));
+        assert_eq!(attributes.get("comment").map(String::as_str), Some("set by the test"));
+        // The keychain always maintains a creation date for a live item.
+        assert!(attributes.contains_key("creation_date"));
+        entry
+            .delete_credential()
+            .expect("Can't delete after attributes");
+    }
+
     #[test]
     fn test_get_credential() {
         let name = generate_random_string();
@@ -356,7 +1240,7 @@ mod tests {
             .expect("Can't set password for get_credential");
         assert!(credential.get_credential().is_ok());
         entry
-            .delete_password()
+            .delete_credential()
             .expect("Couldn't delete after get_credential");
         assert!(matches!(entry.get_password(), Err(Error::NoEntry)));
     }
@@ -454,7 +1338,7 @@ mod tests {
 
         let mut expected: HashSet<&str> = expected.lines().collect();
         expected.insert("1");
-        entry.delete_password().expect("Failed to delete entry");
+        entry.delete_credential().expect("Failed to delete entry");
         assert_eq!(expected, result);
     }
 
@@ -487,7 +1371,7 @@ mod tests {
         assert_eq!(searched_entry_password, entry_password);
 
         searched_entry
-            .delete_password()
+            .delete_credential()
             .expect("Failed to delete password2 from searched entry");
 
         let entry_password = entry.get_password().unwrap_err();
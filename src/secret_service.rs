@@ -84,6 +84,7 @@ this keystore doesn't work "out of the box" on WSL.  See the
 issue for more details and possible workarounds.
  */
 use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
 
 #[cfg(not(feature = "async-secret-service"))]
 use dbus_secret_service::{Collection, EncryptionType, Error, Item, SecretService};
@@ -93,7 +94,11 @@ use secret_service::{
     EncryptionType, Error,
 };
 
-use super::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use super::credential::{
+    Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi, CredentialMetadata,
+};
 use super::error::{decode_password, Error as ErrorCode, Result};
 
 /// The representation of an item in the secret-service.
@@ -109,6 +114,10 @@ pub struct SsCredential {
     pub attributes: HashMap<String, String>,
     pub label: String,
     target: Option<String>,
+    /// Extra caller-supplied attributes, merged into both the stored item (at
+    /// creation) and the search query (at lookup).  Empty for the standard
+    /// service/user model.
+    extra: HashMap<String, String>,
 }
 
 impl CredentialApi for SsCredential {
@@ -133,11 +142,6 @@ impl CredentialApi for SsCredential {
     /// When creating, the item is put into a collection named by the credential's `target`
     /// attribute.  
     fn set_secret(&self, secret: &[u8]) -> Result<()> {
-        #[cfg(any(feature = "crypto-rust", feature = "crypto-openssl"))]
-        let session_type = EncryptionType::Dh;
-        #[cfg(not(any(feature = "crypto-rust", feature = "crypto-openssl")))]
-        let session_type = EncryptionType::Plain;
-        let ss = SecretService::connect(session_type).map_err(platform_failure)?;
         // first try to find a unique, existing, matching item and set its password
         match self.map_matching_items(|i| set_item_secret(i, secret), true) {
             Ok(_) => return Ok(()),
@@ -149,17 +153,19 @@ impl CredentialApi for SsCredential {
         // the [new] or [new_with_target] commands will have explicit targets.  But entries
         // created to wrap 3rd-party items that don't have `target` attributes may not.
         let name = self.target.as_ref().ok_or_else(empty_target)?;
-        let collection = get_collection(&ss, name).or_else(|_| create_collection(&ss, name))?;
-        collection
-            .create_item(
-                self.label.as_str(),
-                self.all_attributes(),
-                secret,
-                true, // replace
-                "text/plain",
-            )
-            .map_err(platform_failure)?;
-        Ok(())
+        with_service(|ss| {
+            let collection = get_collection(ss, name).or_else(|_| create_collection(ss, name))?;
+            collection
+                .create_item(
+                    self.label.as_str(),
+                    self.all_attributes(),
+                    secret,
+                    true, // replace
+                    "text/plain",
+                )
+                .map_err(platform_failure)?;
+            Ok(())
+        })
     }
 
     /// Gets the password on a unique matching item, if it exists.
@@ -198,6 +204,29 @@ impl CredentialApi for SsCredential {
         Ok(())
     }
 
+    /// Read the creation and rotation timestamps recorded on the matching item.
+    ///
+    /// The secret service persists our reserved timestamp attributes alongside
+    /// the item, so [Entry::rotate_secret](crate::Entry::rotate_secret)'s stamps
+    /// round-trip here.  Attributes this store doesn't set (comment, persistence
+    /// scope, credential type) are left `None`; errors match
+    /// [get_secret](SsCredential::get_secret).
+    fn get_metadata(&self) -> Result<CredentialMetadata> {
+        let maps: Vec<HashMap<String, String>> =
+            self.map_matching_items(|item| item.get_attributes().map_err(decode_error), true)?;
+        let attributes = &maps[0];
+        let timestamp = |key: &str| {
+            attributes
+                .get(key)
+                .and_then(|value| OffsetDateTime::parse(value, &Rfc3339).ok())
+        };
+        Ok(CredentialMetadata {
+            created: timestamp("keyring.created"),
+            last_rotated: timestamp("keyring.rotated"),
+            ..CredentialMetadata::default()
+        })
+    }
+
     /// Return the underlying credential object with an `Any` type so that it can
     /// be downgraded to an [SsCredential] for platform-specific processing.
     fn as_any(&self) -> &dyn std::any::Any {
@@ -237,9 +266,67 @@ impl SsCredential {
                 env!("CARGO_PKG_VERSION"),
             ),
             target: Some(target.to_string()),
+            extra: HashMap::new(),
         })
     }
 
+    /// Create a credential with extra user-defined attributes.
+    ///
+    /// The `extra` attributes are merged into the item's attribute map when it
+    /// is created and into the query used to find it, in addition to the
+    /// standard `target`/`service`/`username` attributes.  This lets callers
+    /// disambiguate items that share a service and user (per-device or
+    /// per-profile secrets, say) and find third-party items written with
+    /// non-standard schemas.
+    pub fn new_with_attributes(
+        target: Option<&str>,
+        service: &str,
+        user: &str,
+        extra: HashMap<String, String>,
+    ) -> Result<Self> {
+        let mut credential = Self::new_with_target(target, service, user)?;
+        for (key, value) in &extra {
+            credential.attributes.insert(key.clone(), value.clone());
+        }
+        credential.extra = extra;
+        Ok(credential)
+    }
+
+    /// Create a credential describing an *internet* (network) password.
+    ///
+    /// In addition to the standard service/user attributes, this sets the
+    /// freedesktop network-password attribute keys (`server`, `protocol`,
+    /// `port`, `authtype`, and `object` for the path) that GNOME and KDE tools
+    /// use, so secrets created here are discoverable by — and can read secrets
+    /// created by — those tools.  The richer attribute set is merged into both
+    /// storage and search, so entries created this way round-trip through the
+    /// usual [set_password](SsCredential::set_password) /
+    /// [get_password](SsCredential::get_password) /
+    /// [delete_credential](SsCredential::delete_credential) path.
+    pub fn new_internet_password(
+        target: Option<&str>,
+        service: &str,
+        user: &str,
+        server: &str,
+        port: Option<u16>,
+        protocol: &str,
+        authtype: &str,
+        path: Option<&str>,
+    ) -> Result<Self> {
+        let mut extra = HashMap::from([
+            ("server".to_string(), server.to_string()),
+            ("protocol".to_string(), protocol.to_string()),
+            ("authtype".to_string(), authtype.to_string()),
+        ]);
+        if let Some(port) = port {
+            extra.insert("port".to_string(), port.to_string());
+        }
+        if let Some(path) = path {
+            extra.insert("object".to_string(), path.to_string());
+        }
+        Self::new_with_attributes(target, service, user, extra)
+    }
+
     /// Create a credential that has *no* target and the given service and user.
     ///
     /// This emulates what keyring v1 did, and can be very handy when you need to
@@ -257,6 +344,7 @@ impl SsCredential {
                 env!("CARGO_PKG_VERSION"),
             ),
             target: None,
+            extra: HashMap::new(),
         })
     }
 
@@ -271,6 +359,7 @@ impl SsCredential {
             attributes,
             label: item.get_label().map_err(decode_error)?,
             target,
+            extra: HashMap::new(),
         })
     }
 
@@ -313,35 +402,102 @@ impl SsCredential {
         F: Fn(&Item) -> Result<T>,
         T: Sized,
     {
-        #[cfg(any(feature = "crypto-rust", feature = "crypto-openssl"))]
-        let session_type = EncryptionType::Dh;
-        #[cfg(not(any(feature = "crypto-rust", feature = "crypto-openssl")))]
-        let session_type = EncryptionType::Plain;
-        let ss = SecretService::connect(session_type).map_err(platform_failure)?;
-        let attributes: HashMap<&str, &str> = self.search_attributes().into_iter().collect();
-        let search = ss.search_items(attributes).map_err(decode_error)?;
-        if require_unique {
-            let count = search.locked.len() + search.unlocked.len();
-            if count == 0 {
-                return Err(ErrorCode::NoEntry);
-            } else if count > 1 {
-                let mut creds: Vec<Box<Credential>> = vec![];
-                for item in search.locked.iter().chain(search.unlocked.iter()) {
-                    let cred = Self::new_from_item(item)?;
-                    creds.push(Box::new(cred))
+        with_service(|ss| {
+            let attributes: HashMap<&str, &str> = self.search_attributes().into_iter().collect();
+            let search = ss.search_items(attributes).map_err(decode_error)?;
+            if require_unique {
+                let count = search.locked.len() + search.unlocked.len();
+                if count == 0 {
+                    return Err(ErrorCode::NoEntry);
+                } else if count > 1 {
+                    let mut creds: Vec<Box<Credential>> = vec![];
+                    for item in search.locked.iter().chain(search.unlocked.iter()) {
+                        let cred = Self::new_from_item(item)?;
+                        creds.push(Box::new(cred))
+                    }
+                    return Err(ErrorCode::Ambiguous(creds));
                 }
-                return Err(ErrorCode::Ambiguous(creds));
             }
-        }
-        let mut results: Vec<T> = vec![];
-        for item in search.unlocked.iter() {
-            results.push(f(item)?);
-        }
-        for item in search.locked.iter() {
-            item.unlock().map_err(decode_error)?;
-            results.push(f(item)?);
-        }
-        Ok(results)
+            let mut results: Vec<T> = vec![];
+            for item in search.unlocked.iter() {
+                results.push(f(item)?);
+            }
+            for item in search.locked.iter() {
+                item.unlock().map_err(decode_error)?;
+                results.push(f(item)?);
+            }
+            Ok(results)
+        })
+    }
+
+    /// Set the secret on many credentials concurrently.
+    ///
+    /// Each credential's item operation runs on its own thread (with its own
+    /// connection) so the D-Bus round-trips overlap, then the results are
+    /// joined.  Results are returned positionally and are partial-failure
+    /// tolerant: a failure on one credential is reported in place without
+    /// affecting the others.
+    pub fn set_many(items: &[(SsCredential, &[u8])]) -> Vec<Result<()>> {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = items
+                .iter()
+                .map(|(credential, secret)| scope.spawn(move || credential.set_secret(secret)))
+                .collect();
+            join_all(handles)
+        })
+    }
+
+    /// Get the secret from many credentials concurrently.
+    ///
+    /// See [set_many](SsCredential::set_many) for the concurrency and
+    /// partial-failure semantics.
+    pub fn get_many(credentials: &[SsCredential]) -> Vec<Result<Vec<u8>>> {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = credentials
+                .iter()
+                .map(|credential| scope.spawn(move || credential.get_secret()))
+                .collect();
+            join_all(handles)
+        })
+    }
+
+    /// Delete many credentials concurrently.
+    ///
+    /// See [set_many](SsCredential::set_many) for the concurrency and
+    /// partial-failure semantics.
+    pub fn delete_many(credentials: &[SsCredential]) -> Vec<Result<()>> {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = credentials
+                .iter()
+                .map(|credential| scope.spawn(move || credential.delete_credential()))
+                .collect();
+            join_all(handles)
+        })
+    }
+
+    /// Report whether the collection backing this credential is locked.
+    ///
+    /// This inspects the collection named by the credential's `target`
+    /// attribute (or the default collection), without unlocking it.
+    pub fn is_locked(&self) -> Result<bool> {
+        let name = self.target.as_deref().unwrap_or("default");
+        with_service(|ss| locate_collection(ss, name)?.is_locked().map_err(decode_error))
+    }
+
+    /// Unlock the collection backing this credential.
+    ///
+    /// This may prompt the user through the platform's secret agent.  After it
+    /// succeeds, [get_password](SsCredential::get_password) and friends can
+    /// proceed without failing on a locked collection.
+    pub fn unlock(&self) -> Result<()> {
+        let name = self.target.as_deref().unwrap_or("default");
+        with_service(|ss| locate_collection(ss, name)?.unlock().map_err(decode_error))
+    }
+
+    /// Lock the collection backing this credential.
+    pub fn lock(&self) -> Result<()> {
+        let name = self.target.as_deref().unwrap_or("default");
+        with_service(|ss| locate_collection(ss, name)?.lock().map_err(decode_error))
     }
 
     /// Using strings in the credential map makes managing the lifetime
@@ -364,6 +520,9 @@ impl SsCredential {
         }
         result.insert("service", self.attributes["service"].as_str());
         result.insert("username", self.attributes["username"].as_str());
+        for (key, value) in &self.extra {
+            result.insert(key.as_str(), value.as_str());
+        }
         result
     }
 }
@@ -380,6 +539,16 @@ pub fn default_credential_builder() -> Box<CredentialBuilder> {
     Box::new(SsCredentialBuilder {})
 }
 
+impl SsCredentialBuilder {
+    /// List every item this crate manages in the secret service.
+    ///
+    /// See [list_credentials] for details; this is a convenience wrapper so
+    /// callers holding a builder can enumerate without a separate import.
+    pub fn list(&self, target: Option<&str>) -> Result<Vec<SsCredential>> {
+        list_credentials(target)
+    }
+}
+
 impl CredentialBuilderApi for SsCredentialBuilder {
     /// Build an [SsCredential] for the given target, service, and user.
     fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
@@ -395,6 +564,151 @@ impl CredentialBuilderApi for SsCredentialBuilder {
     }
 }
 
+//
+// Batch utilities
+//
+
+/// Join a set of scoped batch-worker handles, preserving per-item results and
+/// turning a worker panic into a platform failure.
+fn join_all<'scope, T>(
+    handles: Vec<std::thread::ScopedJoinHandle<'scope, Result<T>>>,
+) -> Vec<Result<T>> {
+    handles
+        .into_iter()
+        .map(|handle| match handle.join() {
+            Ok(result) => result,
+            Err(_) => Err(ErrorCode::PlatformFailure(Box::new(std::io::Error::other(
+                "batch worker thread panicked",
+            )))),
+        })
+        .collect()
+}
+
+//
+// Connection cache
+//
+
+/// The process-wide cached secret-service connection.
+///
+/// Connecting to the secret service is a D-Bus round-trip, so we keep a single
+/// connection alive and reuse it across calls rather than reconnecting on every
+/// operation.  The connection is held behind a [Mutex] because the underlying
+/// handle is not shareable across threads, and it is reconnected transparently
+/// if it has been dropped.
+static CONNECTION: OnceLock<Mutex<Option<SecretService>>> = OnceLock::new();
+
+/// The session encryption type selected by the crate's crypto features.
+fn session_type() -> EncryptionType {
+    #[cfg(any(feature = "crypto-rust", feature = "crypto-openssl"))]
+    {
+        EncryptionType::Dh
+    }
+    #[cfg(not(any(feature = "crypto-rust", feature = "crypto-openssl")))]
+    {
+        EncryptionType::Plain
+    }
+}
+
+/// Open a fresh connection to the secret service.
+fn connect() -> Result<SecretService> {
+    SecretService::connect(session_type()).map_err(platform_failure)
+}
+
+/// Run `f` with the process-wide cached secret-service connection.
+///
+/// The connection is created on first use and reused thereafter.  Because
+/// [Collection] and [Item] borrow the service, the work must be done inside the
+/// closure so the borrow stays scoped to the held lock.  If the cached
+/// connection has dropped (the closure reports a
+/// [PlatformFailure](ErrorCode::PlatformFailure)), it is reconnected once and
+/// the closure is retried.
+pub fn with_service<F, T>(f: F) -> Result<T>
+where
+    F: Fn(&SecretService) -> Result<T>,
+{
+    let cache = CONNECTION.get_or_init(|| Mutex::new(None));
+    let mut guard = cache.lock().unwrap_or_else(|err| err.into_inner());
+    if guard.is_none() {
+        *guard = Some(connect()?);
+    }
+    match f(guard.as_ref().unwrap()) {
+        Err(ErrorCode::PlatformFailure(err)) => {
+            // the cached connection may have dropped; reconnect once and retry
+            *guard = Some(connect()?);
+            match f(guard.as_ref().unwrap()) {
+                Ok(value) => Ok(value),
+                // surface the original failure if the retry fails the same way
+                Err(_) => Err(ErrorCode::PlatformFailure(err)),
+            }
+        }
+        other => other,
+    }
+}
+
+/// Run `f` with a freshly opened secret-service connection, bypassing the
+/// [cache](with_service).
+///
+/// Use this when you specifically need an isolated session rather than the
+/// shared cached connection.
+pub fn with_fresh_service<F, T>(f: F) -> Result<T>
+where
+    F: FnOnce(&SecretService) -> Result<T>,
+{
+    let ss = connect()?;
+    f(&ss)
+}
+
+/// Search the secret service for every item matching an arbitrary attribute map.
+///
+/// Unlike the single-credential resolution used by
+/// [get_password](SsCredential::get_password) and friends (which match on a
+/// fixed service/user/target set), this issues a `SearchItems` call with
+/// whatever attributes the caller supplies — for example just
+/// `{"service": "x"}` to list every account for a service — and materializes
+/// each match into an [Entry](crate::Entry) wrapping an [SsCredential].
+///
+/// This mirrors the attribute-dictionary lookup model used by libsecret and the
+/// macOS security framework.
+pub fn search(attributes: &HashMap<&str, &str>) -> Result<Vec<crate::Entry>> {
+    with_service(|ss| {
+        let search = ss.search_items(attributes.clone()).map_err(decode_error)?;
+        let mut entries = Vec::new();
+        for item in search.unlocked.iter().chain(search.locked.iter()) {
+            let credential = SsCredential::new_from_item(item)?;
+            entries.push(crate::Entry::new_with_credential(Box::new(credential)));
+        }
+        Ok(entries)
+    })
+}
+
+/// List every item this crate manages in the secret service.
+///
+/// All items carry an `application=rust-keyring` attribute, so searching on it
+/// returns everything written through this crate (across all collections),
+/// regardless of service or user.  Each matching item is returned as an
+/// [SsCredential] built with [new_from_item](SsCredential::new_from_item), so
+/// callers can inspect its attributes and label.  Pass a `target` to restrict
+/// the search to items created with that target (collection).
+///
+/// This is useful for building credential managers, auditing what exists, or
+/// bulk-migrating v1 (no-target) items without knowing service/user names in
+/// advance.
+pub fn list_credentials(target: Option<&str>) -> Result<Vec<SsCredential>> {
+    with_service(|ss| {
+        let mut attributes: HashMap<&str, &str> = HashMap::new();
+        attributes.insert("application", "rust-keyring");
+        if let Some(target) = target {
+            attributes.insert("target", target);
+        }
+        let search = ss.search_items(attributes).map_err(decode_error)?;
+        let mut credentials = Vec::new();
+        for item in search.unlocked.iter().chain(search.locked.iter()) {
+            credentials.push(SsCredential::new_from_item(item)?);
+        }
+        Ok(credentials)
+    })
+}
+
 //
 // Secret Service utilities
 //
@@ -419,6 +733,24 @@ pub fn get_collection<'a>(ss: &'a SecretService, name: &str) -> Result<Collectio
     Ok(collection)
 }
 
+/// Locate the secret service collection whose label is the given name,
+/// *without* unlocking it.
+///
+/// Like [get_collection], the name `default` names the default collection
+/// regardless of its label.  Unlike it, this leaves a locked collection
+/// locked, so callers can inspect [is_locked](Collection::is_locked) or unlock
+/// explicitly.
+pub fn locate_collection<'a>(ss: &'a SecretService, name: &str) -> Result<Collection<'a>> {
+    if name.eq("default") {
+        ss.get_default_collection().map_err(decode_error)
+    } else {
+        let all = ss.get_all_collections().map_err(decode_error)?;
+        all.into_iter()
+            .find(|c| c.get_label().map(|l| l.eq(name)).unwrap_or(false))
+            .ok_or(ErrorCode::NoEntry)
+    }
+}
+
 /// Create a secret service collection labeled with the given name.
 ///
 /// If a collection with that name already exists, it is returned.
@@ -456,6 +788,148 @@ pub fn delete_item(item: &Item) -> Result<()> {
     item.delete().map_err(decode_error)
 }
 
+//
+// Native async implementation
+//
+
+/// A native-async view over the secret-service store.
+///
+/// This implements [AsyncCredentialApi](crate::credential::AsyncCredentialApi)
+/// by calling the non-blocking `secret_service` API directly, so `tokio`
+/// callers can `.await` keyring operations without dedicating a thread to the
+/// blocking path.  It shares [SsCredential]'s attribute model; construct one
+/// from any [SsCredential] with [AsyncSsCredential::new].
+#[cfg(feature = "async-secret-service")]
+pub struct AsyncSsCredential {
+    credential: SsCredential,
+}
+
+#[cfg(feature = "async-secret-service")]
+impl AsyncSsCredential {
+    /// Wrap an [SsCredential] in its native-async view.
+    pub fn new(credential: SsCredential) -> Self {
+        Self { credential }
+    }
+
+    /// Connect to the secret service using the crate's configured session type.
+    async fn connect() -> Result<secret_service::SecretService<'static>> {
+        secret_service::SecretService::connect(session_type())
+            .await
+            .map_err(platform_failure)
+    }
+
+    /// Find the single item matching this credential, erroring in the same way
+    /// as the blocking [map_matching_items](SsCredential::map_matching_items).
+    async fn find_unique_item<'a>(
+        &self,
+        ss: &'a secret_service::SecretService<'a>,
+    ) -> Result<secret_service::Item<'a>> {
+        let attributes: HashMap<&str, &str> =
+            self.credential.search_attributes().into_iter().collect();
+        let mut search = ss.search_items(attributes).await.map_err(decode_error)?;
+        let mut items = Vec::new();
+        items.append(&mut search.unlocked);
+        items.append(&mut search.locked);
+        match items.len() {
+            0 => Err(ErrorCode::NoEntry),
+            1 => {
+                let item = items.into_iter().next().unwrap();
+                item.unlock().await.map_err(decode_error)?;
+                Ok(item)
+            }
+            _ => {
+                let mut creds: Vec<Box<Credential>> = vec![];
+                for item in &items {
+                    let attributes = item.get_attributes().await.map_err(decode_error)?;
+                    let target = attributes.get("target").cloned();
+                    let label = item.get_label().await.map_err(decode_error)?;
+                    creds.push(Box::new(SsCredential {
+                        attributes,
+                        label,
+                        target,
+                        extra: HashMap::new(),
+                    }));
+                }
+                Err(ErrorCode::Ambiguous(creds))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async-secret-service")]
+impl crate::credential::AsyncCredentialApi for AsyncSsCredential {
+    async fn set_password(&self, password: &str) -> Result<()> {
+        self.set_secret(password.as_bytes()).await
+    }
+
+    async fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        let ss = Self::connect().await?;
+        if let Ok(item) = self.find_unique_item(&ss).await {
+            return item
+                .set_secret(secret, "text/plain")
+                .await
+                .map_err(decode_error);
+        }
+        let name = self.credential.target.as_ref().ok_or_else(empty_target)?;
+        let collection = match ss.get_all_collections().await {
+            Ok(all) => {
+                let mut found = None;
+                for collection in all {
+                    if collection
+                        .get_label()
+                        .await
+                        .map(|label| label.eq(name) || name.eq("default"))
+                        .unwrap_or(false)
+                    {
+                        found = Some(collection);
+                        break;
+                    }
+                }
+                match found {
+                    Some(collection) => collection,
+                    None => ss.create_collection(name, "").await.map_err(decode_error)?,
+                }
+            }
+            Err(err) => return Err(decode_error(err)),
+        };
+        if collection.is_locked().await.map_err(decode_error)? {
+            collection.unlock().await.map_err(decode_error)?;
+        }
+        collection
+            .create_item(
+                self.credential.label.as_str(),
+                self.credential.all_attributes(),
+                secret,
+                true,
+                "text/plain",
+            )
+            .await
+            .map_err(platform_failure)?;
+        Ok(())
+    }
+
+    async fn get_password(&self) -> Result<String> {
+        let secret = self.get_secret().await?;
+        decode_password(secret)
+    }
+
+    async fn get_secret(&self) -> Result<Vec<u8>> {
+        let ss = Self::connect().await?;
+        let item = self.find_unique_item(&ss).await?;
+        item.get_secret().await.map_err(decode_error)
+    }
+
+    async fn delete_credential(&self) -> Result<()> {
+        let ss = Self::connect().await?;
+        let item = self.find_unique_item(&ss).await?;
+        item.delete().await.map_err(decode_error)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
 //
 // Error utilities
 //
@@ -6,6 +6,7 @@ TODO
 
  */
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[cfg(feature = "sync-secret-service")]
 use dbus_secret_service::{Error, Item};
@@ -23,6 +24,23 @@ use super::error::Result;
 pub struct SsKeyutilsCredential {
     keyutils: KeyutilsCredential,
     ss: SsCredential,
+    /// How long a copy cached in keyutils may live before it must be re-read
+    /// from the authoritative secret-service store.  `None` means no expiry.
+    cache_ttl: Option<Duration>,
+}
+
+impl SsKeyutilsCredential {
+    /// Cache `secret` in keyutils, applying the configured cache TTL (if any).
+    ///
+    /// Caching is best-effort: a keyutils failure leaves the authoritative
+    /// secret-service copy untouched, so errors are swallowed.
+    fn cache_secret(&self, secret: &[u8]) {
+        if self.keyutils.set_secret(secret).is_ok() {
+            if let Some(ttl) = self.cache_ttl {
+                let _ = self.keyutils.set_timeout(ttl);
+            }
+        }
+    }
 }
 
 impl CredentialApi for SsKeyutilsCredential {
@@ -32,7 +50,7 @@ impl CredentialApi for SsKeyutilsCredential {
 
     fn set_secret(&self, secret: &[u8]) -> Result<()> {
         self.ss.set_secret(secret)?;
-        let _ = self.keyutils.set_secret(secret);
+        self.cache_secret(secret);
         Ok(())
     }
 
@@ -42,7 +60,7 @@ impl CredentialApi for SsKeyutilsCredential {
         }
 
         let password = self.ss.get_password()?;
-        let _ = self.keyutils.set_password(&password);
+        self.cache_secret(password.as_bytes());
 
         Ok(password)
     }
@@ -53,7 +71,7 @@ impl CredentialApi for SsKeyutilsCredential {
         }
 
         let secret = self.ss.get_secret()?;
-        let _ = self.keyutils.set_secret(&secret);
+        self.cache_secret(&secret);
 
         Ok(secret)
     }
@@ -109,19 +127,38 @@ impl SsKeyutilsCredential {
     pub fn new_with_target(target: Option<&str>, service: &str, user: &str) -> Result<Self> {
         let ss = SsCredential::new_with_target(target, service, user)?;
         let keyutils = KeyutilsCredential::new_with_target(target, service, user)?;
-        Ok(Self { keyutils, ss })
+        Ok(Self {
+            keyutils,
+            ss,
+            cache_ttl: None,
+        })
     }
 
     pub fn new_with_no_target(service: &str, user: &str) -> Result<Self> {
         let keyutils = KeyutilsCredential::new_with_target(None, service, user)?;
         let ss = SsCredential::new_with_no_target(service, user)?;
-        Ok(Self { keyutils, ss })
+        Ok(Self {
+            keyutils,
+            ss,
+            cache_ttl: None,
+        })
     }
 
     pub fn new_from_item(item: &Item) -> Result<Self> {
         let ss = SsCredential::new_from_item(item)?;
         let keyutils = KeyutilsCredential::new_from_item(item)?;
-        Ok(Self { keyutils, ss })
+        Ok(Self {
+            keyutils,
+            ss,
+            cache_ttl: None,
+        })
+    }
+
+    /// Return a copy of this credential whose keyutils cache entries expire
+    /// after `ttl`, forcing a re-read from the secret-service store.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
     }
 
     pub fn get_all_passwords(&self) -> Result<Vec<String>> {
@@ -143,22 +180,34 @@ impl SsKeyutilsCredential {
 
 /// The builder for secret-service-with-keyutils credentials
 #[derive(Debug, Default)]
-pub struct SsKeyutilsCredentialBuilder {}
+pub struct SsKeyutilsCredentialBuilder {
+    cache_ttl: Option<Duration>,
+}
 
 /// Returns an instance of the secret-service-with-keyutils credential builder.
 ///
 /// If secret-service-with-keyutils is the default credential store,
 /// this is called once when an entry is first created.
 pub fn default_credential_builder() -> Box<CredentialBuilder> {
-    Box::new(SsKeyutilsCredentialBuilder {})
+    Box::new(SsKeyutilsCredentialBuilder::default())
+}
+
+impl SsKeyutilsCredentialBuilder {
+    /// Expire keyutils cache entries built by this builder after `ttl`.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
 }
 
 impl CredentialBuilderApi for SsKeyutilsCredentialBuilder {
     /// Build an [SsKeyutilsCredential] for the given target, service, and user.
     fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
-        Ok(Box::new(SsCredential::new_with_target(
-            target, service, user,
-        )?))
+        let mut credential = SsKeyutilsCredential::new_with_target(target, service, user)?;
+        if let Some(ttl) = self.cache_ttl {
+            credential = credential.with_cache_ttl(ttl);
+        }
+        Ok(Box::new(credential))
     }
 
     /// Return the underlying builder object with an `Any` type so that it can
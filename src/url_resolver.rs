@@ -0,0 +1,149 @@
+/*!
+
+# URL/realm-aware credential resolution
+
+This module lets HTTP clients use keyring the way package managers do: resolve a
+credential from a request URL rather than an explicit service/user pair. A URL
+is reduced to a *realm* — `scheme://host:port` — which becomes the service, and
+the URL's userinfo becomes the user.
+
+A single host often mixes authenticated and anonymous paths, so a
+[UrlResolver] caches lookups at two granularities:
+
+- a **URL-prefix** cache, where a cached result applies to any request URL of
+  which the cached URL is a path prefix (longest match wins); and
+- a **realm** cache keyed by `scheme://host:port`.
+
+The prefix cache is consulted first, then the realm cache; only on a miss in
+both is the platform store queried. Negative results are cached too, so a realm
+or path known to need no credential is not probed again — which avoids sending
+realm-wide credentials to a path that rejects them and causes spurious 401s.
+ */
+
+use std::collections::HashMap;
+
+use super::error::{Error, Result};
+use super::Entry;
+
+/// The parsed pieces of a request URL relevant to credential resolution.
+struct ParsedUrl {
+    /// The `scheme://host:port` realm.
+    realm: String,
+    /// The userinfo component, or the empty string if absent.
+    user: String,
+}
+
+/// Parse a request URL into its realm and userinfo.
+///
+/// Returns an [Invalid](Error::Invalid) `url` error if the scheme separator is
+/// missing.
+fn parse_url(url: &str) -> Result<ParsedUrl> {
+    let (scheme, rest) = url.split_once("://").ok_or_else(|| {
+        Error::Invalid("url".to_string(), format!("'{url}' has no scheme"))
+    })?;
+    let authority = match rest.split_once('/') {
+        Some((authority, _path)) => authority,
+        None => rest,
+    };
+    let authority = authority.split_once('?').map(|(a, _)| a).unwrap_or(authority);
+    let (user, host_port) = match authority.split_once('@') {
+        Some((user, host_port)) => (user.to_string(), host_port),
+        None => (String::new(), authority),
+    };
+    Ok(ParsedUrl {
+        realm: format!("{scheme}://{host_port}"),
+        user,
+    })
+}
+
+impl Entry {
+    /// Create an entry for the credential backing the given request URL.
+    ///
+    /// The URL's `scheme://host:port` realm is used as the service and its
+    /// userinfo as the user. For caching across many related URLs, use a
+    /// [UrlResolver] instead.
+    pub fn for_url(url: &str) -> Result<Entry> {
+        let parsed = parse_url(url)?;
+        Entry::new(&parsed.realm, &parsed.user)
+    }
+}
+
+/// A cached result of resolving a URL or realm.
+#[derive(Clone)]
+enum Resolution {
+    /// A credential exists; the stored userinfo resolves it.
+    Credential(String),
+    /// The realm or path is known to need no credential (negative cache).
+    None,
+}
+
+/// A caching resolver mapping request URLs to keyring [entries](Entry).
+///
+/// See the [module documentation](crate::url_resolver) for the two-tier caching
+/// strategy it uses.
+#[derive(Default)]
+pub struct UrlResolver {
+    realm_cache: HashMap<String, Resolution>,
+    prefix_cache: HashMap<String, Resolution>,
+}
+
+impl UrlResolver {
+    /// Create an empty resolver.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve the credential for `url`, returning `None` if none is needed.
+    ///
+    /// The URL-prefix cache is consulted first (longest matching prefix wins),
+    /// then the realm cache; a miss in both queries the platform store and
+    /// caches the outcome — positive or negative — at both granularities.
+    pub fn resolve(&mut self, url: &str) -> Result<Option<Entry>> {
+        if let Some(resolution) = self.longest_prefix_match(url) {
+            return resolution_to_entry(resolution, url);
+        }
+        let parsed = parse_url(url)?;
+        if let Some(resolution) = self.realm_cache.get(&parsed.realm).cloned() {
+            return resolution_to_entry(resolution, url);
+        }
+
+        // Miss in both caches: probe the platform store.
+        let entry = Entry::new(&parsed.realm, &parsed.user)?;
+        let resolution = match entry.get_secret() {
+            Ok(_) => Resolution::Credential(parsed.user.clone()),
+            Err(Error::NoEntry) => Resolution::None,
+            Err(err) => return Err(err),
+        };
+        self.realm_cache
+            .insert(parsed.realm.clone(), resolution.clone());
+        self.prefix_cache.insert(url.to_string(), resolution.clone());
+        resolution_to_entry(resolution, url)
+    }
+
+    /// Pre-seed the URL-prefix cache so that `url` and any path below it resolve
+    /// to the credential stored for `user` without a platform probe.
+    pub fn cache_prefix(&mut self, url: &str, user: &str) {
+        self.prefix_cache
+            .insert(url.to_string(), Resolution::Credential(user.to_string()));
+    }
+
+    /// Find the resolution whose cached URL is the longest path prefix of `url`.
+    fn longest_prefix_match(&self, url: &str) -> Option<Resolution> {
+        self.prefix_cache
+            .iter()
+            .filter(|(prefix, _)| url.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, resolution)| resolution.clone())
+    }
+}
+
+/// Turn a cached [Resolution] into an [Entry] (or `None` for a negative result).
+fn resolution_to_entry(resolution: Resolution, url: &str) -> Result<Option<Entry>> {
+    match resolution {
+        Resolution::None => Ok(None),
+        Resolution::Credential(user) => {
+            let realm = parse_url(url)?.realm;
+            Ok(Some(Entry::new(&realm, &user)?))
+        }
+    }
+}
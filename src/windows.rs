@@ -38,27 +38,80 @@ use windows_sys::Win32::Foundation::{
     ERROR_NOT_FOUND, ERROR_NO_SUCH_LOGON_SESSION, FILETIME,
 };
 use windows_sys::Win32::Security::Credentials::{
-    CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CREDENTIAL_ATTRIBUTEW, CRED_FLAGS,
+    CredDeleteW, CredEnumerateW, CredFree, CredReadW, CredWriteW, CREDENTIALW,
+    CREDENTIAL_ATTRIBUTEW, CRED_FLAGS,
     CRED_MAX_CREDENTIAL_BLOB_SIZE, CRED_MAX_GENERIC_TARGET_NAME_LENGTH, CRED_MAX_STRING_LENGTH,
-    CRED_MAX_USERNAME_LENGTH, CRED_PERSIST_ENTERPRISE, CRED_TYPE_GENERIC,
+    CRED_MAX_USERNAME_LENGTH, CRED_MAX_VALUE_SIZE, CRED_PERSIST_ENTERPRISE,
+    CRED_PERSIST_LOCAL_MACHINE, CRED_PERSIST_SESSION, CRED_TYPE_GENERIC,
 };
 
+use std::collections::HashMap;
+
 use crate::Entry;
 
 use super::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
 use super::error::{Error as ErrorCode, Result};
 
+/// The scope for which a stored credential is persisted.
+///
+/// This maps directly to the `CRED_PERSIST_*` values understood by the
+/// Windows Credential Manager.  See the [Microsoft documentation][persist]
+/// for the precise semantics of each scope.
+///
+/// [persist]: https://learn.microsoft.com/en-us/windows/win32/api/wincred/ns-wincred-credentialw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Persistence {
+    /// The credential lasts only until the current logon session ends.
+    Session,
+    /// The credential is visible only on the local machine.
+    LocalMachine,
+    /// The credential may roam to other machines with the user's profile.
+    Enterprise,
+}
+
+impl Default for Persistence {
+    fn default() -> Self {
+        Persistence::Enterprise
+    }
+}
+
+impl Persistence {
+    fn to_persist(self) -> u32 {
+        match self {
+            Persistence::Session => CRED_PERSIST_SESSION,
+            Persistence::LocalMachine => CRED_PERSIST_LOCAL_MACHINE,
+            Persistence::Enterprise => CRED_PERSIST_ENTERPRISE,
+        }
+    }
+
+    fn from_persist(persist: u32) -> Self {
+        match persist {
+            CRED_PERSIST_SESSION => Persistence::Session,
+            CRED_PERSIST_LOCAL_MACHINE => Persistence::LocalMachine,
+            _ => Persistence::Enterprise,
+        }
+    }
+}
+
 /// The representation of a Windows Generic credential.
 ///
 /// See the module header for the meanings of these fields.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct WinCredential {
     pub username: String,
     pub target_name: String,
     pub target_alias: String,
     pub comment: String,
+    pub persist: Persistence,
+    /// Arbitrary application-defined attributes, each a keyword string mapped
+    /// to a value blob.  These are marshalled into `CREDENTIAL_ATTRIBUTEW`
+    /// entries on write and read back on [get_credential](WinCredential::get_credential).
+    pub attributes: HashMap<String, Vec<u8>>,
 }
 
+/// The maximum number of custom attributes a Generic credential may carry.
+const CRED_MAX_ATTRIBUTES: usize = 64;
+
 // Windows API type mappings:
 // DWORD is u32
 // LPCWSTR is *const u16
@@ -87,14 +140,21 @@ impl CredentialApi for WinCredential {
         let blob_len = blob.len() as u32;
         let flags = CRED_FLAGS::default();
         let cred_type = CRED_TYPE_GENERIC;
-        let persist = CRED_PERSIST_ENTERPRISE;
+        let persist = self.persist.to_persist();
         // Ignored by CredWriteW
         let last_written = FILETIME {
             dwLowDateTime: 0,
             dwHighDateTime: 0,
         };
-        let attribute_count = 0;
-        let attributes: *mut CREDENTIAL_ATTRIBUTEW = std::ptr::null_mut();
+        // The keyword and value allocations must outlive the `CredWriteW` call,
+        // because `attributes` holds raw pointers into them.
+        let (mut attributes, _keywords, _values) = self.marshal_attributes()?;
+        let attribute_count = attributes.len() as u32;
+        let attributes_ptr = if attributes.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            attributes.as_mut_ptr()
+        };
         let mut credential = CREDENTIALW {
             Flags: flags,
             Type: cred_type,
@@ -105,7 +165,7 @@ impl CredentialApi for WinCredential {
             CredentialBlob: blob.as_mut_ptr(),
             Persist: persist,
             AttributeCount: attribute_count,
-            Attributes: attributes,
+            Attributes: attributes_ptr,
             TargetAlias: target_alias.as_mut_ptr(),
             UserName: username.as_mut_ptr(),
         };
@@ -118,6 +178,58 @@ impl CredentialApi for WinCredential {
         }
     }
 
+    /// Create and write a credential with the given binary secret for this entry.
+    ///
+    /// Unlike [set_password](WinCredential::set_password), the secret is stored
+    /// in the `CredentialBlob` verbatim, with no UTF-16 round trip.  This allows
+    /// storing arbitrary byte sequences (symmetric keys, OAuth refresh tokens,
+    /// protobuf blobs) that aren't valid UTF-16 and need not have even length.
+    /// The trade-off is that such a secret can't be edited in the native Windows
+    /// credential UI, which assumes the blob is a UTF-16 string.
+    fn set_secret(&self, secret: &[u8]) -> Result<()> {
+        self.validate_secret(secret)?;
+        let mut username = to_wstr(&self.username);
+        let mut target_name = to_wstr(&self.target_name);
+        let mut target_alias = to_wstr(&self.target_alias);
+        let mut comment = to_wstr(&self.comment);
+        let mut blob = secret.to_vec();
+        let blob_len = blob.len() as u32;
+        let flags = CRED_FLAGS::default();
+        let cred_type = CRED_TYPE_GENERIC;
+        let persist = self.persist.to_persist();
+        // Ignored by CredWriteW
+        let last_written = FILETIME {
+            dwLowDateTime: 0,
+            dwHighDateTime: 0,
+        };
+        let (mut attributes, _keywords, _values) = self.marshal_attributes()?;
+        let attribute_count = attributes.len() as u32;
+        let attributes_ptr = if attributes.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            attributes.as_mut_ptr()
+        };
+        let mut credential = CREDENTIALW {
+            Flags: flags,
+            Type: cred_type,
+            TargetName: target_name.as_mut_ptr(),
+            Comment: comment.as_mut_ptr(),
+            LastWritten: last_written,
+            CredentialBlobSize: blob_len,
+            CredentialBlob: blob.as_mut_ptr(),
+            Persist: persist,
+            AttributeCount: attribute_count,
+            Attributes: attributes_ptr,
+            TargetAlias: target_alias.as_mut_ptr(),
+            UserName: username.as_mut_ptr(),
+        };
+        let p_credential: *const CREDENTIALW = &mut credential;
+        match unsafe { CredWriteW(p_credential, 0) } {
+            0 => Err(decode_error()),
+            _ => Ok(()),
+        }
+    }
+
     /// Look up the password for this entry, if any.
     ///
     /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no
@@ -126,11 +238,24 @@ impl CredentialApi for WinCredential {
         self.extract_from_platform(extract_password)
     }
 
+    /// Look up the binary secret for this entry, if any.
+    ///
+    /// Unlike [get_password](WinCredential::get_password), this returns the
+    /// `CredentialBlob` bytes verbatim with no UTF-16 decoding, so a secret
+    /// written by [set_secret](WinCredential::set_secret) round-trips losslessly
+    /// regardless of byte parity.
+    ///
+    /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no
+    /// credential in the store.
+    fn get_secret(&self) -> Result<Vec<u8>> {
+        self.extract_from_platform(extract_secret)
+    }
+
     /// Delete the underlying generic credential for this entry, if any.
     ///
     /// Returns a [NoEntry](ErrorCode::NoEntry) error if there is no
     /// credential in the store.
-    fn delete_password(&self) -> Result<()> {
+    fn delete_credential(&self) -> Result<()> {
         self.validate_attributes("")?;
         let target_name = to_wstr(&self.target_name);
         let cred_type = CRED_TYPE_GENERIC;
@@ -140,6 +265,16 @@ impl CredentialApi for WinCredential {
         }
     }
 
+    /// Read machine-readable [metadata](crate::CredentialMetadata) for this entry.
+    ///
+    /// The Windows `FILETIME` `LastWritten` value (100-ns intervals since
+    /// 1601-01-01 UTC) is converted into an [OffsetDateTime] so callers get a
+    /// machine-readable timestamp rather than the locale-dependent string the
+    /// old hand-rolled formatter produced.
+    fn get_metadata(&self) -> Result<crate::CredentialMetadata> {
+        self.extract_from_platform(extract_metadata)
+    }
+
     /// Return the underlying concrete object with an `Any` type so that it can
     /// be downgraded to a [WinCredential] for platform-specific processing.
     fn as_any(&self) -> &dyn std::any::Any {
@@ -190,6 +325,93 @@ impl WinCredential {
         Ok(())
     }
 
+    /// Validate the non-password attributes and the raw length of a binary secret.
+    ///
+    /// Binary secrets are stored in the `CredentialBlob` verbatim, so (unlike the
+    /// UTF-16 password path) their length limit is the raw byte count compared
+    /// directly against `CRED_MAX_CREDENTIAL_BLOB_SIZE`.
+    fn validate_secret(&self, secret: &[u8]) -> Result<()> {
+        self.validate_attributes("")?;
+        if secret.len() > CRED_MAX_CREDENTIAL_BLOB_SIZE as usize {
+            return Err(ErrorCode::TooLong(
+                String::from("secret"),
+                CRED_MAX_CREDENTIAL_BLOB_SIZE,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Marshal this credential's [attributes](WinCredential::attributes) into a
+    /// `Vec<CREDENTIAL_ATTRIBUTEW>` along with the keyword and value allocations
+    /// that back the raw pointers.  The caller must keep all three returned
+    /// vectors alive for the duration of the `CredWriteW` call.
+    fn marshal_attributes(&self) -> Result<(Vec<CREDENTIAL_ATTRIBUTEW>, Vec<Vec<u16>>, Vec<Vec<u8>>)> {
+        if self.attributes.len() > CRED_MAX_ATTRIBUTES {
+            return Err(ErrorCode::TooLong(
+                String::from("attributes"),
+                CRED_MAX_ATTRIBUTES as u32,
+            ));
+        }
+        let mut keywords: Vec<Vec<u16>> = Vec::with_capacity(self.attributes.len());
+        let mut values: Vec<Vec<u8>> = Vec::with_capacity(self.attributes.len());
+        for (keyword, value) in &self.attributes {
+            if value.len() > CRED_MAX_VALUE_SIZE as usize {
+                return Err(ErrorCode::TooLong(keyword.clone(), CRED_MAX_VALUE_SIZE));
+            }
+            keywords.push(to_wstr(keyword));
+            values.push(value.clone());
+        }
+        // Build the attribute structs only after both backing vectors are fully
+        // populated, so their buffers won't be reallocated out from under us.
+        let mut attributes = Vec::with_capacity(keywords.len());
+        for i in 0..keywords.len() {
+            attributes.push(CREDENTIAL_ATTRIBUTEW {
+                Keyword: keywords[i].as_ptr() as *mut u16,
+                Flags: 0,
+                ValueSize: values[i].len() as u32,
+                Value: values[i].as_ptr() as *mut u8,
+            });
+        }
+        Ok((attributes, keywords, values))
+    }
+
+    /// Migrate a credential written under a legacy target name to the canonical one.
+    ///
+    /// keyring-rs identifies Generic credentials by the `"{user}.{service}"`
+    /// target-name convention (or a caller-supplied target), so a secret written
+    /// by an older keyring version or a sibling tool (keytar, a plain `service`
+    /// target name, etc.) is invisible to [get_password](WinCredential::get_password).
+    ///
+    /// If the canonical target already has a credential, this is a no-op and
+    /// returns `Ok(false)`.  Otherwise each name in `alternates` is tried in
+    /// order; on the first hit the secret is rewritten under the canonical
+    /// target name and the legacy entry is deleted, returning `Ok(true)`.  If
+    /// none of the alternates has a credential either, a
+    /// [NoEntry](ErrorCode::NoEntry) error is returned.
+    pub fn migrate_from(&self, alternates: &[&str]) -> Result<bool> {
+        match self.get_password() {
+            Ok(_) => return Ok(false),
+            Err(ErrorCode::NoEntry) => {}
+            Err(err) => return Err(err),
+        }
+        for alternate in alternates {
+            let legacy = WinCredential {
+                target_name: alternate.to_string(),
+                ..self.clone()
+            };
+            match legacy.get_password() {
+                Ok(password) => {
+                    self.set_password(&password)?;
+                    legacy.delete_credential()?;
+                    return Ok(true);
+                }
+                Err(ErrorCode::NoEntry) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Err(ErrorCode::NoEntry)
+    }
+
     /// Construct a credential from this credential's underlying Generic credential.
     ///
     /// This can be useful for seeing modifications made by a third party.
@@ -239,11 +461,29 @@ impl WinCredential {
     }
 
     fn extract_credential(w_credential: &CREDENTIALW) -> Result<Self> {
+        let mut attributes = HashMap::new();
+        if !w_credential.Attributes.is_null() {
+            for i in 0..w_credential.AttributeCount as isize {
+                let attribute = unsafe { &*w_credential.Attributes.offset(i) };
+                let keyword = unsafe { from_wstr(attribute.Keyword) };
+                let value = if attribute.Value.is_null() || attribute.ValueSize == 0 {
+                    Vec::new()
+                } else {
+                    unsafe {
+                        std::slice::from_raw_parts(attribute.Value, attribute.ValueSize as usize)
+                            .to_vec()
+                    }
+                };
+                attributes.insert(keyword, value);
+            }
+        }
         Ok(Self {
             username: unsafe { from_wstr(w_credential.UserName) },
             target_name: unsafe { from_wstr(w_credential.TargetName) },
             target_alias: unsafe { from_wstr(w_credential.TargetAlias) },
             comment: unsafe { from_wstr(w_credential.Comment) },
+            persist: Persistence::from_persist(w_credential.Persist),
+            attributes,
         })
     }
 
@@ -258,6 +498,21 @@ impl WinCredential {
         target: Option<&str>,
         service: &str,
         user: &str,
+    ) -> Result<WinCredential> {
+        Self::new_with_target_and_persistence(target, service, user, None)
+    }
+
+    /// Create a credential for the given target, service, and user, anchored
+    /// to the given [persistence](Persistence) scope.
+    ///
+    /// Passing `None` for the persistence uses the platform default
+    /// ([Enterprise](Persistence::Enterprise)), matching the behavior of
+    /// [new_with_target](WinCredential::new_with_target).
+    pub fn new_with_target_and_persistence(
+        target: Option<&str>,
+        service: &str,
+        user: &str,
+        persist: Option<Persistence>,
     ) -> Result<WinCredential> {
         const VERSION: &str = env!("CARGO_PKG_VERSION");
         let metadata = format!("keyring-rs v{VERSION} for service '{service}', user '{user}'");
@@ -276,6 +531,8 @@ impl WinCredential {
                 target_name: target.to_string(),
                 target_alias: String::new(),
                 comment: metadata,
+                persist: persist.unwrap_or_default(),
+                attributes: HashMap::new(),
             }
         } else {
             Self {
@@ -291,6 +548,8 @@ impl WinCredential {
                 target_name: format!("{user}.{service}"),
                 target_alias: String::new(),
                 comment: metadata,
+                persist: persist.unwrap_or_default(),
+                attributes: HashMap::new(),
             }
         };
         credential.validate_attributes("")?;
@@ -299,21 +558,41 @@ impl WinCredential {
 }
 
 /// The builder for Windows Generic credentials.
-pub struct WinCredentialBuilder {}
+///
+/// The builder optionally carries a [persistence](Persistence) scope that is
+/// applied to every credential it builds.  A `None` scope uses the platform
+/// default (see [Persistence::default]).
+#[derive(Default)]
+pub struct WinCredentialBuilder {
+    persist: Option<Persistence>,
+}
 
 /// Returns an instance of the Windows credential builder.
 ///
 /// On Windows,
 /// this is called once when an entry is first created.
 pub fn default_credential_builder() -> Box<CredentialBuilder> {
-    Box::new(WinCredentialBuilder {})
+    Box::new(WinCredentialBuilder::default())
+}
+
+impl WinCredentialBuilder {
+    /// Returns a credential builder that anchors its credentials to the given
+    /// [persistence](Persistence) scope.
+    pub fn new_with_persistence(persist: Persistence) -> Box<CredentialBuilder> {
+        Box::new(WinCredentialBuilder {
+            persist: Some(persist),
+        })
+    }
 }
 
 impl CredentialBuilderApi for WinCredentialBuilder {
     /// Build a [WinCredential] for the given target, service, and user.
     fn build(&self, target: Option<&str>, service: &str, user: &str) -> Result<Box<Credential>> {
-        Ok(Box::new(WinCredential::new_with_target(
-            target, service, user,
+        Ok(Box::new(WinCredential::new_with_target_and_persistence(
+            target,
+            service,
+            user,
+            self.persist,
         )?))
     }
 
@@ -345,6 +624,55 @@ fn extract_password(credential: &CREDENTIALW) -> Result<String> {
     String::from_utf16(&blob_u16).map_err(|_| ErrorCode::BadEncoding(blob.to_vec()))
 }
 
+/// The number of 100-ns intervals between 1601-01-01 and 1970-01-01 (the Unix epoch).
+const FILETIME_TO_UNIX_EPOCH_INTERVALS: i128 = 116_444_736_000_000_000;
+
+fn filetime_to_datetime(filetime: &FILETIME) -> Option<time::OffsetDateTime> {
+    let intervals = ((filetime.dwHighDateTime as i128) << 32) | filetime.dwLowDateTime as i128;
+    if intervals == 0 {
+        return None;
+    }
+    let unix_nanos = (intervals - FILETIME_TO_UNIX_EPOCH_INTERVALS) * 100;
+    time::OffsetDateTime::from_unix_timestamp_nanos(unix_nanos).ok()
+}
+
+fn extract_metadata(credential: &CREDENTIALW) -> Result<crate::CredentialMetadata> {
+    let cred_type = match credential.Type {
+        CRED_TYPE_GENERIC => "Generic",
+        other => return Ok(unknown_type_metadata(credential, other)),
+    };
+    let persist = match Persistence::from_persist(credential.Persist) {
+        Persistence::Session => "Session",
+        Persistence::LocalMachine => "LocalMachine",
+        Persistence::Enterprise => "Enterprise",
+    };
+    Ok(crate::CredentialMetadata {
+        comment: Some(unsafe { from_wstr(credential.Comment) }),
+        persist: Some(persist.to_string()),
+        cred_type: Some(cred_type.to_string()),
+        last_written: filetime_to_datetime(&credential.LastWritten),
+    })
+}
+
+fn unknown_type_metadata(credential: &CREDENTIALW, cred_type: u32) -> crate::CredentialMetadata {
+    crate::CredentialMetadata {
+        comment: Some(unsafe { from_wstr(credential.Comment) }),
+        persist: None,
+        cred_type: Some(cred_type.to_string()),
+        last_written: filetime_to_datetime(&credential.LastWritten),
+    }
+}
+
+fn extract_secret(credential: &CREDENTIALW) -> Result<Vec<u8>> {
+    let blob_pointer: *const u8 = credential.CredentialBlob;
+    let blob_len: usize = credential.CredentialBlobSize as usize;
+    if blob_len == 0 {
+        return Ok(Vec::new());
+    }
+    let blob = unsafe { std::slice::from_raw_parts(blob_pointer, blob_len) };
+    Ok(blob.to_vec())
+}
+
 fn to_wstr(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(once(0)).collect()
 }
@@ -367,6 +695,165 @@ unsafe fn from_wstr(ws: *const u16) -> String {
     String::from_utf16_lossy(slice)
 }
 
+/// A structured result of a credential [search](search), one per matched
+/// Generic credential.
+///
+/// Unlike the legacy preformatted-string search output, each field is exposed
+/// directly so programmatic callers can sort, filter, or display them however
+/// they like.  `last_written` is the raw Windows `FILETIME` (100-ns intervals
+/// since 1601-01-01 UTC) packed into a `u64`; see the `metadata` accessor for
+/// a conversion to a machine-readable timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    pub target: String,
+    pub user: String,
+    pub comment: String,
+    pub persist: Persistence,
+    pub last_written: u64,
+    pub cred_type: u32,
+}
+
+fn to_search_result(w_credential: &CREDENTIALW) -> SearchResult {
+    let last_written = ((w_credential.LastWritten.dwHighDateTime as u64) << 32)
+        | w_credential.LastWritten.dwLowDateTime as u64;
+    SearchResult {
+        target: unsafe { from_wstr(w_credential.TargetName) },
+        user: unsafe { from_wstr(w_credential.UserName) },
+        comment: unsafe { from_wstr(w_credential.Comment) },
+        persist: Persistence::from_persist(w_credential.Persist),
+        last_written,
+        cred_type: w_credential.Type,
+    }
+}
+
+fn enumerate_search_results(filter: Option<&str>) -> Result<Vec<SearchResult>> {
+    let filter_w = filter.map(to_wstr);
+    let filter_ptr = filter_w
+        .as_ref()
+        .map_or(std::ptr::null(), |filter| filter.as_ptr());
+    let mut count: u32 = 0;
+    let mut credentials: *mut *mut CREDENTIALW = std::ptr::null_mut();
+    if unsafe { CredEnumerateW(filter_ptr, 0, &mut count, &mut credentials) } == 0 {
+        return Err(decode_error());
+    }
+    let mut results = Vec::new();
+    for i in 0..count as isize {
+        let p_credential = unsafe { *credentials.offset(i) };
+        if p_credential.is_null() {
+            continue;
+        }
+        let w_credential = unsafe { &*p_credential };
+        if w_credential.Type == CRED_TYPE_GENERIC {
+            results.push(to_search_result(w_credential));
+        }
+    }
+    unsafe { CredFree(credentials as *mut _) };
+    Ok(results)
+}
+
+/// Search for Generic credentials whose target, user, or comment contains `query`.
+///
+/// The match is a case-sensitive substring test; use [search_regex] for richer
+/// patterns.  Returns an empty vector when nothing matches.
+pub fn search(query: &str) -> Result<Vec<SearchResult>> {
+    Ok(enumerate_search_results(None)?
+        .into_iter()
+        .filter(|result| {
+            result.target.contains(query)
+                || result.user.contains(query)
+                || result.comment.contains(query)
+        })
+        .collect())
+}
+
+/// Search for Generic credentials whose target, user, or comment matches the
+/// regular expression `pattern`.
+///
+/// This lets callers write queries like `keyring-rs v.* for service 'foo'`
+/// rather than exact-name lookups.
+pub fn search_regex(pattern: &str) -> Result<Vec<SearchResult>> {
+    let regex = regex::Regex::new(pattern).map_err(|err| {
+        ErrorCode::Invalid("search pattern".to_string(), err.to_string())
+    })?;
+    Ok(enumerate_search_results(None)?
+        .into_iter()
+        .filter(|result| {
+            regex.is_match(&result.target)
+                || regex.is_match(&result.user)
+                || regex.is_match(&result.comment)
+        })
+        .collect())
+}
+
+/// Build a [WinCredential] from the `index`th entry of a [search] result set.
+pub fn from_search_results(results: &[SearchResult], index: usize) -> Result<WinCredential> {
+    let result = results.get(index).ok_or_else(|| {
+        ErrorCode::Invalid(
+            "search index".to_string(),
+            format!("no search result at index {index}"),
+        )
+    })?;
+    Ok(WinCredential {
+        username: result.user.clone(),
+        target_name: result.target.clone(),
+        target_alias: String::new(),
+        comment: result.comment.clone(),
+        persist: result.persist,
+        attributes: HashMap::new(),
+    })
+}
+
+/// Enumerate the Generic credentials in the Windows Credential Manager.
+///
+/// The optional `filter` is a target-name wildcard (e.g. `"user.service*"`);
+/// passing `None` enumerates all Generic credentials the current user can see.
+/// Only [CRED_TYPE_GENERIC] credentials are returned; credentials of other
+/// types are skipped.
+///
+/// Returns a [NoEntry](ErrorCode::NoEntry) error if no credential matches the
+/// filter, matching the behavior of [get_password](WinCredential::get_password).
+pub fn enumerate(filter: Option<&str>) -> Result<Vec<WinCredential>> {
+    let filter_w = filter.map(to_wstr);
+    let filter_ptr = filter_w
+        .as_ref()
+        .map_or(std::ptr::null(), |filter| filter.as_ptr());
+    let mut count: u32 = 0;
+    let mut credentials: *mut *mut CREDENTIALW = std::ptr::null_mut();
+    let result = unsafe { CredEnumerateW(filter_ptr, 0, &mut count, &mut credentials) };
+    if result == 0 {
+        // no allocation was done on failure, so there is nothing to free
+        return Err(decode_error());
+    }
+    let mut found = Vec::new();
+    let extract = (|| {
+        for i in 0..count as isize {
+            let p_credential = unsafe { *credentials.offset(i) };
+            if p_credential.is_null() {
+                continue;
+            }
+            let w_credential = unsafe { &*p_credential };
+            if w_credential.Type == CRED_TYPE_GENERIC {
+                found.push(WinCredential::extract_credential(w_credential)?);
+            }
+        }
+        Ok(())
+    })();
+    // Free the array (and the credentials it points at) regardless of outcome.
+    unsafe { CredFree(credentials as *mut _) };
+    extract.map(|()| found)
+}
+
+/// Enumerate the Generic credentials matching `filter` as [Entry] values.
+///
+/// This is a convenience wrapper around [enumerate] for callers that want to
+/// operate on the results through the cross-platform [Entry] API.
+pub fn enumerate_entries(filter: Option<&str>) -> Result<Vec<Entry>> {
+    Ok(enumerate(filter)?
+        .into_iter()
+        .map(|credential| Entry::new_with_credential(Box::new(credential)))
+        .collect())
+}
+
 pub fn entry_from_search(credential: &std::collections::HashMap<String, String>) -> Result<Entry> {
     let target = if let Some(target) = credential.get(&"Target".to_string()) {
         target
@@ -398,6 +885,8 @@ pub fn entry_from_search(credential: &std::collections::HashMap<String, String>)
         target_name: target.to_string(),
         target_alias: "".to_string(),
         comment: comment.to_string(),
+        persist: Persistence::default(),
+        attributes: HashMap::new(),
     });
 
     Ok(Entry::new_with_credential(wincredential))
@@ -443,12 +932,6 @@ fn wrap(code: u32) -> Box<dyn std::error::Error + Send + Sync> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
-
-    use windows_sys::Win32::Foundation::SYSTEMTIME;
-    use windows_sys::Win32::Storage::FileSystem::FileTimeToLocalFileTime;
-    use windows_sys::Win32::System::Time::{LocalFileTimeToLocalSystemTime, TIME_ZONE_INFORMATION};
-
     use super::*;
 
     use crate::credential::CredentialPersistence;
@@ -525,6 +1008,8 @@ mod tests {
             target_name: "target_name".to_string(),
             target_alias: "target_alias".to_string(),
             comment: "comment".to_string(),
+            persist: Persistence::default(),
+            attributes: HashMap::new(),
         };
         for (attr, len) in [
             ("user", CRED_MAX_USERNAME_LENGTH),
@@ -560,6 +1045,8 @@ mod tests {
             target_name: "target_name".to_string(),
             target_alias: "target_alias".to_string(),
             comment: "comment".to_string(),
+            persist: Persistence::default(),
+            attributes: HashMap::new(),
         };
 
         let len = CRED_MAX_CREDENTIAL_BLOB_SIZE / 2;
@@ -609,6 +1096,32 @@ mod tests {
         crate::tests::test_update(entry_new);
     }
 
+    #[test]
+    fn test_round_trip_random_secret() {
+        crate::tests::test_round_trip_random_secret(entry_new);
+    }
+
+    #[test]
+    fn test_round_trip_attributes() {
+        let name = generate_random_string();
+        let mut credential = WinCredential::new_with_target(None, &name, &name)
+            .expect("Can't create credential with attributes");
+        credential
+            .attributes
+            .insert("provenance".to_string(), b"keyring-rs".to_vec());
+        credential
+            .attributes
+            .insert("schema".to_string(), b"\x00\x01\x02".to_vec());
+        credential
+            .set_password("attribute password")
+            .expect("Can't set password with attributes");
+        let read = credential.get_credential().expect("Can't read credential");
+        assert_eq!(read.attributes, credential.attributes);
+        credential
+            .delete_credential()
+            .expect("Can't delete credential with attributes");
+    }
+
     #[test]
     fn test_get_credential() {
         let name = generate_random_string();
@@ -636,66 +1149,11 @@ mod tests {
         );
         assert_eq!(actual.comment, credential.comment, "Comments don't match");
         entry
-            .delete_password()
+            .delete_credential()
             .expect("Couldn't delete get-credential");
         assert!(matches!(entry.get_password(), Err(ErrorCode::NoEntry)));
     }
 
-    unsafe fn get_last_written(last_written: FILETIME) -> String {
-        static DAYS: [&str; 7] = [
-            "Monday",
-            "Tuesday",
-            "Wednesday",
-            "Thursday",
-            "Friday",
-            "Saturday",
-            "Sunday",
-        ];
-        static MONTHS: [&str; 12] = [
-            "January",
-            "February",
-            "March",
-            "April",
-            "May",
-            "June",
-            "July",
-            "August",
-            "September",
-            "October",
-            "November",
-            "December",
-        ];
-
-        let mut local_filetime: FILETIME = std::mem::zeroed();
-        let mut system_time: SYSTEMTIME = std::mem::zeroed();
-        let local: TIME_ZONE_INFORMATION = std::mem::zeroed();
-        FileTimeToLocalFileTime(&last_written, &mut local_filetime as *mut FILETIME);
-        LocalFileTimeToLocalSystemTime(
-            &local,
-            &local_filetime,
-            &mut system_time as *mut SYSTEMTIME,
-        );
-
-        let hour = system_time.wHour;
-        let minute = system_time.wMinute;
-        let second = system_time.wSecond;
-        let day = system_time.wDay;
-        let year = system_time.wYear;
-        let month = system_time.wMonth;
-        let day_of_week = system_time.wDayOfWeek;
-
-        format!(
-            "{}, {} {}, {} at {:02}:{:02}:{:02}",
-            DAYS[day_of_week as usize - 1],
-            day,
-            MONTHS[month as usize - 1],
-            year,
-            hour,
-            minute,
-            second
-        )
-    }
-
     #[test]
     fn test_search() {
         let name = generate_random_string();
@@ -726,31 +1184,24 @@ mod tests {
             CredFree(r_credential as *mut _);
             read_credential.LastWritten
         };
+        let last_written = ((last_written_filetime.dwHighDateTime as u64) << 32)
+            | last_written_filetime.dwLowDateTime as u64;
 
-        let search_result = Entry::search(&name);
-        let list = Entry::list_results(&search_result);
-
-        let cred_type = "Generic";
-        let persist = "Enterprise";
         const VERSION: &str = env!("CARGO_PKG_VERSION");
         let comment = format!("keyring-rs v{VERSION} for service '{name}', user '{name}'");
 
-        let expected = format!(
-            "1\nTarget: {}\nLast Written: {}\nType: {}\nPersist: {}\nUser: {}\nComment: {}\n",
-            target,
-            unsafe { get_last_written(last_written_filetime) },
-            cred_type,
-            persist,
-            &name,
-            comment
-        );
-
-        let expected: HashSet<&str> = expected.lines().collect();
-        let result: HashSet<&str> = list.lines().collect();
+        let results = search(&name).expect("Search failed");
+        assert_eq!(results.len(), 1, "Expected exactly one match for {name}");
+        let result = &results[0];
 
-        entry.delete_password().expect("Failed to delete password");
+        assert_eq!(result.target, target);
+        assert_eq!(result.user, name);
+        assert_eq!(result.comment, comment);
+        assert_eq!(result.persist, Persistence::Enterprise);
+        assert_eq!(result.cred_type, CRED_TYPE_GENERIC);
+        assert_eq!(result.last_written, last_written);
 
-        assert_eq!(expected, result);
+        entry.delete_credential().expect("Failed to delete password");
     }
 
     #[test]
@@ -767,10 +1218,11 @@ mod tests {
         let old_password = entry
             .get_password()
             .expect("failed to get password from old entry");
-        let results = &Entry::search(&name);
+        let results = search(&name).expect("Search failed");
 
-        let result_entry =
-            Entry::from_search_results(results, 1).expect("Failed to create entry from results");
+        let credential =
+            from_search_results(&results, 0).expect("Failed to create entry from results");
+        let result_entry = Entry::new_with_credential(Box::new(credential));
         result_entry
             .set_password(password2)
             .expect("error setting password2");
@@ -783,9 +1235,9 @@ mod tests {
         assert_eq!(password2, new_password);
 
         result_entry
-            .delete_password()
+            .delete_credential()
             .expect("Failed to delete new entry");
-        let e = entry.delete_password().unwrap_err();
+        let e = entry.delete_credential().unwrap_err();
 
         assert!(matches!(e, ErrorCode::NoEntry));
     }
@@ -20,7 +20,7 @@ fn test_empty_password_input() {
     let out_pass = entry.get_password().expect("Couldn't get empty password");
     assert_eq!(in_pass, out_pass);
     entry
-        .delete_password()
+        .delete_credential()
         .expect("Couldn't delete empty password");
     assert!(
         matches!(entry.get_password(), Err(Error::NoEntry)),
@@ -39,7 +39,7 @@ fn test_round_trip_ascii_password() {
     let stored_password = entry.get_password().expect("Couldn't get ascii password");
     assert_eq!(stored_password, password);
     entry
-        .delete_password()
+        .delete_credential()
         .expect("Couldn't delete ascii password");
     assert!(matches!(entry.get_password(), Err(Error::NoEntry)))
 }
@@ -57,7 +57,7 @@ fn test_round_trip_non_ascii_password() {
         .expect("Couldn't get non-ascii password");
     assert_eq!(stored_password, password);
     entry
-        .delete_password()
+        .delete_credential()
         .expect("Couldn't delete non-ascii password");
     assert!(matches!(entry.get_password(), Err(Error::NoEntry)))
 }
@@ -79,7 +79,7 @@ fn test_update() {
     let stored_password = entry.get_password().expect("Couldn't get second password");
     assert_eq!(stored_password, password);
     entry
-        .delete_password()
+        .delete_credential()
         .expect("Couldn't delete second password");
     assert!(matches!(entry.get_password(), Err(Error::NoEntry)))
 }
@@ -106,7 +106,7 @@ fn test_independent_credential_and_password() {
     assert_eq!(stored_password, password);
     assert_eq!(credential1, credential2);
     entry
-        .delete_password()
+        .delete_credential()
         .expect("Couldn't delete cred password");
     assert!(
         matches!(entry.get_password(), Err(Error::NoEntry)),
@@ -129,9 +129,9 @@ fn test_same_target() {
         .expect("Couldn't get 2nd entry password");
     assert_eq!(password2, password1);
     entry1
-        .delete_password()
+        .delete_credential()
         .expect("Couldn't delete 1st entry password");
-    assert!(matches!(entry2.delete_password(), Err(Error::NoEntry)))
+    assert!(matches!(entry2.delete_credential(), Err(Error::NoEntry)))
 }
 
 fn generate_random_string() -> String {
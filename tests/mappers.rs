@@ -19,9 +19,9 @@ fn test_reverse_mapper() {
         normal_password, backwards_password,
         "Normal and Backwards entry passwords don't match"
     );
-    normal_entry.delete_password().unwrap();
+    normal_entry.delete_credential().unwrap();
     assert!(
-        matches!(backwards_entry.delete_password(), Err(Error::NoEntry)),
+        matches!(backwards_entry.delete_credential(), Err(Error::NoEntry)),
         "Deleting Normal entry password didn't delete Backwards entry credential"
     )
 }
@@ -38,9 +38,9 @@ fn test_constant_mapper() {
         foo_password, bar_password,
         "Foo and Bar entry passwords don't match"
     );
-    foo_entry.delete_password().unwrap();
+    foo_entry.delete_credential().unwrap();
     assert!(
-        matches!(bar_entry.delete_password(), Err(Error::NoEntry)),
+        matches!(bar_entry.delete_credential(), Err(Error::NoEntry)),
         "Deleting Foo entry password didn't delete Bar entry credential"
     )
 }
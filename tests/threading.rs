@@ -27,7 +27,7 @@ fn test_create_then_move() {
             "Retrieved and set non-ascii passwords don't match"
         );
         entry
-            .delete_password()
+            .delete_credential()
             .expect("Can't delete non-ascii password");
         assert!(
             matches!(entry.get_password(), Err(Error::NoEntry)),
@@ -53,7 +53,7 @@ fn test_create_set_then_move() {
             "Retrieved and set ascii passwords don't match"
         );
         entry
-            .delete_password()
+            .delete_credential()
             .expect("Can't delete ascii password");
         assert!(
             matches!(entry.get_password(), Err(Error::NoEntry)),
@@ -78,7 +78,7 @@ fn test_simultaneous_create_set_move() {
                 "Retrieved and set ascii passwords don't match"
             );
             entry
-                .delete_password()
+                .delete_credential()
                 .expect("Can't delete ascii password");
             assert!(
                 matches!(entry.get_password(), Err(Error::NoEntry)),
@@ -106,7 +106,7 @@ fn test_simultaneous_independent_create_set() {
                 "Retrieved and set ascii passwords don't match"
             );
             entry
-                .delete_password()
+                .delete_credential()
                 .expect("Can't delete ascii password");
             assert!(
                 matches!(entry.get_password(), Err(Error::NoEntry)),